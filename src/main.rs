@@ -1,33 +1,133 @@
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::Local;
+use clap::{Parser, Subcommand};
 use env_logger::Builder;
 use log::{LevelFilter, info, warn};
 
+use udss_proxy_acl::blocklist_import::{
+    ListFormat, import_blocklist, parse_blocklist, set_category_enabled,
+};
 use udss_proxy_acl::domain_blocker::DomainBlocker;
+use udss_proxy_acl::monitor_report::MonitorReport;
 use udss_proxy_config::Settings;
 use udss_proxy_db::{initialize_db, initialize_dbpool};
-use udss_proxy_error::Result;
+use udss_proxy_error::{Result, config_err};
+use udss_proxy_logging::{RequestLogger, UpstreamTlsLogger};
 use udss_proxy_server::proxy_server::ProxyServer;
-use udss_proxy_tls::certs::{ensure_ssl_directories, init_root_ca, load_trusted_certificates};
+use udss_proxy_tls::certs::{
+    create_root_ca_files, ensure_ssl_directories, init_root_ca, load_trusted_certificates,
+    root_ca_exists,
+};
+use udss_proxy_tls::{CaBundle, Interceptor, UpstreamTls};
+
+/// 명령행 인자
+#[derive(Parser)]
+#[command(version, about = "UDSS 프록시 서버")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// 하위 명령 (없으면 서버 실행)
+#[derive(Subcommand)]
+enum Command {
+    /// 설정(ca)에 따라 새 루트 CA 생성
+    GenerateCa {
+        /// 기존 CA 파일 덮어쓰기
+        #[arg(long)]
+        force: bool,
+    },
+    /// 차단 목록 파일을 카테고리로 가져오기 (hosts, 도메인 목록, `||domain^`)
+    ImportBlocklist {
+        /// 카테고리 이름 (없으면 생성)
+        category: String,
+        /// 목록 파일 경로
+        file: PathBuf,
+        /// 목록 형식 (auto, hosts, domains, adblock)
+        #[arg(long, default_value = "auto")]
+        format: String,
+        /// 카테고리 설명
+        #[arg(long)]
+        description: Option<String>,
+        /// 카테고리의 기존 도메인을 지우고 교체
+        #[arg(long)]
+        replace: bool,
+    },
+    /// 카테고리 차단 활성화
+    EnableCategory {
+        /// 카테고리 이름
+        category: String,
+    },
+    /// 카테고리 차단 비활성화
+    DisableCategory {
+        /// 카테고리 이름
+        category: String,
+    },
+    /// 모니터 규칙 차단 예정 요청 보고서 (규칙별 영향받는 클라이언트, 호스트)
+    MonitorReport {
+        /// 집계 기간 (시간)
+        #[arg(long, default_value_t = 24)]
+        hours: i32,
+        /// 클라이언트, 호스트별 최대 항목 수
+        #[arg(long, default_value_t = 20)]
+        top: i64,
+    },
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
     // fd 세팅
     setup_resource_limits();
 
     // 로거 세팅
     setup_logger();
 
-    info!("udss-proxy 서버 시작");
-
     // 통합 설정 로드
     let mut settings = Settings::new()?;
 
     // SSL 디렉토리 확인 및 생성
     ensure_ssl_directories(&settings.proxy)?;
 
+    match cli.command {
+        Some(Command::GenerateCa { force }) => return generate_ca(&settings, force),
+        Some(Command::ImportBlocklist {
+            category,
+            file,
+            format,
+            description,
+            replace,
+        }) => {
+            let format = ListFormat::parse(&format)?;
+            return import_blocklist_file(
+                &settings,
+                &category,
+                &file,
+                format,
+                description.as_deref(),
+                replace,
+            )
+            .await;
+        }
+        Some(Command::EnableCategory { category }) => {
+            return set_category(&settings, &category, true).await;
+        }
+        Some(Command::DisableCategory { category }) => {
+            return set_category(&settings, &category, false).await;
+        }
+        Some(Command::MonitorReport { hours, top }) => {
+            return monitor_report(&settings, hours, top).await;
+        }
+        None => {}
+    }
+
+    info!("udss-proxy 서버 시작");
+
     // 신뢰할 인증서 로드
     load_trusted_certificates(&mut settings.proxy)?;
 
@@ -38,16 +138,137 @@ async fn main() -> Result<()> {
     let db_pool = initialize_dbpool(&settings.database).await?;
     initialize_db(&settings.database, &db_pool).await?;
 
-    let domain_blocker = Arc::new(DomainBlocker::new());
+    let domain_blocker = Arc::new(DomainBlocker::new(settings.proxy.cache_size));
     domain_blocker.init(&db_pool).await?;
+    domain_blocker.clone().spawn_reloader(
+        db_pool.clone(),
+        Duration::from_secs(settings.database.reload.poll_interval_seconds),
+    );
+    domain_blocker.clone().spawn_schedule_refresh();
+    domain_blocker.clone().spawn_hit_flusher(
+        db_pool.clone(),
+        Duration::from_secs(settings.database.rule_hits.flush_interval_seconds),
+    );
+
+    // 요청 로그 기록기
+    let request_logger = RequestLogger::start(db_pool.clone());
+    let upstream_tls_logger = UpstreamTlsLogger::start(db_pool.clone());
+
+    // 업스트림 TLS 정책 및 클라이언트 인증서
+    let upstream_tls = Arc::new(UpstreamTls::new(&settings.proxy)?);
+    upstream_tls.load_client_certs_from_db(&db_pool).await?;
+    upstream_tls.clone().spawn_reloader(
+        db_pool.clone(),
+        Duration::from_secs(settings.database.reload.poll_interval_seconds),
+    );
+
+    // CONNECT 터널 TLS 가로채기
+    let interceptor = if settings.proxy.tls_intercept {
+        let interceptor = Arc::new(Interceptor::new(&settings.proxy, db_pool.clone())?);
+        interceptor.pinning.load().await?;
+        interceptor
+            .pinning
+            .clone()
+            .spawn_refresh(Duration::from_secs(PINNING_BYPASS_REFRESH_SECS));
+        info!("TLS 가로채기 활성화");
+        Some(interceptor)
+    } else {
+        None
+    };
 
     // 서버 시작
-    let server = ProxyServer::new(settings.clone(), domain_blocker);
+    let server = ProxyServer::new(
+        settings.clone(),
+        domain_blocker,
+        request_logger,
+        upstream_tls,
+        upstream_tls_logger,
+        interceptor,
+    )?;
     server.run().await?;
 
     Ok(())
 }
 
+/// 새 루트 CA 생성
+fn generate_ca(settings: &Settings, force: bool) -> Result<()> {
+    if root_ca_exists(&settings.proxy) && !force {
+        return Err(config_err(format!(
+            "{} 에 CA 파일이 이미 있습니다. 교체하려면 --force 옵션을 사용하세요.",
+            settings.proxy.ssl_dir
+        )));
+    }
+
+    create_root_ca_files(&settings.proxy)?;
+    let bundle = CaBundle::load(&settings.proxy)?;
+    info!(
+        "새 루트 CA 생성 완료, 클라이언트에 다시 배포하세요 (SHA-256: {})",
+        bundle.fingerprint()
+    );
+    Ok(())
+}
+
+/// 차단 목록 파일 가져오기
+async fn import_blocklist_file(
+    settings: &Settings,
+    category: &str,
+    file: &Path,
+    format: ListFormat,
+    description: Option<&str>,
+    replace: bool,
+) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| config_err(format!("목록 파일 읽기 실패 {}: {e}", file.display())))?;
+    let parsed = parse_blocklist(&content, format);
+    info!(
+        "{} 파싱 완료: 도메인 {}개, 건너뛴 줄 {}개",
+        file.display(),
+        parsed.entries.len(),
+        parsed.skipped
+    );
+
+    let db_pool = initialize_dbpool(&settings.database).await?;
+    initialize_db(&settings.database, &db_pool).await?;
+
+    let source = file.file_name().map_or_else(
+        || file.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    let inserted = import_blocklist(
+        &db_pool,
+        category,
+        description,
+        &parsed.entries,
+        &source,
+        replace,
+    )
+    .await?;
+    info!("카테고리 {category}에 도메인 {inserted}개 추가");
+    Ok(())
+}
+
+/// 카테고리 차단 활성/비활성
+async fn set_category(settings: &Settings, category: &str, enabled: bool) -> Result<()> {
+    let db_pool = initialize_dbpool(&settings.database).await?;
+    set_category_enabled(&db_pool, category, enabled).await?;
+    info!(
+        "카테고리 {category} 차단 {}",
+        if enabled { "활성화" } else { "비활성화" }
+    );
+    Ok(())
+}
+
+/// 모니터 규칙 보고서 출력
+async fn monitor_report(settings: &Settings, hours: i32, top: i64) -> Result<()> {
+    let db_pool = initialize_dbpool(&settings.database).await?;
+    let report = MonitorReport::load(&db_pool, hours, top).await?;
+    print!("{report}");
+    Ok(())
+}
+
+/// 인증서 고정 우회 목록 DB 재로드 주기 (초)
+const PINNING_BYPASS_REFRESH_SECS: u64 = 60;
+
 /// 파일 디스크립터 제한 설정
 static FD_LIMIT: std::sync::LazyLock<u64> = std::sync::LazyLock::new(|| {
     std::env::var("FD_LIMIT")
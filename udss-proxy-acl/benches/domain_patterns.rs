@@ -0,0 +1,44 @@
+//! 도메인 패턴 매칭 벤치마크 (패턴별 `Regex` 순회 vs `RegexSet`)
+//!
+//! `cargo bench -p udss-proxy-acl`
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use regex::Regex;
+
+use udss_proxy_acl::pattern_set::PatternSet;
+
+const PATTERN_COUNT: usize = 10_000;
+
+fn patterns() -> Vec<String> {
+    (0..PATTERN_COUNT)
+        .map(|i| format!(r"^(.+\.)?tracker{i}\.(com|net)$"))
+        .collect()
+}
+
+fn bench_patterns(c: &mut Criterion) {
+    let patterns = patterns();
+    let regexes: Vec<Regex> = patterns.iter().map(|p| Regex::new(p).unwrap()).collect();
+    let (set, _) = PatternSet::compile(patterns.into_iter().map(|p| (p, ()))).unwrap();
+
+    // 대부분의 요청은 차단되지 않으므로 불일치 호스트가 주 경로
+    let miss = "www.example.com";
+    let hit = "ads.tracker9999.net";
+
+    let mut group = c.benchmark_group("domain_patterns_10k");
+    group.bench_function("regex_vec_miss", |b| {
+        b.iter(|| regexes.iter().any(|r| r.is_match(black_box(miss))))
+    });
+    group.bench_function("regex_set_miss", |b| {
+        b.iter(|| set.find_all(black_box(miss)))
+    });
+    group.bench_function("regex_vec_hit", |b| {
+        b.iter(|| regexes.iter().any(|r| r.is_match(black_box(hit))))
+    });
+    group.bench_function("regex_set_hit", |b| b.iter(|| set.find_all(black_box(hit))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_patterns);
+criterion_main!(benches);
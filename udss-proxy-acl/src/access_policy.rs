@@ -0,0 +1,69 @@
+use std::net::IpAddr;
+
+use ipnet::IpNet;
+
+use udss_proxy_config::AccessPolicyConfig;
+use udss_proxy_error::{Result, config_err};
+
+use crate::decision::{Action, Decision};
+
+/// 규칙에 일치하지 않는 요청의 기본 동작
+///
+/// 클라이언트 네트워크(가장 긴 프리픽스) > 리스너 > 전역 순으로 적용한다.
+#[derive(Debug, Clone)]
+pub struct AccessPolicy {
+    default_action: Action,
+    // 프리픽스 길이 내림차순
+    networks: Vec<(IpNet, Action)>,
+}
+
+impl AccessPolicy {
+    /// 설정으로 생성
+    pub fn new(config: &AccessPolicyConfig) -> Result<Self> {
+        let default_action = parse_action(&config.default_action)?;
+
+        let mut networks = Vec::with_capacity(config.networks.len());
+        for network in &config.networks {
+            let cidr = network
+                .cidr
+                .parse::<IpNet>()
+                .map_err(|e| config_err(format!("잘못된 네트워크 {}: {e}", network.cidr)))?;
+            networks.push((cidr.trunc(), parse_action(&network.default_action)?));
+        }
+        networks.sort_by_key(|(cidr, _)| std::cmp::Reverse(cidr.prefix_len()));
+
+        Ok(Self {
+            default_action,
+            networks,
+        })
+    }
+
+    /// 클라이언트에 적용할 기본 동작 (`listener`: 리스너별 설정)
+    pub fn default_action(&self, listener: Option<Action>, client_ip: IpAddr) -> Action {
+        // IPv4 매핑 IPv6 주소는 IPv4 네트워크로 비교
+        let client_ip = match client_ip {
+            IpAddr::V6(ip) => ip.to_ipv4_mapped().map_or(client_ip, IpAddr::V4),
+            ip => ip,
+        };
+        self.networks
+            .iter()
+            .find(|(cidr, _)| cidr.contains(&client_ip))
+            .map(|(_, action)| *action)
+            .or(listener)
+            .unwrap_or(self.default_action)
+    }
+
+    /// 일치하는 규칙이 없을 때의 판정 (기본 허용이면 None)
+    pub fn fallback(action: Action) -> Option<Decision> {
+        (action == Action::Block)
+            .then(|| Decision::default_policy(action, "no allow rule matched".to_string()))
+    }
+}
+
+/// 설정 `default_action` 값 변환
+pub fn parse_action(value: &str) -> Result<Action> {
+    // 기본 동작은 허용, 차단만 가능
+    Action::parse(value)
+        .filter(|action| matches!(action, Action::Allow | Action::Block))
+        .ok_or_else(|| config_err(format!("알 수 없는 기본 동작: {value} (allow, block)")))
+}
@@ -1 +1,79 @@
+use crate::decision::Decision;
+use crate::warn::CONTINUE_PATH;
 
+/// 차단 안내 페이지 HTML
+pub fn render(url: &str, message: &str, decision: &Decision) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="ko">
+<head>
+<meta charset="utf-8">
+<title>접근 차단</title>
+</head>
+<body>
+<h1>접근이 차단되었습니다</h1>
+<p>{message}</p>
+<p>URL: <code>{url}</code></p>
+<p>사유: <code>{decision}</code></p>
+<p>업무상 필요한 경우 관리자에게 위 정보를 전달하세요.</p>
+</body>
+</html>
+"#,
+        message = escape_html(message),
+        url = escape_html(url),
+        decision = escape_html(&decision.to_string()),
+    )
+}
+
+/// 경고 안내 페이지 HTML (계속 진행 버튼은 같은 호스트의 `CONTINUE_PATH`로 이동)
+pub fn render_warning(
+    url: &str,
+    message: &str,
+    decision: &Decision,
+    rule_id: i64,
+    token: &str,
+) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="ko">
+<head>
+<meta charset="utf-8">
+<title>접속 주의</title>
+</head>
+<body>
+<h1>접속에 주의가 필요한 사이트입니다</h1>
+<p>{message}</p>
+<p>URL: <code>{url}</code></p>
+<p>사유: <code>{decision}</code></p>
+<p>업무상 필요한 경우에만 계속 진행하세요. 접속 기록은 남습니다.</p>
+<form method="get" action="{CONTINUE_PATH}">
+<input type="hidden" name="rule" value="{rule_id}">
+<input type="hidden" name="token" value="{token}">
+<input type="hidden" name="url" value="{url}">
+<button type="submit">계속 진행</button>
+</form>
+</body>
+</html>
+"#,
+        message = escape_html(message),
+        url = escape_html(url),
+        decision = escape_html(&decision.to_string()),
+        token = escape_html(token),
+    )
+}
+
+/// HTML 특수문자 치환
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,341 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
+
+use log::{debug, info};
+
+use udss_proxy_db::DatabasePool;
+use udss_proxy_db::{category_domains, domain_categories, rule_audit_log};
+use udss_proxy_error::{Result, config_err};
+
+use crate::domain_trie::MatchType;
+use crate::host::normalize_host;
+
+/// 한 번에 넣는 도메인 수
+const INSERT_BATCH_SIZE: usize = 5_000;
+
+/// hosts 파일의 로컬 항목 (차단 대상 아님)
+const HOSTS_LOCAL_NAMES: [&str; 6] = [
+    "localhost",
+    "localhost.localdomain",
+    "local",
+    "broadcasthost",
+    "ip6-localhost",
+    "ip6-loopback",
+];
+
+/// 차단 목록 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFormat {
+    /// 줄마다 형식 자동 판별
+    Auto,
+    /// hosts 파일 (`0.0.0.0 ads.example.com`)
+    Hosts,
+    /// 도메인 목록 (`ads.example.com`, `*.example.com`, `.example.com`)
+    Domains,
+    /// Adblock 규칙 (`||ads.example.com^`)
+    Adblock,
+}
+
+impl ListFormat {
+    /// 명령행 값 변환
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "auto" => Ok(Self::Auto),
+            "hosts" => Ok(Self::Hosts),
+            "domains" => Ok(Self::Domains),
+            "adblock" => Ok(Self::Adblock),
+            _ => Err(config_err(format!(
+                "알 수 없는 목록 형식: {value} (auto, hosts, domains, adblock)"
+            ))),
+        }
+    }
+}
+
+/// 파싱 결과
+#[derive(Debug, Default)]
+pub struct ParsedBlocklist {
+    /// 도메인과 일치 방식 (중복 제거)
+    pub entries: Vec<(String, MatchType)>,
+    /// 해석하지 못했거나 지원하지 않는 줄 수
+    pub skipped: usize,
+}
+
+/// 차단 목록 파일 파싱
+pub fn parse_blocklist(content: &str, format: ListFormat) -> ParsedBlocklist {
+    let mut seen = HashSet::new();
+    let mut parsed = ParsedBlocklist::default();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with('!')
+            || line.starts_with('[')
+        {
+            continue;
+        }
+
+        let line_format = match format {
+            ListFormat::Auto if line.starts_with("||") => ListFormat::Adblock,
+            ListFormat::Auto
+                if line
+                    .split_whitespace()
+                    .next()
+                    .is_some_and(|token| token.parse::<IpAddr>().is_ok()) =>
+            {
+                ListFormat::Hosts
+            }
+            ListFormat::Auto => ListFormat::Domains,
+            format => format,
+        };
+
+        let entries = match line_format {
+            ListFormat::Hosts => parse_hosts_line(line),
+            ListFormat::Adblock => parse_adblock_line(line).into_iter().collect(),
+            ListFormat::Domains | ListFormat::Auto => parse_domain_line(line).into_iter().collect(),
+        };
+        if entries.is_empty() {
+            debug!("차단 목록 줄 건너뜀: {line}");
+            parsed.skipped += 1;
+            continue;
+        }
+
+        for entry in entries {
+            if seen.insert(entry.clone()) {
+                parsed.entries.push(entry);
+            }
+        }
+    }
+
+    parsed
+}
+
+/// 카테고리에 차단 목록 가져오기 (새로 추가된 도메인 수 반환)
+///
+/// 하나의 트랜잭션으로 처리하므로 차단 목록 재로드 알림은 커밋 시 한 번 발생한다.
+pub async fn import_blocklist(
+    pool: &DatabasePool,
+    category: &str,
+    description: Option<&str>,
+    entries: &[(String, MatchType)],
+    source: &str,
+    replace: bool,
+) -> Result<u64> {
+    let actor = format!("import:{source}");
+    let mut conn = pool.get_connection().await?;
+    let transaction = conn.transaction().await?;
+    transaction
+        .execute(rule_audit_log::SET_ACTOR, &[&actor])
+        .await?;
+
+    let row = transaction
+        .query_one(domain_categories::UPSERT, &[&category, &description])
+        .await?;
+    let category_id: i64 = row.get(0);
+
+    let mut deleted = 0;
+    if replace {
+        deleted = transaction
+            .execute(category_domains::DELETE_BY_CATEGORY, &[&category_id])
+            .await?;
+        info!("카테고리 {category} 기존 도메인 {deleted}개 삭제");
+    }
+
+    let mut inserted = 0;
+    for chunk in entries.chunks(INSERT_BATCH_SIZE) {
+        let domains: Vec<&str> = chunk.iter().map(|(domain, _)| domain.as_str()).collect();
+        let match_types: Vec<String> = chunk
+            .iter()
+            .map(|(_, match_type)| match_type.to_string())
+            .collect();
+        inserted += transaction
+            .execute(
+                category_domains::INSERT_BATCH,
+                &[&category_id, &domains, &match_types, &source],
+            )
+            .await?;
+    }
+
+    // 도메인별 기록 대신 가져오기 요약 기록
+    let count = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+    transaction
+        .execute(
+            rule_audit_log::INSERT_IMPORT_SUMMARY,
+            &[
+                &category_id,
+                &category,
+                &source,
+                &replace,
+                &count(deleted),
+                &count(inserted),
+                &actor,
+            ],
+        )
+        .await?;
+
+    transaction.commit().await?;
+    Ok(inserted)
+}
+
+/// 카테고리 활성/비활성
+pub async fn set_category_enabled(
+    pool: &DatabasePool,
+    category: &str,
+    enabled: bool,
+) -> Result<()> {
+    let conn = pool.get_connection().await?;
+    let updated = conn
+        .execute(domain_categories::SET_ENABLED, &[&category, &enabled])
+        .await?;
+    if updated == 0 {
+        return Err(config_err(format!("카테고리가 없습니다: {category}")));
+    }
+    Ok(())
+}
+
+/// hosts 파일 줄 (`<IP> <호스트>...`)
+fn parse_hosts_line(line: &str) -> Vec<(String, MatchType)> {
+    let line = line.split('#').next().unwrap_or_default();
+    let mut tokens = line.split_whitespace();
+    if tokens.next().is_none_or(|ip| ip.parse::<IpAddr>().is_err()) {
+        return Vec::new();
+    }
+    tokens
+        .filter(|host| !HOSTS_LOCAL_NAMES.contains(&host.to_ascii_lowercase().as_str()))
+        .filter_map(normalize_domain)
+        .map(|domain| (domain, MatchType::Exact))
+        .collect()
+}
+
+/// 도메인 목록 줄 (`*.`, `.` 접두어 허용)
+fn parse_domain_line(line: &str) -> Option<(String, MatchType)> {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    let (domain, match_type) = MatchType::split_rule(line, MatchType::Exact);
+    normalize_domain(domain).map(|domain| (domain, match_type))
+}
+
+/// Adblock 도메인 규칙 (`||domain^`, 옵션이 붙은 규칙과 예외 규칙은 제외)
+fn parse_adblock_line(line: &str) -> Option<(String, MatchType)> {
+    let rule = line.strip_prefix("||")?;
+    let domain = rule.strip_suffix('^').unwrap_or(rule);
+    // 경로나 옵션이 있는 규칙은 도메인 단위로 표현할 수 없음
+    if domain.contains(['^', '$', '/', '*', '|']) {
+        return None;
+    }
+    normalize_domain(domain).map(|domain| (domain, MatchType::Suffix))
+}
+
+/// 도메인 검증 및 정규화 (국제화 도메인은 퓨니코드로 저장)
+fn normalize_domain(domain: &str) -> Option<String> {
+    let domain = normalize_host(domain).into_owned();
+    let valid = !domain.is_empty()
+        && domain.len() <= 253
+        && domain.parse::<IpAddr>().is_err()
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        });
+    valid.then_some(domain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(content: &str, format: ListFormat) -> Vec<(String, MatchType)> {
+        parse_blocklist(content, format).entries
+    }
+
+    fn exact(domain: &str) -> (String, MatchType) {
+        (domain.to_string(), MatchType::Exact)
+    }
+
+    #[test]
+    fn hosts_line_with_several_names_and_comment() {
+        let parsed = entries(
+            "0.0.0.0 ads.example.com Tracker.Example.com # 광고\n\
+             127.0.0.1\tstats.example.net#comment\n",
+            ListFormat::Auto,
+        );
+        assert_eq!(
+            parsed,
+            [
+                exact("ads.example.com"),
+                exact("tracker.example.com"),
+                exact("stats.example.net"),
+            ]
+        );
+    }
+
+    #[test]
+    fn hosts_local_and_address_names_are_skipped() {
+        let parsed = parse_blocklist(
+            "0.0.0.0 0.0.0.0\n\
+             127.0.0.1 localhost\n\
+             ::1 ip6-localhost ip6-loopback\n\
+             255.255.255.255 broadcasthost\n\
+             0.0.0.0 localhost ads.example.com\n",
+            ListFormat::Hosts,
+        );
+        assert_eq!(parsed.entries, [exact("ads.example.com")]);
+        assert_eq!(parsed.skipped, 4);
+    }
+
+    #[test]
+    fn adblock_options_and_exceptions_are_skipped() {
+        let parsed = parse_blocklist(
+            "[Adblock Plus 2.0]\n\
+             ! 주석\n\
+             ||ads.example.com^\n\
+             ||tracker.example.com^$third-party\n\
+             @@||allowed.example.com^\n\
+             ||example.org/banner^\n",
+            ListFormat::Adblock,
+        );
+        assert_eq!(
+            parsed.entries,
+            [("ads.example.com".to_string(), MatchType::Suffix)]
+        );
+        assert_eq!(parsed.skipped, 3);
+
+        // 자동 판별에서도 예외 규칙은 도메인으로 해석하지 않음
+        let parsed = parse_blocklist("@@||allowed.example.com^\n", ListFormat::Auto);
+        assert!(parsed.entries.is_empty());
+        assert_eq!(parsed.skipped, 1);
+    }
+
+    #[test]
+    fn domain_prefixes_select_match_type() {
+        let parsed = entries(
+            "*.wild.example.com\n.suffix.example.com\nexact.example.com\nexact.example.com.\n",
+            ListFormat::Domains,
+        );
+        assert_eq!(
+            parsed,
+            [
+                ("wild.example.com".to_string(), MatchType::Wildcard),
+                ("suffix.example.com".to_string(), MatchType::Suffix),
+                exact("exact.example.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn idn_is_stored_as_punycode() {
+        let parsed = entries(
+            "0.0.0.0 광고.한국\n*.Bücher.example\n||例え.テスト^\n",
+            ListFormat::Auto,
+        );
+        assert_eq!(
+            parsed,
+            [
+                exact("xn--299aqd.xn--3e0b707e"),
+                ("xn--bcher-kva.example".to_string(), MatchType::Wildcard),
+                ("xn--r8jz45g.xn--zckzah".to_string(), MatchType::Suffix),
+            ]
+        );
+    }
+}
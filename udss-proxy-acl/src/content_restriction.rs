@@ -0,0 +1,335 @@
+use udss_proxy_config::ContentRestrictionConfig;
+use udss_proxy_error::{Result, config_err};
+
+use crate::domain_trie::MatchType;
+use crate::host::normalize_host;
+
+/// Google 세이프서치 전용 엔드포인트
+const GOOGLE_SAFESEARCH_ENDPOINT: &str = "forcesafesearch.google.com";
+/// Google 검색 세이프서치 적용 경로
+const GOOGLE_SEARCH_PATHS: [&str; 4] = ["/search", "/webhp", "/images", "/complete/search"];
+
+/// Bing 세이프서치 전용 엔드포인트
+const BING_SAFESEARCH_ENDPOINT: &str = "strict.bing.com";
+const BING_HOSTS: [&str; 2] = ["www.bing.com", "bing.com"];
+/// Bing 검색 세이프서치 적용 경로
+const BING_SEARCH_PATHS: [&str; 3] = ["/search", "/images/search", "/videos/search"];
+
+/// YouTube 제한 모드 적용 호스트 (웹, 모바일, 앱 API)
+const YOUTUBE_HOSTS: [&str; 5] = [
+    "www.youtube.com",
+    "m.youtube.com",
+    "youtubei.googleapis.com",
+    "youtube.googleapis.com",
+    "www.youtube-nocookie.com",
+];
+
+/// Microsoft 365 로그인 호스트
+const MICROSOFT_LOGIN_HOSTS: [&str; 3] = [
+    "login.microsoftonline.com",
+    "login.microsoft.com",
+    "login.windows.net",
+];
+
+/// YouTube 제한 모드
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum YoutubeMode {
+    Strict,
+    Moderate,
+}
+
+impl YoutubeMode {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "strict" => Some(Self::Strict),
+            "moderate" => Some(Self::Moderate),
+            _ => None,
+        }
+    }
+
+    /// `YouTube-Restrict` 헤더 값
+    fn header_value(self) -> &'static str {
+        match self {
+            Self::Strict => "Strict",
+            Self::Moderate => "Moderate",
+        }
+    }
+
+    /// 제한 모드 전용 엔드포인트
+    fn endpoint(self) -> &'static str {
+        match self {
+            Self::Strict => "restrict.youtube.com",
+            Self::Moderate => "restrictmoderate.youtube.com",
+        }
+    }
+}
+
+/// 세이프서치, 테넌트 제한 강제
+///
+/// 쿼리 파라미터와 헤더는 평문 HTTP, 가로챈 HTTPS 요청에 적용하고,
+/// 내용을 볼 수 없는 터널은 공급자가 제공하는 제한 전용 엔드포인트로 연결한다.
+#[derive(Debug, Clone, Default)]
+pub struct ContentRestrictions {
+    google_safesearch: bool,
+    bing_safesearch: bool,
+    youtube: Option<YoutubeMode>,
+    // (`Restrict-Access-To-Tenants`, `Restrict-Access-Context`)
+    microsoft_tenants: Option<(String, String)>,
+    // `X-GoogApps-Allowed-Domains`
+    google_workspace_domains: Option<String>,
+}
+
+impl ContentRestrictions {
+    /// 설정으로 생성
+    pub fn new(config: &ContentRestrictionConfig) -> Result<Self> {
+        let youtube = match config.youtube_restrict.as_deref() {
+            Some(mode) => Some(YoutubeMode::parse(mode).ok_or_else(|| {
+                config_err(format!(
+                    "알 수 없는 YouTube 제한 모드: {mode} (strict, moderate)"
+                ))
+            })?),
+            None => None,
+        };
+
+        let microsoft_tenants = match &config.microsoft_tenants {
+            Some(restriction) => {
+                let tenants = join_values(&restriction.allowed_tenants);
+                let context = restriction.context_tenant_id.trim();
+                if tenants.is_empty() || context.is_empty() {
+                    return Err(config_err(
+                        "Microsoft 테넌트 제한에는 allowed_tenants, context_tenant_id가 필요합니다",
+                    ));
+                }
+                Some((tenants, context.to_string()))
+            }
+            None => None,
+        };
+
+        let google_workspace_domains = Some(join_values(&config.google_workspace_domains))
+            .filter(|domains| !domains.is_empty());
+
+        Ok(Self {
+            google_safesearch: config.google_safesearch,
+            bing_safesearch: config.bing_safesearch,
+            youtube,
+            microsoft_tenants,
+            google_workspace_domains,
+        })
+    }
+
+    /// 검색 요청의 세이프서치 파라미터 강제 (변경이 없으면 None)
+    pub fn rewrite_query(&self, host: &str, path_and_query: &str) -> Option<String> {
+        let host = normalize_host(host);
+        let path = path_and_query.split('?').next().unwrap_or_default();
+        let (name, value) = if self.google_safesearch
+            && is_google_search_host(&host)
+            && GOOGLE_SEARCH_PATHS.contains(&path)
+        {
+            ("safe", "active")
+        } else if self.bing_safesearch
+            && BING_HOSTS.contains(&host.as_ref())
+            && BING_SEARCH_PATHS
+                .iter()
+                .any(|search| path.eq_ignore_ascii_case(search))
+        {
+            ("adlt", "strict")
+        } else {
+            return None;
+        };
+
+        let rewritten = set_query_param(path_and_query, name, value);
+        (rewritten != path_and_query).then_some(rewritten)
+    }
+
+    /// 요청에 넣을 공급자 헤더 (소문자 이름, 값)
+    pub fn request_headers(&self, host: &str) -> Vec<(&'static str, String)> {
+        let host = normalize_host(host);
+        let mut headers = Vec::new();
+        if let Some(mode) = self.youtube
+            && YOUTUBE_HOSTS.contains(&host.as_ref())
+        {
+            headers.push(("youtube-restrict", mode.header_value().to_string()));
+        }
+        if let Some((tenants, context)) = &self.microsoft_tenants
+            && MICROSOFT_LOGIN_HOSTS.contains(&host.as_ref())
+        {
+            headers.push(("restrict-access-to-tenants", tenants.clone()));
+            headers.push(("restrict-access-context", context.clone()));
+        }
+        if let Some(domains) = &self.google_workspace_domains
+            && MatchType::Suffix.matches("google.com", &host)
+        {
+            headers.push(("x-googapps-allowed-domains", domains.clone()));
+        }
+        headers
+    }
+
+    /// 가로채지 않은 터널의 연결 대상 (제한 전용 엔드포인트, 해당 없으면 None)
+    pub fn restricted_endpoint(&self, host: &str) -> Option<&'static str> {
+        let host = normalize_host(host);
+        if self.google_safesearch && is_google_search_host(&host) {
+            Some(GOOGLE_SAFESEARCH_ENDPOINT)
+        } else if self.bing_safesearch && BING_HOSTS.contains(&host.as_ref()) {
+            Some(BING_SAFESEARCH_ENDPOINT)
+        } else if let Some(mode) = self.youtube
+            && YOUTUBE_HOSTS.contains(&host.as_ref())
+        {
+            Some(mode.endpoint())
+        } else {
+            None
+        }
+    }
+}
+
+/// Google 검색 호스트 (`www.google.com`, `google.co.kr` 등 국가별 도메인 포함)
+fn is_google_search_host(host: &str) -> bool {
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    host.strip_prefix("google.").is_some_and(|tld| {
+        let labels = tld.split('.').count();
+        (1..=2).contains(&labels) && tld.split('.').all(|label| (2..=3).contains(&label.len()))
+    })
+}
+
+/// 쉼표로 연결 (빈 값 제외)
+fn join_values(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// 쿼리 파라미터 설정 (같은 이름의 기존 값은 모두 제거)
+fn set_query_param(path_and_query: &str, name: &str, value: &str) -> String {
+    let (path, query) = path_and_query
+        .split_once('?')
+        .unwrap_or((path_and_query, ""));
+    let mut pairs: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(name))
+        .collect();
+    let param = format!("{name}={value}");
+    pairs.push(&param);
+    format!("{path}?{}", pairs.join("&"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restrictions(setup: impl FnOnce(&mut ContentRestrictionConfig)) -> ContentRestrictions {
+        let mut config = ContentRestrictionConfig::default();
+        setup(&mut config);
+        ContentRestrictions::new(&config).unwrap()
+    }
+
+    #[test]
+    fn google_query_replaces_existing_safe_param() {
+        let restrictions = restrictions(|config| config.google_safesearch = true);
+        assert_eq!(
+            restrictions.rewrite_query("www.google.com", "/search?q=rust&safe=off&hl=ko"),
+            Some("/search?q=rust&hl=ko&safe=active".to_string())
+        );
+        assert_eq!(
+            restrictions.rewrite_query("WWW.Google.co.kr.", "/search?safe=off&safe=images"),
+            Some("/search?safe=active".to_string())
+        );
+        assert_eq!(
+            restrictions.rewrite_query("google.com", "/images"),
+            Some("/images?safe=active".to_string())
+        );
+        // 이미 적용된 요청, 검색 외 경로, 다른 호스트는 변경하지 않음
+        assert_eq!(
+            restrictions.rewrite_query("www.google.com", "/search?q=rust&safe=active"),
+            None
+        );
+        assert_eq!(
+            restrictions.rewrite_query("www.google.com", "/maps?safe=off"),
+            None
+        );
+        assert_eq!(
+            restrictions.rewrite_query("mail.google.com", "/search?q=a"),
+            None
+        );
+        // 비슷한 이름의 파라미터는 유지
+        assert_eq!(
+            restrictions.rewrite_query("www.google.com", "/search?safety=off"),
+            Some("/search?safety=off&safe=active".to_string())
+        );
+    }
+
+    #[test]
+    fn bing_query_replaces_existing_adult_param() {
+        let restrictions = restrictions(|config| config.bing_safesearch = true);
+        assert_eq!(
+            restrictions.rewrite_query("www.bing.com", "/search?q=rust&adlt=off"),
+            Some("/search?q=rust&adlt=strict".to_string())
+        );
+        assert_eq!(
+            restrictions.rewrite_query("bing.com", "/Images/Search?adlt=moderate&q=a"),
+            Some("/Images/Search?q=a&adlt=strict".to_string())
+        );
+        assert_eq!(
+            restrictions.rewrite_query("www.bing.com", "/maps?q=a"),
+            None
+        );
+        // Google 세이프서치가 꺼져 있으면 Google 요청은 그대로
+        assert_eq!(
+            restrictions.rewrite_query("www.google.com", "/search?q=a"),
+            None
+        );
+    }
+
+    #[test]
+    fn youtube_mode_selects_header_and_endpoint() {
+        let strict = restrictions(|config| config.youtube_restrict = Some("strict".into()));
+        let moderate = restrictions(|config| config.youtube_restrict = Some("moderate".into()));
+
+        assert_eq!(
+            strict.restricted_endpoint("www.youtube.com"),
+            Some("restrict.youtube.com")
+        );
+        assert_eq!(
+            moderate.restricted_endpoint("m.youtube.com"),
+            Some("restrictmoderate.youtube.com")
+        );
+        assert_eq!(
+            strict.request_headers("youtubei.googleapis.com"),
+            [("youtube-restrict", "Strict".to_string())]
+        );
+        assert_eq!(
+            moderate.request_headers("www.youtube.com"),
+            [("youtube-restrict", "Moderate".to_string())]
+        );
+        // YouTube 외 호스트는 제한하지 않음
+        assert_eq!(strict.restricted_endpoint("www.google.com"), None);
+        assert!(strict.request_headers("music.example.com").is_empty());
+    }
+
+    #[test]
+    fn unknown_youtube_mode_is_rejected() {
+        let config = ContentRestrictionConfig {
+            youtube_restrict: Some("off".into()),
+            ..ContentRestrictionConfig::default()
+        };
+        assert!(ContentRestrictions::new(&config).is_err());
+    }
+
+    #[test]
+    fn search_endpoints_for_tunnels() {
+        let restrictions = restrictions(|config| {
+            config.google_safesearch = true;
+            config.bing_safesearch = true;
+        });
+        assert_eq!(
+            restrictions.restricted_endpoint("www.google.de"),
+            Some(GOOGLE_SAFESEARCH_ENDPOINT)
+        );
+        assert_eq!(
+            restrictions.restricted_endpoint("www.bing.com"),
+            Some(BING_SAFESEARCH_ENDPOINT)
+        );
+        assert_eq!(restrictions.restricted_endpoint("google.example.com"), None);
+    }
+}
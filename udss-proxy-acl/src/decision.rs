@@ -0,0 +1,371 @@
+use std::fmt;
+
+use crate::domain_trie::MatchType;
+
+/// 규칙 동작
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    /// 허용
+    Allow,
+    /// 경고 페이지 표시 (사용자가 계속 진행하면 일정 시간 허용)
+    Warn,
+    /// 차단
+    Block,
+    /// 허용하되 차단 예정으로 기록 (새 규칙 사전 점검용)
+    Monitor,
+}
+
+impl Action {
+    /// DB `action` 값 변환
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "block" => Some(Self::Block),
+            "monitor" => Some(Self::Monitor),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Block => "block",
+            Self::Monitor => "monitor",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 판정에 사용된 규칙 종류 (같은 조건이면 뒤쪽이 우선)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RuleType {
+    /// 일치하는 규칙이 없을 때의 기본 동작
+    Default,
+    /// `client_networks` 클라이언트 네트워크
+    Client,
+    /// `ip_blocks` 목적지 IP/CIDR
+    Ip,
+    /// `domain_pattern_blocks` 정규식
+    Pattern,
+    /// `category_domains` 카테고리 도메인
+    Category,
+    /// `domain_blocks` 도메인
+    Domain,
+    /// `url_blocks` URL
+    Url,
+    /// `file_type_blocks` 다운로드 파일 형식
+    FileType,
+}
+
+impl fmt::Display for RuleType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Default => "default",
+            Self::Client => "client",
+            Self::Ip => "ip",
+            Self::Pattern => "pattern",
+            Self::Category => "category",
+            Self::Domain => "domain",
+            Self::Url => "url",
+            Self::FileType => "file_type",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 규칙 공통 속성
+#[derive(Debug, Clone, Copy)]
+pub struct RuleMeta {
+    /// 규칙 ID
+    pub id: i64,
+    /// 동작
+    pub action: Action,
+    /// 우선순위 (클수록 우선)
+    pub priority: i32,
+    /// 적용 스케줄 (None이면 항상 적용)
+    pub schedule_id: Option<i64>,
+}
+
+/// 규칙 우선순위 비교 키 (필드 순서대로 비교)
+///
+/// 우선순위가 같으면 경로 조건이 있는 URL 규칙, 더 긴 호스트 규칙, 더 긴 IP 프리픽스, 더 긴 경로,
+/// 규칙 종류 순으로 구체적인 규칙이 이기고, 모두 같으면 차단, 경고, 허용 순으로 이긴다.
+/// 모니터 규칙은 차단 규칙과 같은 순위로 비교한다.
+///
+/// IP 규칙은 호스트 구체성이 0이므로 같은 우선순위의 도메인, 카테고리, URL 규칙에 지고,
+/// 호스트 조건이 없는 패턴 규칙에는 이긴다. IP 규칙끼리는 프리픽스 길이로 비교한다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Rank {
+    priority: i32,
+    has_path: bool,
+    host: u32,
+    // IP 규칙 프리픽스 길이 + 1 (IP 규칙이 아니면 0)
+    network: u32,
+    path: u32,
+    rule_type: RuleType,
+    action: Action,
+}
+
+/// 접근 제어 판정
+#[derive(Debug, Clone)]
+pub struct Decision {
+    /// 동작
+    pub action: Action,
+    /// 규칙 ID (기본 동작이면 None)
+    pub rule_id: Option<i64>,
+    /// 규칙 종류
+    pub rule_type: RuleType,
+    /// 판정 사유
+    pub reason: String,
+    rank: Rank,
+}
+
+impl Decision {
+    /// 호스트 규칙 판정 (`depth`: 규칙 도메인 레이블 수)
+    pub(crate) fn host(
+        meta: &RuleMeta,
+        rule_type: RuleType,
+        match_type: MatchType,
+        depth: usize,
+        reason: String,
+    ) -> Self {
+        Self::new(meta, rule_type, host_rank(match_type, depth), None, reason)
+    }
+
+    /// IP/CIDR 규칙 판정 (`prefix_len`: 규칙 네트워크 프리픽스 길이)
+    pub(crate) fn network(
+        meta: &RuleMeta,
+        rule_type: RuleType,
+        prefix_len: u8,
+        reason: String,
+    ) -> Self {
+        let mut decision = Self::new(meta, rule_type, 0, None, reason);
+        decision.rank.network = u32::from(prefix_len) + 1;
+        decision
+    }
+
+    /// 규칙 판정 생성
+    pub(crate) fn new(
+        meta: &RuleMeta,
+        rule_type: RuleType,
+        host: u32,
+        path: Option<usize>,
+        reason: String,
+    ) -> Self {
+        Self {
+            action: meta.action,
+            rule_id: Some(meta.id),
+            rule_type,
+            reason,
+            rank: Rank {
+                priority: meta.priority,
+                has_path: path.is_some(),
+                host,
+                network: 0,
+                path: path.map_or(0, |len| u32::try_from(len).unwrap_or(u32::MAX)),
+                rule_type,
+                action: match meta.action {
+                    Action::Monitor => Action::Block,
+                    action => action,
+                },
+            },
+        }
+    }
+
+    /// 일치하는 규칙이 없을 때의 기본 동작 판정
+    pub fn default_policy(action: Action, reason: String) -> Self {
+        Self {
+            action,
+            rule_id: None,
+            rule_type: RuleType::Default,
+            reason,
+            rank: Rank {
+                priority: i32::MIN,
+                has_path: false,
+                host: 0,
+                network: 0,
+                path: 0,
+                rule_type: RuleType::Default,
+                action,
+            },
+        }
+    }
+
+    /// 차단 판정 여부
+    pub fn is_blocked(&self) -> bool {
+        self.action == Action::Block
+    }
+
+    /// 두 판정 중 우선하는 판정
+    pub(crate) fn pick(current: Option<Self>, candidate: Self) -> Option<Self> {
+        match current {
+            Some(current) if current.rank >= candidate.rank => Some(current),
+            _ => Some(candidate),
+        }
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.rule_id {
+            Some(rule_id) => write!(
+                f,
+                "{} by {} rule {rule_id}: {}",
+                self.action, self.rule_type, self.reason
+            ),
+            None => write!(f, "{} by default policy: {}", self.action, self.reason),
+        }
+    }
+}
+
+/// 적용 판정과 모니터 판정
+///
+/// 모니터 규칙은 적용 판정에서 제외하고, 모니터 규칙을 차단으로 간주한 판정에서
+/// 모니터 규칙이 이기면 차단 예정으로 본다.
+#[derive(Debug, Clone, Default)]
+pub struct Verdict {
+    /// 적용 판정 (모니터 규칙 제외, 일치하는 규칙이 없으면 None)
+    pub decision: Option<Decision>,
+    // 모니터 규칙을 차단으로 간주한 판정
+    shadow: Option<Decision>,
+}
+
+impl Verdict {
+    /// 후보 규칙 반영
+    pub(crate) fn pick(self, candidate: Decision) -> Self {
+        if candidate.action == Action::Monitor {
+            return Self {
+                decision: self.decision,
+                shadow: Decision::pick(self.shadow, candidate),
+            };
+        }
+        Self {
+            decision: Decision::pick(self.decision, candidate.clone()),
+            shadow: Decision::pick(self.shadow, candidate),
+        }
+    }
+
+    /// 모니터 규칙이 적용됐다면 차단했을 판정
+    pub fn monitor(&self) -> Option<&Decision> {
+        self.shadow
+            .as_ref()
+            .filter(|decision| decision.action == Action::Monitor)
+    }
+}
+
+/// 호스트 규칙 구체성 (레이블이 많을수록, 같으면 exact > wildcard > suffix)
+pub(crate) fn host_rank(match_type: MatchType, depth: usize) -> u32 {
+    let kind = match match_type {
+        MatchType::Exact => 3,
+        MatchType::Wildcard => 2,
+        MatchType::Suffix => 1,
+    };
+    u32::try_from(depth).unwrap_or(u32::MAX / 4) * 4 + kind
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(id: i64, action: Action, priority: i32) -> RuleMeta {
+        RuleMeta {
+            id,
+            action,
+            priority,
+            schedule_id: None,
+        }
+    }
+
+    fn domain(
+        id: i64,
+        action: Action,
+        priority: i32,
+        match_type: MatchType,
+        depth: usize,
+    ) -> Decision {
+        Decision::host(
+            &meta(id, action, priority),
+            RuleType::Domain,
+            match_type,
+            depth,
+            String::new(),
+        )
+    }
+
+    fn winner(candidates: impl IntoIterator<Item = Decision>) -> Option<i64> {
+        candidates
+            .into_iter()
+            .fold(Verdict::default(), Verdict::pick)
+            .decision
+            .and_then(|decision| decision.rule_id)
+    }
+
+    #[test]
+    fn higher_priority_allow_beats_block() {
+        let block = || domain(1, Action::Block, 0, MatchType::Exact, 3);
+        let allow = || domain(2, Action::Allow, 10, MatchType::Suffix, 1);
+        assert_eq!(winner([block(), allow()]), Some(2));
+        assert_eq!(winner([allow(), block()]), Some(2));
+
+        // 우선순위와 구체성이 같으면 차단이 이김
+        let allow = domain(2, Action::Allow, 0, MatchType::Exact, 3);
+        assert_eq!(winner([allow, block()]), Some(1));
+    }
+
+    #[test]
+    fn exact_beats_wildcard_beats_suffix() {
+        let exact = || domain(1, Action::Allow, 0, MatchType::Exact, 2);
+        let wildcard = || domain(2, Action::Block, 0, MatchType::Wildcard, 2);
+        let suffix = || domain(3, Action::Warn, 0, MatchType::Suffix, 2);
+        assert_eq!(winner([suffix(), wildcard(), exact()]), Some(1));
+        assert_eq!(winner([exact(), suffix(), wildcard()]), Some(1));
+        assert_eq!(winner([suffix(), wildcard()]), Some(2));
+
+        // 레이블 수가 먼저 비교됨
+        let deeper_suffix = domain(4, Action::Block, 0, MatchType::Suffix, 3);
+        assert_eq!(winner([exact(), deeper_suffix]), Some(4));
+    }
+
+    #[test]
+    fn url_rule_beats_host_rule_at_equal_priority() {
+        let host = || domain(1, Action::Block, 0, MatchType::Exact, 3);
+        let url = Decision::new(
+            &meta(2, Action::Allow, 0),
+            RuleType::Url,
+            host_rank(MatchType::Suffix, 2),
+            Some(1),
+            String::new(),
+        );
+        assert_eq!(winner([host(), url.clone()]), Some(2));
+
+        // 우선순위가 더 높으면 호스트 규칙이 이김
+        let host = domain(1, Action::Block, 1, MatchType::Exact, 3);
+        assert_eq!(winner([url, host]), Some(1));
+    }
+
+    #[test]
+    fn monitor_does_not_override_decision() {
+        let allow = domain(1, Action::Allow, 0, MatchType::Suffix, 2);
+        let monitor = domain(2, Action::Monitor, 5, MatchType::Exact, 2);
+        let verdict = Verdict::default().pick(allow).pick(monitor);
+        assert_eq!(verdict.decision.as_ref().unwrap().rule_id, Some(1));
+        assert_eq!(verdict.monitor().unwrap().rule_id, Some(2));
+
+        // 모니터 규칙보다 앞선 규칙이 있으면 모니터 판정 없음
+        let block = domain(3, Action::Block, 10, MatchType::Suffix, 2);
+        let monitor = domain(2, Action::Monitor, 5, MatchType::Exact, 2);
+        let verdict = Verdict::default().pick(monitor).pick(block);
+        assert_eq!(verdict.decision.as_ref().unwrap().rule_id, Some(3));
+        assert!(verdict.monitor().is_none());
+
+        // 모니터 규칙만 일치하면 적용 판정 없음
+        let monitor = domain(2, Action::Monitor, 0, MatchType::Exact, 2);
+        let verdict = Verdict::default().pick(monitor);
+        assert!(verdict.decision.is_none());
+        assert_eq!(verdict.monitor().unwrap().action, Action::Monitor);
+    }
+}
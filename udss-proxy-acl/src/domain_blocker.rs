@@ -1,110 +1,711 @@
-use std::collections::HashSet;
-use std::sync::RwLock;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use log::{debug, error, info};
-use regex::Regex;
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Timelike, Utc};
+use ipnet::IpNet;
+use log::{debug, error, info, warn};
+use lru::LruCache;
 
 use udss_proxy_db::pool::DatabasePool;
+use udss_proxy_db::{blocklist_notify, rule_hits, upstream_client_certs};
 use udss_proxy_error::{ProxyError, Result};
 
+use crate::decision::{Action, Decision, RuleMeta, RuleType, Verdict, host_rank};
+use crate::domain_trie::{DomainTrie, MatchType};
+use crate::file_type::{Download, FileMatchType, FileTypeMatcher, FileTypeRule};
+use crate::header_rewrite::{
+    HeaderContext, HeaderDirection, HeaderEdit, HeaderOperation, HeaderRewriteRule, HeaderRewriter,
+    HeaderTemplate,
+};
+use crate::host::normalize_host;
+use crate::ip_trie::IpTrie;
+use crate::pattern_set::PatternSet;
+use crate::schedule::Schedule;
 use crate::sql;
+use crate::url_matcher::{PathMatchType, UrlMatcher, UrlRule};
 
-/// 도메인 차단을 처리하는 구조체
-pub struct DomainBlocker {
-    // 차단된 도메인 목록
-    blocked_domains: RwLock<HashSet<String>>,
-    // 정규표현식 패턴
-    regex_patterns: RwLock<Vec<Regex>>,
+/// 목적지 IP 규칙 확인용 DNS 조회 결과 유지 시간
+const RESOLVED_TTL: Duration = Duration::from_secs(60);
+
+/// 규칙 만료 후 재로드 실패시 첫 재시도 간격
+const RELOAD_RETRY_MIN: Duration = Duration::from_secs(1);
+
+/// 카테고리 도메인 규칙 (카테고리 속성, 카테고리 이름)
+type CategoryRule = (RuleMeta, Arc<str>);
+
+/// 규칙 적중 수, 마지막 적중 시각 (DB 반영 전까지 누적)
+struct RuleHit {
+    count: i64,
+    first_hit_at: DateTime<Utc>,
+    last_hit_at: DateTime<Utc>,
 }
 
-impl Default for DomainBlocker {
-    fn default() -> Self {
-        Self::new()
+/// DB에서 읽은 전체 규칙 (한번에 교체)
+struct Blocklists {
+    domains: DomainTrie<RuleMeta>,
+    category_domains: DomainTrie<CategoryRule>,
+    patterns: PatternSet<RuleMeta>,
+    fingerprints: HashSet<String>,
+    url_rules: UrlMatcher,
+    schedules: HashMap<i64, Schedule>,
+    ip_rules: IpTrie<RuleMeta>,
+    client_networks: IpTrie<RuleMeta>,
+    file_rules: FileTypeMatcher,
+    header_rules: HeaderRewriter,
+}
+
+impl Blocklists {
+    fn empty() -> Self {
+        Self {
+            domains: DomainTrie::new(),
+            category_domains: DomainTrie::new(),
+            patterns: PatternSet::empty(),
+            fingerprints: HashSet::new(),
+            url_rules: UrlMatcher::default(),
+            schedules: HashMap::new(),
+            ip_rules: IpTrie::new(),
+            client_networks: IpTrie::new(),
+            file_rules: FileTypeMatcher::default(),
+            header_rules: HeaderRewriter::default(),
+        }
     }
 }
 
+/// 도메인 차단을 처리하는 구조체
+pub struct DomainBlocker {
+    // 전체 규칙 (재로드시 통째로 교체, 판정 한 번에 한 번만 읽어 같은 세대 규칙으로 평가)
+    rules: ArcSwap<Blocklists>,
+    // 현재 적용 중인 스케줄 ID (시간 경과에 따라 재계산)
+    active_schedules: ArcSwap<HashSet<i64>>,
+    // 호스트별 판정 캐시 (일치 규칙이 없으면 None)
+    decisions: Mutex<LruCache<String, Verdict>>,
+    // 호스트별 DNS 조회 결과 (조회 시각, 주소, 규칙은 매번 현재 목록으로 평가)
+    resolved: Mutex<LruCache<String, (Instant, Vec<IpAddr>)>>,
+    // 차단 목록 세대 (교체 전 계산한 판정이 캐시에 남지 않도록 사용)
+    generation: AtomicU64,
+    // 규칙 종류, ID별 적중 기록 (주기적으로 DB에 반영 후 비움)
+    rule_hits: Mutex<HashMap<(RuleType, i64), RuleHit>>,
+    // 다음 규칙 만료 시각 (만료 예정 규칙이 없으면 None)
+    next_expiry: Mutex<Option<Instant>>,
+}
+
 impl DomainBlocker {
     /// 새로운 `DomainBlocker` 인스턴스 생성
-    pub fn new() -> Self {
+    pub fn new(cache_size: usize) -> Self {
+        let capacity = NonZeroUsize::new(cache_size).unwrap_or(NonZeroUsize::MIN);
         Self {
-            blocked_domains: RwLock::new(HashSet::new()),
-            regex_patterns: RwLock::new(Vec::new()),
+            rules: ArcSwap::from_pointee(Blocklists::empty()),
+            active_schedules: ArcSwap::from_pointee(HashSet::new()),
+            decisions: Mutex::new(LruCache::new(capacity)),
+            resolved: Mutex::new(LruCache::new(capacity)),
+            generation: AtomicU64::new(0),
+            rule_hits: Mutex::new(HashMap::new()),
+            next_expiry: Mutex::new(None),
         }
     }
 
     /// 초기화
     pub async fn init(&self, pool: &DatabasePool) -> Result<()> {
+        self.reload(pool).await
+    }
+
+    /// 차단 목록 재로드 (새 목록을 모두 읽은 뒤 한번에 교체)
+    pub async fn reload(&self, pool: &DatabasePool) -> Result<()> {
         // 커넥션 풀에서 로드
         let conn = pool.get_connection().await?;
 
-        self.clear_blocklists()?;
-        self.load_domains_from_db(&conn).await?;
-        self.load_domain_patterns_from_db(&conn).await?;
+        let blocklists = Blocklists {
+            domains: self.load_domains_from_db(&conn).await?,
+            category_domains: self.load_category_domains_from_db(&conn).await?,
+            patterns: self.load_domain_patterns_from_db(&conn).await?,
+            fingerprints: self.load_fingerprints_from_db(&conn).await?,
+            url_rules: self.load_url_rules_from_db(&conn).await?,
+            schedules: self.load_schedules_from_db(&conn).await?,
+            ip_rules: self
+                .load_networks_from_db(&conn, sql::SELECT_ACTIVE_IP_RULES, "목적지 IP")
+                .await?,
+            client_networks: self
+                .load_networks_from_db(
+                    &conn,
+                    sql::SELECT_ACTIVE_CLIENT_NETWORKS,
+                    "클라이언트 네트워크",
+                )
+                .await?,
+            file_rules: self.load_file_type_rules_from_db(&conn).await?,
+            header_rules: self.load_header_rules_from_db(&conn).await?,
+        };
+        let next_expiry = conn
+            .query_one(sql::SELECT_NEXT_EXPIRY_SECONDS, &[])
+            .await?
+            .try_get::<usize, Option<f64>>(0)?;
 
+        self.swap_blocklists(blocklists)?;
+
+        // DB 시각 기준 남은 시간으로 계산 (서버 간 시각 차이 무시)
+        let next_expiry = next_expiry.map(|seconds| {
+            debug!("다음 규칙 만료까지 {seconds:.0}초");
+            Instant::now() + Duration::from_secs_f64(seconds.max(0.0))
+        });
+        match self.next_expiry.lock() {
+            Ok(mut guard) => *guard = next_expiry,
+            Err(e) => error!("next_expiry Mutex 잠금 실패 (reload): {e}"),
+        }
         Ok(())
     }
 
-    /// 도메인 차단여부
-    pub fn is_blocked(&self, host: &str) -> bool {
-        if host.is_empty() {
-            return false;
+    /// 다음 규칙 만료 시각까지 대기 (만료 예정 규칙이 없으면 계속 대기)
+    ///
+    /// 만료 시각은 재로드가 성공해야 갱신되므로 실패하면 만료된 규칙이 빠질 때까지 다시 대기한다.
+    async fn wait_next_expiry(&self) {
+        let next_expiry = match self.next_expiry.lock() {
+            Ok(guard) => *guard,
+            Err(e) => {
+                error!("next_expiry Mutex 잠금 실패 (wait_next_expiry): {e}");
+                None
+            }
+        };
+        match next_expiry {
+            Some(at) => tokio::time::sleep_until(at.into()).await,
+            None => std::future::pending().await,
         }
+    }
 
-        match self.blocked_domains.read() {
-            Ok(guard) => {
-                if guard.contains(host) {
-                    debug!("정확히 차단된 도메인: {host}");
-                    return true;
+    /// 재로드 실패시 지난 만료 시각을 `backoff` 뒤로 미룸 (즉시 반복 재로드 방지)
+    fn defer_next_expiry(&self, backoff: Duration) {
+        match self.next_expiry.lock() {
+            Ok(mut guard) => {
+                let now = Instant::now();
+                if let Some(at) = guard.as_mut()
+                    && *at <= now
+                {
+                    debug!("규칙 만료 재로드 {}초 뒤 재시도", backoff.as_secs());
+                    *at = now + backoff;
                 }
             }
-            Err(e) => {
-                error!("blocked_domains RwLock 읽기 잠금 실패 (is_blocked): {e}");
-                return false;
-            }
+            Err(e) => error!("next_expiry Mutex 잠금 실패 (defer_next_expiry): {e}"),
         }
+    }
 
-        match self.regex_patterns.read() {
-            Ok(guard) => {
-                for pattern in guard.iter() {
-                    if pattern.is_match(host) {
-                        debug!("패턴으로 차단된 도메인: {} ({})", host, pattern.as_str());
-                        return true;
+    /// DB 변경 알림 수신시 재로드 (알림 연결이 끊기면 주기적 재로드)
+    pub fn spawn_reloader(self: Arc<Self>, pool: DatabasePool, poll_interval: Duration) {
+        tokio::spawn(async move {
+            loop {
+                match pool.listen(blocklist_notify::CHANNEL).await {
+                    Ok(mut listener) => {
+                        info!("차단 목록 변경 알림 수신 시작");
+                        // 연결이 끊긴 동안의 변경 반영
+                        if let Err(e) = self.reload(&pool).await {
+                            error!("차단 목록 재로드 실패: {e}");
+                        }
+                        // 재로드 실패시 만료 재시도 간격 (실패할 때마다 두배, 최대 poll_interval)
+                        let mut backoff = RELOAD_RETRY_MIN;
+                        loop {
+                            tokio::select! {
+                                table = listener.recv() => {
+                                    let Some(table) = table else {
+                                        break;
+                                    };
+                                    // 업스트림 클라이언트 인증서는 UpstreamTls에서 재로드
+                                    if table == upstream_client_certs::TABLE {
+                                        continue;
+                                    }
+                                    listener.drain();
+                                    info!("차단 목록 변경 감지 ({table}), 재로드");
+                                }
+                                () = self.wait_next_expiry() => {
+                                    info!("규칙 만료 시각 도달, 재로드");
+                                }
+                            }
+                            match self.reload(&pool).await {
+                                Ok(()) => backoff = RELOAD_RETRY_MIN,
+                                Err(e) => {
+                                    error!("차단 목록 재로드 실패: {e}");
+                                    self.defer_next_expiry(backoff);
+                                    backoff =
+                                        (backoff * 2).min(poll_interval.max(RELOAD_RETRY_MIN));
+                                }
+                            }
+                        }
+                        warn!("차단 목록 알림 연결 끊김, 주기적 재로드로 전환");
                     }
+                    Err(e) => warn!("차단 목록 알림 연결 실패: {e}"),
+                }
+
+                // 알림 재연결 전까지 폴링
+                tokio::time::sleep(poll_interval).await;
+                if let Err(e) = self.reload(&pool).await {
+                    error!("차단 목록 주기적 재로드 실패: {e}");
                 }
             }
-            Err(e) => {
-                error!("regex_patterns RwLock 읽기 잠금 실패 (is_blocked): {e}");
-                return false;
+        });
+    }
+
+    /// 규칙 적중 기록 주기적 DB 반영
+    pub fn spawn_hit_flusher(self: Arc<Self>, pool: DatabasePool, interval: Duration) {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                match self.flush_rule_hits(&pool).await {
+                    Ok(0) => {}
+                    Ok(count) => debug!("규칙 적중 기록 반영: {count}개 규칙"),
+                    Err(e) => error!("규칙 적중 기록 반영 실패: {e}"),
+                }
             }
-        }
+        });
+    }
 
-        false
+    /// 판정에 쓰인 규칙 적중 기록 (기본 동작 판정 제외)
+    pub fn record_hit(&self, decision: &Decision) {
+        let Some(rule_id) = decision.rule_id else {
+            return;
+        };
+        match self.rule_hits.lock() {
+            Ok(mut hits) => {
+                let now = Utc::now();
+                let hit = hits
+                    .entry((decision.rule_type, rule_id))
+                    .or_insert(RuleHit {
+                        count: 0,
+                        first_hit_at: now,
+                        last_hit_at: now,
+                    });
+                hit.count += 1;
+                hit.last_hit_at = now;
+            }
+            Err(e) => error!("rule_hits Mutex 잠금 실패 (record_hit): {e}"),
+        }
     }
 
-    /// 기존목록 초기화
-    fn clear_blocklists(&self) -> Result<()> {
-        match self.blocked_domains.write() {
-            Ok(mut guard) => guard.clear(),
+    /// 누적된 규칙 적중 기록을 DB에 반영 (실패하면 다음 반영 때 다시 시도)
+    pub async fn flush_rule_hits(&self, pool: &DatabasePool) -> Result<usize> {
+        let hits = match self.rule_hits.lock() {
+            Ok(mut hits) => std::mem::take(&mut *hits),
             Err(e) => {
-                let err_msg = format!("blocked_domains RwLock 쓰기 잠금 실패 (초기화 중): {e}");
+                let err_msg = format!("rule_hits 잠금 실패 (반영 중): {e}");
                 error!("{err_msg}");
                 return Err(ProxyError::Internal(err_msg));
             }
+        };
+        if hits.is_empty() {
+            return Ok(0);
+        }
+
+        let mut rule_types = Vec::with_capacity(hits.len());
+        let mut rule_ids = Vec::with_capacity(hits.len());
+        let mut counts = Vec::with_capacity(hits.len());
+        let mut first_hits = Vec::with_capacity(hits.len());
+        let mut last_hits = Vec::with_capacity(hits.len());
+        for ((rule_type, rule_id), hit) in &hits {
+            rule_types.push(rule_type.to_string());
+            rule_ids.push(*rule_id);
+            counts.push(hit.count);
+            first_hits.push(hit.first_hit_at);
+            last_hits.push(hit.last_hit_at);
+        }
+
+        let result = async {
+            let conn = pool.get_connection().await?;
+            conn.execute(
+                rule_hits::UPSERT_BATCH,
+                &[&rule_types, &rule_ids, &counts, &first_hits, &last_hits],
+            )
+            .await?;
+            Ok::<_, ProxyError>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            // 반영하지 못한 기록은 새 기록과 합쳐 다음에 반영
+            if let Ok(mut pending) = self.rule_hits.lock() {
+                for (key, hit) in hits {
+                    let entry = pending.entry(key).or_insert(RuleHit {
+                        count: 0,
+                        first_hit_at: hit.first_hit_at,
+                        last_hit_at: hit.last_hit_at,
+                    });
+                    entry.count += hit.count;
+                    entry.first_hit_at = entry.first_hit_at.min(hit.first_hit_at);
+                    entry.last_hit_at = entry.last_hit_at.max(hit.last_hit_at);
+                }
+            }
+            return Err(e);
+        }
+        Ok(hits.len())
+    }
+
+    /// 매 분 스케줄 적용 여부 재계산 (DB 재로드 없이 규칙 적용 상태 갱신)
+    pub fn spawn_schedule_refresh(self: Arc<Self>) {
+        tokio::spawn(async move {
+            loop {
+                // 다음 분 시작까지 대기
+                let now = Utc::now();
+                let elapsed = Duration::new(u64::from(now.second()), now.nanosecond());
+                tokio::time::sleep(Duration::from_secs(60).saturating_sub(elapsed)).await;
+
+                if let Err(e) = self.refresh_schedules() {
+                    error!("스케줄 적용 상태 갱신 실패: {e}");
+                }
+            }
+        });
+    }
+
+    /// 적용 중인 스케줄 재계산 (바뀌었으면 판정 캐시 무효화)
+    fn refresh_schedules(&self) -> Result<()> {
+        // 차단 목록 교체와 겹치지 않도록 캐시 잠금을 먼저 획득
+        let mut decisions = self.decisions.lock().map_err(|e| {
+            let err_msg = format!("decisions 잠금 실패 (스케줄 갱신 중): {e}");
+            error!("{err_msg}");
+            ProxyError::Internal(err_msg)
+        })?;
+
+        let rules = self.rules.load();
+        let schedules = &rules.schedules;
+        let active = active_schedule_ids(schedules);
+        if **self.active_schedules.load() == active {
+            return Ok(());
+        }
+
+        let mut names: Vec<&str> = active
+            .iter()
+            .filter_map(|id| schedules.get(id))
+            .map(|schedule| schedule.name.as_str())
+            .collect();
+        names.sort_unstable();
+        info!("적용 중인 스케줄 변경: [{}]", names.join(", "));
+
+        self.active_schedules.store(Arc::new(active));
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        decisions.clear();
+        Ok(())
+    }
+
+    /// 규칙 스케줄 적용 여부
+    fn is_scheduled(meta: &RuleMeta, active_schedules: &HashSet<i64>) -> bool {
+        meta.schedule_id
+            .is_none_or(|schedule_id| active_schedules.contains(&schedule_id))
+    }
+
+    /// 호스트 규칙 판정 (호스트는 정규화 후 비교, 일치하는 규칙이 없으면 빈 판정)
+    pub fn check_host(&self, host: &str) -> Verdict {
+        let host = normalize_host(host);
+        if host.is_empty() {
+            return Verdict::default();
+        }
+
+        match self.decisions.lock() {
+            Ok(mut guard) => {
+                if let Some(verdict) = guard.get(host.as_ref()) {
+                    return verdict.clone();
+                }
+            }
+            Err(e) => error!("decisions Mutex 잠금 실패 (check_host): {e}"),
+        }
+
+        let generation = self.generation.load(Ordering::Acquire);
+        let verdict = self.evaluate_host(&host);
+        if let Some(decision) = &verdict.decision {
+            debug!("도메인 판정: {host} ({decision})");
+        }
+        if let Some(decision) = verdict.monitor() {
+            debug!("도메인 모니터 판정: {host} ({decision})");
+        }
+
+        match self.decisions.lock() {
+            Ok(mut guard) => {
+                if self.generation.load(Ordering::Acquire) == generation {
+                    guard.put(host.into_owned(), verdict.clone());
+                }
+            }
+            Err(e) => error!("decisions Mutex 잠금 실패 (check_host): {e}"),
         }
-        match self.regex_patterns.write() {
-            Ok(mut guard) => guard.clear(),
+        verdict
+    }
+
+    /// 요청 판정 (호스트 규칙과 URL 규칙 중 우선하는 규칙, 경로와 쿼리는 정규화 후 비교)
+    pub fn check_request(&self, method: &str, host: &str, path_and_query: &str) -> Verdict {
+        let host = normalize_host(host);
+        let mut verdict = self.check_host(&host);
+        if host.is_empty() {
+            return verdict;
+        }
+
+        let rules = self.rules.load();
+        for (match_type, depth, rule) in rules.url_rules.find_all(method, &host, path_and_query) {
+            let reason = format!(
+                "{} {}, {match_type} host match",
+                rule.method.as_deref().unwrap_or("*"),
+                rule.path
+            );
+            let candidate = Decision::new(
+                &rule.meta,
+                RuleType::Url,
+                host_rank(match_type, depth),
+                Some(rule.path.len()),
+                reason,
+            );
+            verdict = verdict.pick(candidate);
+        }
+
+        if let Some(decision) = &verdict.decision
+            && decision.rule_type == RuleType::Url
+        {
+            debug!("URL 판정: {method} {host}{path_and_query} ({decision})");
+        }
+        verdict
+    }
+
+    /// 호스트 규칙 평가 (적용 중인 규칙 중 우선순위, 구체성 순으로 가장 앞선 규칙)
+    fn evaluate_host(&self, host: &str) -> Verdict {
+        let mut verdict = Verdict::default();
+        let rules = self.rules.load();
+        let active_schedules = self.active_schedules.load();
+
+        for (match_type, depth, meta) in rules.domains.find_all(host) {
+            if !Self::is_scheduled(meta, &active_schedules) {
+                continue;
+            }
+            let reason = format!("{match_type} match");
+            let candidate = Decision::host(meta, RuleType::Domain, match_type, depth, reason);
+            verdict = verdict.pick(candidate);
+        }
+
+        for (match_type, depth, (meta, category)) in rules.category_domains.find_all(host) {
+            if !Self::is_scheduled(meta, &active_schedules) {
+                continue;
+            }
+            let reason = format!("category '{category}', {match_type} match");
+            let candidate = Decision::host(meta, RuleType::Category, match_type, depth, reason);
+            verdict = verdict.pick(candidate);
+        }
+
+        for (pattern, meta) in rules.patterns.find_all(host) {
+            if !Self::is_scheduled(meta, &active_schedules) {
+                continue;
+            }
+            let reason = format!("pattern '{pattern}'");
+            let candidate = Decision::new(meta, RuleType::Pattern, 0, None, reason);
+            verdict = verdict.pick(candidate);
+        }
+
+        // IP 리터럴 호스트
+        if let Some(addr) = literal_ip(host) {
+            verdict = Self::evaluate_ip(&rules, addr, "destination", verdict, &active_schedules);
+        }
+
+        verdict
+    }
+
+    /// 목적지 IP 규칙 평가 (`current`와 비교해 우선하는 판정)
+    fn evaluate_ip(
+        rules: &Blocklists,
+        addr: IpAddr,
+        label: &str,
+        current: Verdict,
+        active_schedules: &HashSet<i64>,
+    ) -> Verdict {
+        let mut verdict = current;
+        for (network, meta) in rules.ip_rules.find_all(addr) {
+            if !Self::is_scheduled(meta, active_schedules) {
+                continue;
+            }
+            let reason = format!("{label} {addr} in {network}");
+            let candidate = Decision::network(meta, RuleType::Ip, network.prefix_len(), reason);
+            verdict = verdict.pick(candidate);
+        }
+        verdict
+    }
+
+    /// DNS 조회 결과 주소까지 포함한 판정
+    ///
+    /// 목적지 IP 규칙이 있고 호스트가 IP 리터럴이 아니면 호스트를 조회해
+    /// 모든 주소를 `current`(호스트/URL 판정)와 함께 평가한다.
+    /// 조회에 실패하면 `current`를 그대로 반환한다 (업스트림 연결도 실패함).
+    /// 조회 결과는 `RESOLVED_TTL` 동안 재사용한다.
+    pub async fn check_resolved(&self, host: &str, current: Verdict) -> Verdict {
+        let host = normalize_host(host);
+        if host.is_empty() || literal_ip(&host).is_some() || self.rules.load().ip_rules.is_empty() {
+            return current;
+        }
+
+        let Some(addrs) = self.resolve(&host).await else {
+            return current;
+        };
+
+        let rules = self.rules.load();
+        let active_schedules = self.active_schedules.load();
+        let mut verdict = current;
+        for addr in addrs {
+            verdict = Self::evaluate_ip(&rules, addr, "resolved", verdict, &active_schedules);
+        }
+        if let Some(decision) = &verdict.decision
+            && decision.rule_type == RuleType::Ip
+        {
+            debug!("목적지 IP 판정: {host} ({decision})");
+        }
+        verdict
+    }
+
+    /// 호스트 주소 조회 (캐시 우선, 조회 실패는 캐시하지 않음)
+    async fn resolve(&self, host: &str) -> Option<Vec<IpAddr>> {
+        match self.resolved.lock() {
+            Ok(mut guard) => {
+                if let Some((resolved_at, addrs)) = guard.get(host)
+                    && resolved_at.elapsed() < RESOLVED_TTL
+                {
+                    return Some(addrs.clone());
+                }
+            }
+            Err(e) => error!("resolved Mutex 잠금 실패 (resolve): {e}"),
+        }
+
+        let addrs: Vec<IpAddr> = match tokio::net::lookup_host((host, 0)).await {
+            Ok(addrs) => addrs.map(|addr| addr.ip()).collect(),
             Err(e) => {
-                let err_msg = format!("regex_patterns RwLock 쓰기 잠금 실패 (초기화 중): {e}");
-                error!("{err_msg}");
-                return Err(ProxyError::Internal(err_msg));
+                debug!("목적지 IP 규칙 확인용 DNS 조회 실패 {host}: {e}");
+                return None;
+            }
+        };
+
+        match self.resolved.lock() {
+            Ok(mut guard) => {
+                guard.put(host.to_string(), (Instant::now(), addrs.clone()));
             }
+            Err(e) => error!("resolved Mutex 잠금 실패 (resolve): {e}"),
         }
-        info!("기존 도메인 차단 목록 초기화 완료.");
+        Some(addrs)
+    }
+
+    /// 다운로드 파일 형식 규칙 존재 여부 (없으면 응답 검사 생략)
+    pub fn has_file_type_rules(&self) -> bool {
+        !self.rules.load().file_rules.is_empty()
+    }
+
+    /// 다운로드 파일 형식 판정 (파일 형식 규칙끼리 우선순위 비교, 일치하는 규칙이 없으면 빈 판정)
+    pub fn check_download(&self, host: &str, download: &Download) -> Verdict {
+        let rules = self.rules.load();
+        let file_rules = &rules.file_rules;
+        if file_rules.is_empty() {
+            return Verdict::default();
+        }
+
+        let host = normalize_host(host);
+        let active_schedules = self.active_schedules.load();
+        let categorized =
+            file_rules.has_uncategorized_rules() && self.is_categorized(&rules, &host);
+        let mut verdict = Verdict::default();
+        for (rule, reason) in file_rules.find_all(&host, categorized, download) {
+            if !Self::is_scheduled(&rule.meta, &active_schedules) {
+                continue;
+            }
+            let host_rank = u32::try_from(rule.host_depth()).unwrap_or(u32::MAX);
+            let candidate = Decision::new(&rule.meta, RuleType::FileType, host_rank, None, reason);
+            verdict = verdict.pick(candidate);
+        }
+        if let Some(decision) = &verdict.decision {
+            debug!("다운로드 판정: {host} ({decision})");
+        }
+        if let Some(decision) = verdict.monitor() {
+            debug!("다운로드 모니터 판정: {host} ({decision})");
+        }
+        verdict
+    }
+
+    /// 목적지 호스트에 적용할 요청/응답 헤더 변경
+    pub fn header_edits(
+        &self,
+        direction: HeaderDirection,
+        host: &str,
+        context: &HeaderContext<'_>,
+    ) -> Vec<HeaderEdit> {
+        let rules = self.rules.load();
+        let header_rules = &rules.header_rules;
+        if header_rules.is_empty() {
+            return Vec::new();
+        }
+        let active_schedules = self.active_schedules.load();
+        header_rules.edits(direction, host, context, |schedule_id| {
+            schedule_id.is_none_or(|schedule_id| active_schedules.contains(&schedule_id))
+        })
+    }
+
+    /// 적용 중인 카테고리에 속한 호스트인지 여부
+    fn is_categorized(&self, rules: &Blocklists, host: &str) -> bool {
+        let active_schedules = self.active_schedules.load();
+        rules
+            .category_domains
+            .find_all(host)
+            .into_iter()
+            .any(|(_, _, (meta, _))| Self::is_scheduled(meta, &active_schedules))
+    }
+
+    /// 클라이언트 네트워크 판정 (가장 긴 프리픽스 규칙, 일치하는 규칙이 없으면 None)
+    pub fn check_client(&self, addr: IpAddr) -> Option<Decision> {
+        let rules = self.rules.load();
+        let (network, meta) = rules.client_networks.find(addr)?;
+        let reason = format!("client {} in {network}", addr.to_canonical());
+        Some(Decision::network(
+            meta,
+            RuleType::Client,
+            network.prefix_len(),
+            reason,
+        ))
+    }
+
+    /// TLS 핑거프린트 차단여부
+    pub fn is_fingerprint_blocked(&self, ja3_hash: &str, ja4: &str) -> bool {
+        let rules = self.rules.load();
+        if rules.fingerprints.contains(&format!("ja3:{ja3_hash}")) {
+            debug!("차단된 JA3 핑거프린트: {ja3_hash}");
+            return true;
+        }
+        if rules.fingerprints.contains(&format!("ja4:{ja4}")) {
+            debug!("차단된 JA4 핑거프린트: {ja4}");
+            return true;
+        }
+        false
+    }
+
+    /// 차단 목록 교체 (판정 캐시 잠금을 잡은 상태에서 한번에 교체)
+    fn swap_blocklists(&self, blocklists: Blocklists) -> Result<()> {
+        let mut decisions = self.decisions.lock().map_err(|e| {
+            let err_msg = format!("decisions 잠금 실패 (교체 중): {e}");
+            error!("{err_msg}");
+            ProxyError::Internal(err_msg)
+        })?;
+
+        info!(
+            "차단 목록 교체 완료: 도메인 {}개, 카테고리 도메인 {}개, 패턴 {}개, 핑거프린트 {}개, URL 규칙 {}개, 스케줄 {}개, IP 규칙 {}개, 클라이언트 네트워크 {}개, 파일 형식 규칙 {}개, 헤더 변경 규칙 {}개",
+            blocklists.domains.len(),
+            blocklists.category_domains.len(),
+            blocklists.patterns.len(),
+            blocklists.fingerprints.len(),
+            blocklists.url_rules.len(),
+            blocklists.schedules.len(),
+            blocklists.ip_rules.len(),
+            blocklists.client_networks.len(),
+            blocklists.file_rules.len(),
+            blocklists.header_rules.len()
+        );
+        self.active_schedules
+            .store(Arc::new(active_schedule_ids(&blocklists.schedules)));
+        self.rules.store(Arc::new(blocklists));
+
+        // 이전 목록 기준 판정 무효화
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        decisions.clear();
         Ok(())
     }
 
     /// 도메인 차단목록
-    async fn load_domains_from_db(&self, conn: &deadpool_postgres::Object) -> Result<()> {
+    async fn load_domains_from_db(
+        &self,
+        conn: &deadpool_postgres::Object,
+    ) -> Result<DomainTrie<RuleMeta>> {
         debug!("데이터베이스에서 도메인 차단 목록 로드 중...");
 
         let pg_rows = conn
@@ -115,33 +716,111 @@ impl DomainBlocker {
                 ProxyError::Database(format!("DB query error: {e}"))
             })?;
 
-        let mut blocked_domains_writer = self.blocked_domains.write().map_err(|e| {
-            let err_msg = format!("blocked_domains RwLock 쓰기 잠금 실패 (DB 로드 중): {e}");
-            error!("{err_msg}");
-            ProxyError::Internal(err_msg)
-        })?;
-
+        let mut blocked_domains = DomainTrie::new();
         for row in pg_rows {
-            match row.try_get::<usize, String>(0) {
-                Ok(domain) => {
-                    debug!("차단 목록에 도메인 추가: {domain}");
-                    blocked_domains_writer.insert(domain);
-                }
+            let parsed = (|| -> std::result::Result<_, tokio_postgres::Error> {
+                Ok((
+                    row.try_get::<usize, i64>(0)?,
+                    row.try_get::<usize, String>(1)?,
+                    row.try_get::<usize, String>(2)?,
+                    row.try_get::<usize, String>(3)?,
+                    row.try_get::<usize, i32>(4)?,
+                    row.try_get::<usize, Option<i64>>(5)?,
+                ))
+            })();
+            let (id, domain, match_type, action, priority, schedule_id) = match parsed {
+                Ok(values) => values,
                 Err(e) => {
-                    error!("DB 행에서 도메인 문자열 추출 실패: {e}");
+                    error!("DB 행에서 도메인 규칙 추출 실패: {e}");
+                    continue;
                 }
-            }
+            };
+
+            let Some(match_type) = MatchType::parse(&match_type) else {
+                error!("알 수 없는 도메인 일치 방식 '{match_type}': {domain}");
+                continue;
+            };
+            let Some(meta) = rule_meta(id, &action, priority, schedule_id) else {
+                continue;
+            };
+            // `*.`, `.` 접두어가 있으면 접두어 기준으로 판단
+            let (domain, match_type) = MatchType::split_rule(&domain, match_type);
+            debug!("도메인 규칙 추가: {domain} ({match_type}, {action}, 우선순위 {priority})");
+            blocked_domains.insert(domain, match_type, meta);
         }
 
         info!(
             "도메인 차단 목록 로드 완료. {}개의 도메인 로드",
-            blocked_domains_writer.len()
+            blocked_domains.len()
         );
-        Ok(())
+        Ok(blocked_domains)
+    }
+
+    /// 활성 카테고리 도메인 차단목록
+    async fn load_category_domains_from_db(
+        &self,
+        conn: &deadpool_postgres::Object,
+    ) -> Result<DomainTrie<CategoryRule>> {
+        debug!("데이터베이스에서 카테고리 도메인 목록 로드 중...");
+
+        let pg_rows = conn
+            .query(sql::SELECT_ENABLED_CATEGORY_DOMAINS, &[])
+            .await
+            .map_err(|e| {
+                error!("카테고리 도메인 목록 쿼리 실패: {e}");
+                ProxyError::Database(format!("DB query error: {e}"))
+            })?;
+
+        // 카테고리 이름은 규칙끼리 공유
+        let mut categories: HashMap<String, Arc<str>> = HashMap::new();
+        let mut category_domains = DomainTrie::new();
+        for row in pg_rows {
+            let parsed = (|| -> std::result::Result<_, tokio_postgres::Error> {
+                Ok((
+                    row.try_get::<usize, i64>(0)?,
+                    row.try_get::<usize, String>(1)?,
+                    row.try_get::<usize, String>(2)?,
+                    row.try_get::<usize, String>(3)?,
+                    row.try_get::<usize, String>(4)?,
+                    row.try_get::<usize, i32>(5)?,
+                    row.try_get::<usize, Option<i64>>(6)?,
+                ))
+            })();
+            let (id, domain, match_type, category, action, priority, schedule_id) = match parsed {
+                Ok(values) => values,
+                Err(e) => {
+                    error!("DB 행에서 카테고리 도메인 추출 실패: {e}");
+                    continue;
+                }
+            };
+
+            let Some(match_type) = MatchType::parse(&match_type) else {
+                error!("알 수 없는 도메인 일치 방식 '{match_type}': {domain}");
+                continue;
+            };
+            let Some(meta) = rule_meta(id, &action, priority, schedule_id) else {
+                continue;
+            };
+            let category = categories
+                .entry(category)
+                .or_insert_with_key(|name| Arc::from(name.as_str()))
+                .clone();
+            category_domains.insert(&domain, match_type, (meta, category));
+        }
+
+        info!(
+            "카테고리 도메인 목록 로드 완료. {}개 카테고리, {}개의 도메인 로드",
+            categories.len(),
+            category_domains.len()
+        );
+        Ok(category_domains)
     }
 
     /// 도메인 패턴 차단목록
-    async fn load_domain_patterns_from_db(&self, conn: &deadpool_postgres::Object) -> Result<()> {
+    async fn load_domain_patterns_from_db(
+        &self,
+        conn: &deadpool_postgres::Object,
+    ) -> Result<PatternSet<RuleMeta>> {
         debug!("데이터베이스에서 도메인 차단 패턴 목록 로드 중...");
 
         let pg_rows = conn
@@ -152,34 +831,644 @@ impl DomainBlocker {
                 ProxyError::Database(format!("DB query error: {e}"))
             })?;
 
-        let mut regex_patterns_writer = self.regex_patterns.write().map_err(|e| {
-            let err_msg = format!("regex_patterns RwLock 쓰기 잠금 실패 (DB 로드 중): {e}");
-            error!("{err_msg}");
-            ProxyError::Internal(err_msg)
+        let mut pattern_rules = Vec::with_capacity(pg_rows.len());
+        for row in pg_rows {
+            let parsed = (|| -> std::result::Result<_, tokio_postgres::Error> {
+                Ok((
+                    row.try_get::<usize, i64>(0)?,
+                    row.try_get::<usize, String>(1)?,
+                    row.try_get::<usize, String>(2)?,
+                    row.try_get::<usize, i32>(3)?,
+                    row.try_get::<usize, Option<i64>>(4)?,
+                ))
+            })();
+            let (id, pattern_str, action, priority, schedule_id) = match parsed {
+                Ok(values) => values,
+                Err(e) => {
+                    error!("DB 행에서 패턴 규칙 추출 실패: {e}");
+                    continue;
+                }
+            };
+
+            let Some(meta) = rule_meta(id, &action, priority, schedule_id) else {
+                continue;
+            };
+            debug!("차단 패턴 목록에 정규식 추가: {pattern_str} ({action})");
+            pattern_rules.push((pattern_str, meta));
+        }
+
+        let (regex_patterns, invalid) = PatternSet::compile(pattern_rules)?;
+        for (pattern_str, e) in invalid {
+            error!("정규식 컴파일 실패 '{pattern_str}': {e}");
+        }
+
+        info!(
+            "도메인 차단 패턴 목록 로드 완료. {}개의 패턴 로드됨.",
+            regex_patterns.len()
+        );
+
+        Ok(regex_patterns)
+    }
+
+    /// TLS 핑거프린트 차단목록
+    async fn load_fingerprints_from_db(
+        &self,
+        conn: &deadpool_postgres::Object,
+    ) -> Result<HashSet<String>> {
+        debug!("데이터베이스에서 TLS 핑거프린트 차단 목록 로드 중...");
+
+        let pg_rows = conn
+            .query(sql::SELECT_ACTIVE_FINGERPRINTS, &[])
+            .await
+            .map_err(|e| {
+                error!("TLS 핑거프린트 차단 목록 쿼리 실패: {e}");
+                ProxyError::Database(format!("DB query error: {e}"))
+            })?;
+
+        let mut fingerprints = HashSet::with_capacity(pg_rows.len());
+        for row in pg_rows {
+            match (
+                row.try_get::<usize, String>(0),
+                row.try_get::<usize, String>(1),
+            ) {
+                (Ok(kind), Ok(fingerprint)) => {
+                    debug!("차단 목록에 TLS 핑거프린트 추가: {kind} {fingerprint}");
+                    fingerprints.insert(format!("{kind}:{fingerprint}"));
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    error!("DB 행에서 핑거프린트 추출 실패: {e}");
+                }
+            }
+        }
+
+        info!(
+            "TLS 핑거프린트 차단 목록 로드 완료. {}개의 핑거프린트 로드",
+            fingerprints.len()
+        );
+        Ok(fingerprints)
+    }
+
+    /// URL 차단목록
+    async fn load_url_rules_from_db(&self, conn: &deadpool_postgres::Object) -> Result<UrlMatcher> {
+        debug!("데이터베이스에서 URL 차단 규칙 로드 중...");
+
+        let pg_rows = conn
+            .query(sql::SELECT_ACTIVE_URL_RULES, &[])
+            .await
+            .map_err(|e| {
+                error!("URL 차단 규칙 쿼리 실패: {e}");
+                ProxyError::Database(format!("DB query error: {e}"))
+            })?;
+
+        let mut rules = Vec::with_capacity(pg_rows.len());
+        for row in pg_rows {
+            let parsed = (|| -> std::result::Result<_, tokio_postgres::Error> {
+                Ok((
+                    row.try_get::<usize, i64>(0)?,
+                    row.try_get::<usize, String>(1)?,
+                    row.try_get::<usize, Option<String>>(2)?,
+                    row.try_get::<usize, String>(3)?,
+                    row.try_get::<usize, String>(4)?,
+                    row.try_get::<usize, Option<String>>(5)?,
+                    row.try_get::<usize, bool>(6)?,
+                    row.try_get::<usize, String>(7)?,
+                    row.try_get::<usize, i32>(8)?,
+                ))
+            })();
+            let (
+                id,
+                host,
+                method,
+                path,
+                path_match_type,
+                query_param,
+                case_sensitive,
+                action,
+                priority,
+            ) = match parsed {
+                Ok(values) => values,
+                Err(e) => {
+                    error!("DB 행에서 URL 규칙 추출 실패: {e}");
+                    continue;
+                }
+            };
+
+            let Some(path_match_type) = PathMatchType::parse(&path_match_type) else {
+                error!("알 수 없는 경로 일치 방식 '{path_match_type}': 규칙 {id}");
+                continue;
+            };
+            let Some(meta) = rule_meta(id, &action, priority, None) else {
+                continue;
+            };
+            debug!("URL 차단 규칙 추가: {host}{path} ({id})");
+            let rule = UrlRule::new(
+                meta,
+                method.as_deref(),
+                &path,
+                path_match_type,
+                query_param.as_deref(),
+                case_sensitive,
+            );
+            rules.push((host, rule));
+        }
+
+        info!("URL 차단 규칙 로드 완료. {}개의 규칙 로드", rules.len());
+        Ok(UrlMatcher::new(rules))
+    }
+
+    /// IP/CIDR 규칙 (`ip_blocks`, `client_networks`)
+    async fn load_networks_from_db(
+        &self,
+        conn: &deadpool_postgres::Object,
+        query: &str,
+        label: &str,
+    ) -> Result<IpTrie<RuleMeta>> {
+        debug!("데이터베이스에서 {label} 규칙 로드 중...");
+
+        let pg_rows = conn.query(query, &[]).await.map_err(|e| {
+            error!("{label} 규칙 쿼리 실패: {e}");
+            ProxyError::Database(format!("DB query error: {e}"))
         })?;
 
+        let mut networks = IpTrie::new();
         for row in pg_rows {
-            match row.try_get::<usize, String>(0) {
-                Ok(pattern_str) => match Regex::new(&pattern_str) {
-                    Ok(regex) => {
-                        debug!("차단 패턴 목록에 정규식 추가: {pattern_str}");
-                        regex_patterns_writer.push(regex);
-                    }
+            let parsed = (|| -> std::result::Result<_, tokio_postgres::Error> {
+                Ok((
+                    row.try_get::<usize, i64>(0)?,
+                    row.try_get::<usize, String>(1)?,
+                    row.try_get::<usize, String>(2)?,
+                    row.try_get::<usize, i32>(3)?,
+                    row.try_get::<usize, Option<i64>>(4)?,
+                ))
+            })();
+            let (id, cidr, action, priority, schedule_id) = match parsed {
+                Ok(values) => values,
+                Err(e) => {
+                    error!("DB 행에서 {label} 규칙 추출 실패: {e}");
+                    continue;
+                }
+            };
+
+            let network = match cidr.parse::<IpNet>() {
+                Ok(network) => network,
+                Err(e) => {
+                    error!("잘못된 {label} 네트워크 '{cidr}': 규칙 {id}: {e}");
+                    continue;
+                }
+            };
+            let Some(meta) = rule_meta(id, &action, priority, schedule_id) else {
+                continue;
+            };
+            debug!("{label} 규칙 추가: {network} ({action})");
+            networks.insert(network, meta);
+        }
+
+        info!("{label} 규칙 로드 완료. {}개의 규칙 로드", networks.len());
+        Ok(networks)
+    }
+
+    /// 다운로드 파일 형식 규칙
+    async fn load_file_type_rules_from_db(
+        &self,
+        conn: &deadpool_postgres::Object,
+    ) -> Result<FileTypeMatcher> {
+        debug!("데이터베이스에서 파일 형식 규칙 로드 중...");
+
+        let pg_rows = conn
+            .query(sql::SELECT_ACTIVE_FILE_TYPE_RULES, &[])
+            .await
+            .map_err(|e| {
+                error!("파일 형식 규칙 쿼리 실패: {e}");
+                ProxyError::Database(format!("DB query error: {e}"))
+            })?;
+
+        let mut rules = Vec::with_capacity(pg_rows.len());
+        for row in pg_rows {
+            let parsed = (|| -> std::result::Result<_, tokio_postgres::Error> {
+                Ok((
+                    row.try_get::<usize, i64>(0)?,
+                    row.try_get::<usize, String>(1)?,
+                    row.try_get::<usize, String>(2)?,
+                    row.try_get::<usize, Option<String>>(3)?,
+                    row.try_get::<usize, bool>(4)?,
+                    row.try_get::<usize, String>(5)?,
+                    row.try_get::<usize, i32>(6)?,
+                    row.try_get::<usize, Option<i64>>(7)?,
+                ))
+            })();
+            let (id, match_type, value, host, uncategorized_only, action, priority, schedule_id) =
+                match parsed {
+                    Ok(values) => values,
                     Err(e) => {
-                        error!("정규식 컴파일 실패 '{pattern_str}': {e}");
+                        error!("DB 행에서 파일 형식 규칙 추출 실패: {e}");
+                        continue;
                     }
-                },
+                };
+
+            let Some(match_type) = FileMatchType::parse(&match_type) else {
+                error!("알 수 없는 파일 형식 비교 대상 '{match_type}': 규칙 {id}");
+                continue;
+            };
+            let Some(meta) = rule_meta(id, &action, priority, schedule_id) else {
+                continue;
+            };
+            let Some(rule) = FileTypeRule::new(
+                meta,
+                match_type,
+                &value,
+                host.as_deref(),
+                uncategorized_only,
+            ) else {
+                error!("알 수 없는 매직 형식 '{value}': 규칙 {id}");
+                continue;
+            };
+            debug!("파일 형식 규칙 추가: {match_type} {value} ({action})");
+            rules.push(rule);
+        }
+
+        info!("파일 형식 규칙 로드 완료. {}개의 규칙 로드", rules.len());
+        Ok(FileTypeMatcher::new(rules))
+    }
+
+    /// 헤더 변경 규칙
+    async fn load_header_rules_from_db(
+        &self,
+        conn: &deadpool_postgres::Object,
+    ) -> Result<HeaderRewriter> {
+        debug!("데이터베이스에서 헤더 변경 규칙 로드 중...");
+
+        let pg_rows = conn
+            .query(sql::SELECT_ACTIVE_HEADER_RULES, &[])
+            .await
+            .map_err(|e| {
+                error!("헤더 변경 규칙 쿼리 실패: {e}");
+                ProxyError::Database(format!("DB query error: {e}"))
+            })?;
+
+        let mut rules = Vec::with_capacity(pg_rows.len());
+        for row in pg_rows {
+            let parsed = (|| -> std::result::Result<_, tokio_postgres::Error> {
+                Ok((
+                    row.try_get::<usize, i64>(0)?,
+                    row.try_get::<usize, Option<String>>(1)?,
+                    row.try_get::<usize, String>(2)?,
+                    row.try_get::<usize, String>(3)?,
+                    row.try_get::<usize, String>(4)?,
+                    row.try_get::<usize, Option<String>>(5)?,
+                    row.try_get::<usize, i32>(6)?,
+                    row.try_get::<usize, Option<i64>>(7)?,
+                ))
+            })();
+            let (id, host, direction, operation, name, value, priority, schedule_id) = match parsed
+            {
+                Ok(values) => values,
                 Err(e) => {
-                    error!("DB 행에서 패턴 문자열 추출 실패: {e}");
+                    error!("DB 행에서 헤더 변경 규칙 추출 실패: {e}");
+                    continue;
                 }
+            };
+
+            let Some(direction) = HeaderDirection::parse(&direction) else {
+                error!("알 수 없는 헤더 변경 대상 '{direction}': 규칙 {id}");
+                continue;
+            };
+            let Some(operation) = HeaderOperation::parse(&operation) else {
+                error!("알 수 없는 헤더 변경 방식 '{operation}': 규칙 {id}");
+                continue;
+            };
+            let name = name.trim().to_ascii_lowercase();
+            if !is_header_name(&name) {
+                error!("잘못된 헤더 이름 '{name}': 규칙 {id}");
+                continue;
             }
+            let value = match operation {
+                HeaderOperation::Set | HeaderOperation::Add => {
+                    let Some(template) = value.as_deref().and_then(HeaderTemplate::parse) else {
+                        error!("잘못된 헤더 값 템플릿 {value:?}: 규칙 {id}");
+                        continue;
+                    };
+                    Some(template)
+                }
+                HeaderOperation::Remove | HeaderOperation::Keep => None,
+            };
+            let host = host.filter(|host| !host.is_empty()).map(|host| {
+                let (domain, match_type) = MatchType::split_rule(&host, MatchType::Suffix);
+                (normalize_host(domain).into_owned(), match_type)
+            });
+
+            debug!("헤더 변경 규칙 추가: {direction} {operation} {name}");
+            rules.push(HeaderRewriteRule {
+                id,
+                priority,
+                schedule_id,
+                host,
+                direction,
+                operation,
+                name,
+                value,
+            });
         }
 
-        info!(
-            "도메인 차단 패턴 목록 로드 완료. {}개의 패턴 로드됨.",
-            regex_patterns_writer.len()
+        info!("헤더 변경 규칙 로드 완료. {}개의 규칙 로드", rules.len());
+        Ok(HeaderRewriter::new(rules))
+    }
+
+    /// 규칙 스케줄 (잘못된 스케줄을 쓰는 규칙은 적용되지 않음)
+    async fn load_schedules_from_db(
+        &self,
+        conn: &deadpool_postgres::Object,
+    ) -> Result<HashMap<i64, Schedule>> {
+        debug!("데이터베이스에서 규칙 스케줄 로드 중...");
+
+        let pg_rows = conn.query(sql::SELECT_SCHEDULES, &[]).await.map_err(|e| {
+            error!("규칙 스케줄 쿼리 실패: {e}");
+            ProxyError::Database(format!("DB query error: {e}"))
+        })?;
+
+        let mut schedules = HashMap::with_capacity(pg_rows.len());
+        for row in pg_rows {
+            let parsed = (|| -> std::result::Result<_, tokio_postgres::Error> {
+                Ok((
+                    row.try_get::<usize, i64>(0)?,
+                    row.try_get::<usize, String>(1)?,
+                    row.try_get::<usize, Option<String>>(2)?,
+                    row.try_get::<usize, Option<String>>(3)?,
+                    row.try_get::<usize, Option<String>>(4)?,
+                ))
+            })();
+            let (id, name, weekdays, time_ranges, timezone) = match parsed {
+                Ok(values) => values,
+                Err(e) => {
+                    error!("DB 행에서 스케줄 추출 실패: {e}");
+                    continue;
+                }
+            };
+
+            match Schedule::parse(
+                &name,
+                weekdays.as_deref(),
+                time_ranges.as_deref(),
+                timezone.as_deref(),
+            ) {
+                Ok(schedule) => {
+                    debug!("규칙 스케줄 추가: {name} ({id})");
+                    schedules.insert(id, schedule);
+                }
+                Err(e) => error!("스케줄 '{name}' 해석 실패, 해당 규칙 미적용: {e}"),
+            }
+        }
+
+        info!("규칙 스케줄 로드 완료. {}개의 스케줄 로드", schedules.len());
+        Ok(schedules)
+    }
+}
+
+/// 규칙 속성 변환 (알 수 없는 동작이면 규칙 무시)
+fn rule_meta(id: i64, action: &str, priority: i32, schedule_id: Option<i64>) -> Option<RuleMeta> {
+    let Some(action) = Action::parse(action) else {
+        error!("알 수 없는 규칙 동작 '{action}': 규칙 {id}");
+        return None;
+    };
+    Some(RuleMeta {
+        id,
+        action,
+        priority,
+        schedule_id,
+    })
+}
+
+/// IP 리터럴 호스트 (정규화된 호스트, IPv6는 대괄호 없음)
+fn literal_ip(host: &str) -> Option<IpAddr> {
+    host.parse().ok()
+}
+
+/// HTTP 헤더 이름 문자 검증 (RFC 9110 token)
+fn is_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// 현재 적용 중인 스케줄 ID
+fn active_schedule_ids(schedules: &HashMap<i64, Schedule>) -> HashSet<i64> {
+    let now = Utc::now();
+    schedules
+        .iter()
+        .filter(|(_, schedule)| schedule.is_active(now))
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(id: i64, action: Action, priority: i32) -> RuleMeta {
+        RuleMeta {
+            id,
+            action,
+            priority,
+            schedule_id: None,
+        }
+    }
+
+    fn blocker(setup: impl FnOnce(&mut Blocklists)) -> DomainBlocker {
+        let blocker = DomainBlocker::new(16);
+        let mut blocklists = Blocklists::empty();
+        setup(&mut blocklists);
+        blocker.swap_blocklists(blocklists).unwrap();
+        blocker
+    }
+
+    /// 호스트 판정에 조회 결과 주소의 IP 규칙을 더한 판정
+    fn resolved(blocker: &DomainBlocker, host: &str, addr: &str) -> (Action, Option<i64>) {
+        let rules = blocker.rules.load();
+        let verdict = DomainBlocker::evaluate_ip(
+            &rules,
+            addr.parse().unwrap(),
+            "resolved",
+            blocker.check_host(host),
+            &HashSet::new(),
         );
+        let decision = verdict.decision.unwrap();
+        (decision.action, decision.rule_id)
+    }
 
-        Ok(())
+    #[test]
+    fn domain_rule_beats_ip_rule_at_equal_priority() {
+        let blocker = blocker(|rules| {
+            rules
+                .domains
+                .insert("example.com", MatchType::Exact, meta(1, Action::Allow, 0));
+            rules
+                .domains
+                .insert("suffix.test", MatchType::Suffix, meta(2, Action::Allow, 0));
+            rules
+                .ip_rules
+                .insert("93.184.0.0/16".parse().unwrap(), meta(3, Action::Block, 0));
+            rules
+                .ip_rules
+                .insert("10.0.0.0/8".parse().unwrap(), meta(4, Action::Block, 0));
+        });
+
+        // 프리픽스 길이와 관계없이 호스트 이름 규칙이 이김
+        assert_eq!(
+            resolved(&blocker, "example.com", "93.184.216.34"),
+            (Action::Allow, Some(1))
+        );
+        assert_eq!(
+            resolved(&blocker, "example.com", "10.1.2.3"),
+            (Action::Allow, Some(1))
+        );
+        assert_eq!(
+            resolved(&blocker, "www.suffix.test", "93.184.216.34"),
+            (Action::Allow, Some(2))
+        );
+        // 호스트 규칙이 없으면 IP 규칙 적용
+        assert_eq!(
+            resolved(&blocker, "other.test", "93.184.216.34"),
+            (Action::Block, Some(3))
+        );
+    }
+
+    #[test]
+    fn ip_rule_wins_by_priority_and_prefix_length() {
+        let blocker = blocker(|rules| {
+            rules
+                .domains
+                .insert("example.com", MatchType::Exact, meta(1, Action::Allow, 0));
+            rules
+                .ip_rules
+                .insert("93.184.0.0/16".parse().unwrap(), meta(2, Action::Allow, 5));
+            rules.ip_rules.insert(
+                "93.184.216.0/24".parse().unwrap(),
+                meta(3, Action::Block, 5),
+            );
+            rules
+                .ip_rules
+                .insert("10.0.0.0/8".parse().unwrap(), meta(4, Action::Block, 0));
+            let (patterns, invalid) =
+                PatternSet::compile(vec![(r"^pattern\.".to_string(), meta(5, Action::Allow, 0))])
+                    .unwrap();
+            assert!(invalid.is_empty());
+            rules.patterns = patterns;
+        });
+
+        // 우선순위가 높은 IP 규칙끼리는 더 긴 프리픽스가 이김
+        assert_eq!(
+            resolved(&blocker, "example.com", "93.184.216.34"),
+            (Action::Block, Some(3))
+        );
+        assert_eq!(
+            resolved(&blocker, "example.com", "93.184.1.1"),
+            (Action::Allow, Some(2))
+        );
+        // 호스트 조건이 없는 패턴 규칙보다는 IP 규칙이 이김
+        assert_eq!(
+            resolved(&blocker, "pattern.test", "10.1.2.3"),
+            (Action::Block, Some(4))
+        );
+        // IP 리터럴 호스트
+        let decision = blocker.check_host("93.184.216.34").decision.unwrap();
+        assert_eq!(decision.rule_id, Some(3));
+    }
+
+    #[test]
+    fn url_rule_beats_host_rule() {
+        let blocker = blocker(|rules| {
+            rules
+                .domains
+                .insert("example.com", MatchType::Exact, meta(1, Action::Block, 0));
+            rules.url_rules = UrlMatcher::new(vec![(
+                ".example.com".to_string(),
+                UrlRule::new(
+                    meta(2, Action::Allow, 0),
+                    None,
+                    "/public/",
+                    PathMatchType::Prefix,
+                    None,
+                    false,
+                ),
+            )]);
+        });
+
+        let decision = |path: &str| {
+            let decision = blocker
+                .check_request("GET", "example.com", path)
+                .decision
+                .unwrap();
+            (decision.rule_type, decision.rule_id)
+        };
+        assert_eq!(decision("/public/a"), (RuleType::Url, Some(2)));
+        assert_eq!(decision("/private"), (RuleType::Domain, Some(1)));
+        // CONNECT 판정은 호스트 규칙만 사용
+        assert_eq!(
+            blocker.check_host("example.com").decision.unwrap().rule_id,
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn exact_beats_wildcard_beats_suffix_across_tables() {
+        let blocker = blocker(|rules| {
+            rules
+                .domains
+                .insert("example.com", MatchType::Suffix, meta(1, Action::Block, 0));
+            rules
+                .domains
+                .insert("example.com", MatchType::Wildcard, meta(2, Action::Warn, 0));
+            rules.category_domains.insert(
+                "www.example.com",
+                MatchType::Exact,
+                (meta(3, Action::Allow, 0), Arc::from("news")),
+            );
+        });
+
+        let rule_id = |host: &str| blocker.check_host(host).decision.unwrap().rule_id;
+        assert_eq!(rule_id("www.example.com"), Some(3));
+        assert_eq!(rule_id("api.example.com"), Some(2));
+        assert_eq!(rule_id("example.com"), Some(1));
+    }
+
+    #[test]
+    fn inactive_schedule_rule_is_ignored() {
+        let blocker = blocker(|rules| {
+            rules.domains.insert(
+                "example.com",
+                MatchType::Exact,
+                RuleMeta {
+                    schedule_id: Some(7),
+                    ..meta(1, Action::Block, 10)
+                },
+            );
+            rules
+                .domains
+                .insert("example.com", MatchType::Suffix, meta(2, Action::Allow, 0));
+        });
+        assert_eq!(
+            blocker.check_host("example.com").decision.unwrap().rule_id,
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn failed_reload_keeps_expired_deadline() {
+        // 재로드 실패시 지난 만료 시각은 비우지 않고 재시도 시각으로 미룸
+        let blocker = DomainBlocker::new(16);
+        let expired = Instant::now();
+        *blocker.next_expiry.lock().unwrap() = Some(expired);
+        blocker.defer_next_expiry(Duration::from_secs(5));
+        let retry_at = blocker.next_expiry.lock().unwrap().unwrap();
+        assert!(retry_at >= expired + Duration::from_secs(5));
+
+        // 아직 오지 않은 만료 시각은 그대로 유지
+        blocker.defer_next_expiry(Duration::from_secs(60));
+        assert_eq!(*blocker.next_expiry.lock().unwrap(), Some(retry_at));
+
+        // 만료 예정 규칙이 없으면 재시도하지 않음
+        *blocker.next_expiry.lock().unwrap() = None;
+        blocker.defer_next_expiry(Duration::from_secs(5));
+        assert!(blocker.next_expiry.lock().unwrap().is_none());
     }
 }
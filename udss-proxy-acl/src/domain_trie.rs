@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::host::normalize_host;
+
+/// 도메인 규칙 일치 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchType {
+    /// 도메인만 일치
+    Exact,
+    /// 도메인과 모든 하위 도메인 (`.example.com`)
+    Suffix,
+    /// 하위 도메인만 (`*.example.com`)
+    Wildcard,
+}
+
+impl MatchType {
+    /// DB `match_type` 값 변환
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "exact" => Some(Self::Exact),
+            "suffix" => Some(Self::Suffix),
+            "wildcard" => Some(Self::Wildcard),
+            _ => None,
+        }
+    }
+
+    /// 규칙 문자열의 접두어(`*.`, `.`)로 일치 방식 결정 (접두어 제거한 도메인 반환)
+    pub fn split_rule(domain: &str, default: Self) -> (&str, Self) {
+        if let Some(rest) = domain.strip_prefix("*.") {
+            (rest, Self::Wildcard)
+        } else if let Some(rest) = domain.strip_prefix('.') {
+            (rest, Self::Suffix)
+        } else {
+            (domain, default)
+        }
+    }
+
+    /// 단일 규칙 비교 (`domain`, `host`는 `normalize_host`로 정규화된 값)
+    pub fn matches(self, domain: &str, host: &str) -> bool {
+        let is_subdomain = host
+            .strip_suffix(domain)
+            .is_some_and(|rest| rest.ends_with('.'));
+        match self {
+            Self::Exact => host == domain,
+            Self::Suffix => host == domain || is_subdomain,
+            Self::Wildcard => is_subdomain,
+        }
+    }
+}
+
+impl fmt::Display for MatchType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Exact => "exact",
+            Self::Suffix => "suffix",
+            Self::Wildcard => "wildcard",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 레이블 역순 트라이 노드
+#[derive(Debug)]
+struct Node<T> {
+    children: HashMap<Box<str>, Node<T>>,
+    exact: Option<T>,
+    suffix: Option<T>,
+    wildcard: Option<T>,
+}
+
+impl<T> Default for Node<T> {
+    fn default() -> Self {
+        Self {
+            children: HashMap::new(),
+            exact: None,
+            suffix: None,
+            wildcard: None,
+        }
+    }
+}
+
+/// 레이블 역순(`com` -> `example` -> `ads`) 도메인 트라이
+///
+/// 조회 비용은 규칙 수와 무관하게 호스트 레이블 수에 비례한다.
+#[derive(Debug)]
+pub struct DomainTrie<T> {
+    root: Node<T>,
+    len: usize,
+}
+
+impl<T> Default for DomainTrie<T> {
+    fn default() -> Self {
+        Self {
+            root: Node::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> DomainTrie<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 규칙 수
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 규칙 추가 (같은 도메인/방식이 있으면 교체)
+    pub fn insert(&mut self, domain: &str, match_type: MatchType, value: T) {
+        let domain = normalize_host(domain);
+        let mut node = &mut self.root;
+        for label in domain.rsplit('.') {
+            node = node.children.entry(label.into()).or_default();
+        }
+
+        let slot = match match_type {
+            MatchType::Exact => &mut node.exact,
+            MatchType::Suffix => &mut node.suffix,
+            MatchType::Wildcard => &mut node.wildcard,
+        };
+        if slot.replace(value).is_none() {
+            self.len += 1;
+        }
+    }
+
+    /// 호스트에 일치하는 모든 규칙과 규칙 도메인 레이블 수 (구체적인 규칙 우선)
+    pub fn find_all(&self, host: &str) -> Vec<(MatchType, usize, &T)> {
+        let host = normalize_host(host);
+        let mut labels = host.rsplit('.').peekable();
+        let mut node = &self.root;
+        let mut depth = 0;
+        let mut found = Vec::new();
+
+        while let Some(label) = labels.next() {
+            let Some(child) = node.children.get(label) else {
+                break;
+            };
+            node = child;
+            depth += 1;
+
+            if let Some(value) = &node.suffix {
+                found.push((MatchType::Suffix, depth, value));
+            }
+            if labels.peek().is_some() {
+                if let Some(value) = &node.wildcard {
+                    found.push((MatchType::Wildcard, depth, value));
+                }
+            } else if let Some(value) = &node.exact {
+                found.push((MatchType::Exact, depth, value));
+            }
+        }
+
+        found.reverse();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trie() -> DomainTrie<&'static str> {
+        let mut trie = DomainTrie::new();
+        trie.insert("example.com", MatchType::Suffix, "suffix");
+        trie.insert("Example.COM.", MatchType::Exact, "exact");
+        trie.insert("example.com", MatchType::Wildcard, "wildcard");
+        trie.insert("ads.example.com", MatchType::Exact, "ads");
+        trie
+    }
+
+    fn found(trie: &DomainTrie<&'static str>, host: &str) -> Vec<(MatchType, usize, &'static str)> {
+        trie.find_all(host)
+            .into_iter()
+            .map(|(match_type, depth, value)| (match_type, depth, *value))
+            .collect()
+    }
+
+    #[test]
+    fn find_all_orders_most_specific_first() {
+        let trie = trie();
+        assert_eq!(trie.len(), 4);
+        assert_eq!(
+            found(&trie, "example.com"),
+            [
+                (MatchType::Exact, 2, "exact"),
+                (MatchType::Suffix, 2, "suffix")
+            ]
+        );
+        assert_eq!(
+            found(&trie, "ADS.example.com."),
+            [
+                (MatchType::Exact, 3, "ads"),
+                (MatchType::Wildcard, 2, "wildcard"),
+                (MatchType::Suffix, 2, "suffix"),
+            ]
+        );
+        assert_eq!(
+            found(&trie, "a.b.example.com"),
+            [
+                (MatchType::Wildcard, 2, "wildcard"),
+                (MatchType::Suffix, 2, "suffix"),
+            ]
+        );
+        assert!(found(&trie, "notexample.com").is_empty());
+        assert!(found(&trie, "com").is_empty());
+    }
+
+    #[test]
+    fn insert_replaces_same_rule() {
+        let mut trie = trie();
+        trie.insert("example.com", MatchType::Exact, "replaced");
+        assert_eq!(trie.len(), 4);
+        assert_eq!(
+            found(&trie, "example.com")[0],
+            (MatchType::Exact, 2, "replaced")
+        );
+    }
+
+    #[test]
+    fn split_rule_and_matches() {
+        assert_eq!(
+            MatchType::split_rule("*.example.com", MatchType::Exact),
+            ("example.com", MatchType::Wildcard)
+        );
+        assert_eq!(
+            MatchType::split_rule(".example.com", MatchType::Exact),
+            ("example.com", MatchType::Suffix)
+        );
+        assert_eq!(
+            MatchType::split_rule("example.com", MatchType::Exact),
+            ("example.com", MatchType::Exact)
+        );
+
+        assert!(MatchType::Suffix.matches("example.com", "example.com"));
+        assert!(MatchType::Suffix.matches("example.com", "a.example.com"));
+        assert!(!MatchType::Suffix.matches("example.com", "badexample.com"));
+        assert!(!MatchType::Wildcard.matches("example.com", "example.com"));
+        assert!(!MatchType::Exact.matches("example.com", "a.example.com"));
+    }
+}
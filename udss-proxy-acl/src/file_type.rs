@@ -0,0 +1,436 @@
+use std::fmt;
+
+use crate::decision::RuleMeta;
+use crate::domain_trie::MatchType;
+use crate::host::normalize_host;
+use crate::url_matcher::{normalize_path, percent_decode};
+
+/// 매직 바이트 검사 길이 (응답 첫 부분)
+pub const SNIFF_LEN: usize = 512;
+
+/// 시그니처 위치와 바이트
+type MagicPart = (usize, &'static [u8]);
+
+/// 파일 형식별 매직 바이트 (위치, 바이트가 모두 일치해야 함)
+///
+/// 평문과 겹치지 않도록 짧은 시그니처는 뒤따르는 고정 바이트까지 확인한다.
+const MAGIC_SIGNATURES: [(&str, &[MagicPart]); 13] = [
+    ("elf", &[(0, b"\x7fELF")]),
+    ("macho", &[(0, b"\xcf\xfa\xed\xfe")]),
+    ("macho", &[(0, b"\xce\xfa\xed\xfe")]),
+    ("macho", &[(0, b"\xca\xfe\xba\xbe")]),
+    ("ole", &[(0, b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1")]),
+    ("zip", &[(0, b"PK\x03\x04")]),
+    ("rar", &[(0, b"Rar!\x1a\x07")]),
+    ("7z", &[(0, b"7z\xbc\xaf\x27\x1c")]),
+    // 압축 방식 deflate 고정
+    ("gzip", &[(0, b"\x1f\x8b\x08")]),
+    // 스트림 헤더 뒤 첫 블록 매직 (pi)
+    ("bzip2", &[(0, b"BZh"), (4, b"1AY&SY")]),
+    ("xz", &[(0, b"\xfd7zXZ\x00")]),
+    ("cab", &[(0, b"MSCF\x00\x00\x00\x00")]),
+    ("pdf", &[(0, b"%PDF-")]),
+];
+
+/// PE 실행 파일 형식 이름 (`MZ` 헤더의 `e_lfanew` 위치에서 `PE\0\0` 확인)
+const PE_KIND: &str = "exe";
+
+/// 응답 첫 부분으로 판별한 형식 목록
+fn sniff(head: &[u8]) -> Vec<&'static str> {
+    let mut kinds: Vec<&'static str> = MAGIC_SIGNATURES
+        .iter()
+        .filter(|(_, parts)| {
+            parts.iter().all(|(offset, bytes)| {
+                head.get(*offset..)
+                    .is_some_and(|rest| rest.starts_with(bytes))
+            })
+        })
+        .map(|(kind, _)| *kind)
+        .collect();
+    if is_pe(head) {
+        kinds.push(PE_KIND);
+    }
+    kinds
+}
+
+/// DOS 헤더(`MZ`)가 가리키는 PE 헤더 확인 (검사 길이 안에 있을 때만)
+fn is_pe(head: &[u8]) -> bool {
+    if !head.starts_with(b"MZ") {
+        return false;
+    }
+    let Some(offset) = head
+        .get(0x3c..0x40)
+        .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok())
+        .and_then(|bytes| usize::try_from(u32::from_le_bytes(bytes)).ok())
+    else {
+        return false;
+    };
+    head.get(offset..)
+        .is_some_and(|rest| rest.starts_with(b"PE\0\0"))
+}
+
+/// 파일 형식 규칙 비교 대상
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileMatchType {
+    /// URL 경로, `Content-Disposition` 파일명 확장자
+    Extension,
+    /// 응답 `Content-Type` (`type/*` 허용)
+    ContentType,
+    /// 응답 첫 부분 매직 바이트 (`exe`, `zip` 등 형식 이름)
+    Magic,
+}
+
+impl FileMatchType {
+    /// DB `match_type` 값 변환
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "extension" => Some(Self::Extension),
+            "content_type" => Some(Self::ContentType),
+            "magic" => Some(Self::Magic),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FileMatchType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Extension => "extension",
+            Self::ContentType => "content_type",
+            Self::Magic => "magic",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 다운로드 파일 형식 규칙
+#[derive(Debug, Clone)]
+pub struct FileTypeRule {
+    /// 규칙 ID, 동작, 우선순위
+    pub meta: RuleMeta,
+    /// 비교 대상
+    pub match_type: FileMatchType,
+    /// 확장자(점 제외), MIME 타입 또는 매직 형식 이름 (소문자)
+    pub value: String,
+    /// 적용 호스트 (없으면 모든 호스트)
+    pub host: Option<(String, MatchType)>,
+    /// 카테고리에 속하지 않은 호스트에만 적용
+    pub uncategorized_only: bool,
+}
+
+impl FileTypeRule {
+    /// 새 규칙 생성 (알 수 없는 매직 형식이면 None)
+    pub fn new(
+        meta: RuleMeta,
+        match_type: FileMatchType,
+        value: &str,
+        host: Option<&str>,
+        uncategorized_only: bool,
+    ) -> Option<Self> {
+        let value = value.trim().trim_start_matches('.').to_ascii_lowercase();
+        if match_type == FileMatchType::Magic
+            && value != PE_KIND
+            && !MAGIC_SIGNATURES.iter().any(|(kind, _)| *kind == value)
+        {
+            return None;
+        }
+        let host = host.filter(|host| !host.is_empty()).map(|host| {
+            let (domain, match_type) = MatchType::split_rule(host, MatchType::Suffix);
+            (normalize_host(domain).into_owned(), match_type)
+        });
+
+        Some(Self {
+            meta,
+            match_type,
+            value,
+            host,
+            uncategorized_only,
+        })
+    }
+
+    /// 적용 호스트 레이블 수 (호스트 조건이 없으면 0)
+    pub fn host_depth(&self) -> usize {
+        self.host
+            .as_ref()
+            .map_or(0, |(domain, _)| domain.split('.').count())
+    }
+
+    fn applies_to(&self, host: &str, categorized: bool) -> bool {
+        if self.uncategorized_only && categorized {
+            return false;
+        }
+        self.host
+            .as_ref()
+            .is_none_or(|(domain, match_type)| match_type.matches(domain, host))
+    }
+
+    fn matches(&self, download: &Download) -> Option<String> {
+        match self.match_type {
+            FileMatchType::Extension => {
+                let suffix = format!(".{}", self.value);
+                download
+                    .file_names
+                    .iter()
+                    .find(|name| name.ends_with(&suffix))
+                    .map(|name| format!("extension '{}' ({name})", self.value))
+            }
+            FileMatchType::ContentType => {
+                let content_type = download.content_type.as_deref()?;
+                let matched = match self.value.strip_suffix("/*") {
+                    Some(top_level) => content_type
+                        .split_once('/')
+                        .is_some_and(|(kind, _)| kind == top_level),
+                    None => content_type == self.value,
+                };
+                matched.then(|| format!("content type '{content_type}'"))
+            }
+            FileMatchType::Magic => download
+                .magic
+                .contains(&self.value.as_str())
+                .then(|| format!("file signature '{}'", self.value)),
+        }
+    }
+}
+
+/// 응답에서 추출한 다운로드 정보
+#[derive(Debug)]
+pub struct Download {
+    /// URL 경로, `Content-Disposition`의 파일명 (소문자)
+    file_names: Vec<String>,
+    /// 파라미터를 제외한 MIME 타입 (소문자)
+    content_type: Option<String>,
+    /// 매직 바이트로 판별한 형식
+    magic: Vec<&'static str>,
+}
+
+impl Download {
+    /// 요청 경로와 응답 헤더, 응답 첫 부분으로 생성
+    pub fn new(
+        path_and_query: &str,
+        content_type: Option<&str>,
+        content_disposition: Option<&str>,
+        body: &[u8],
+    ) -> Self {
+        let mut file_names = Vec::new();
+        let path = path_and_query.split(['?', '#']).next().unwrap_or_default();
+        if let Some(name) = normalize_path(path).rsplit('/').next()
+            && !name.is_empty()
+        {
+            file_names.push(name.to_lowercase());
+        }
+        if let Some(name) = content_disposition.and_then(disposition_file_name) {
+            file_names.push(name.to_lowercase());
+        }
+
+        let content_type = content_type
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase())
+            .filter(|value| !value.is_empty());
+
+        let magic = sniff(&body[..body.len().min(SNIFF_LEN)]);
+
+        Self {
+            file_names,
+            content_type,
+            magic,
+        }
+    }
+}
+
+/// 다운로드 파일 형식 규칙 매처
+#[derive(Debug, Default)]
+pub struct FileTypeMatcher {
+    rules: Vec<FileTypeRule>,
+}
+
+impl FileTypeMatcher {
+    pub fn new(rules: Vec<FileTypeRule>) -> Self {
+        Self { rules }
+    }
+
+    /// 규칙 수
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 호스트와 다운로드에 일치하는 모든 규칙과 일치 사유
+    pub fn find_all(
+        &self,
+        host: &str,
+        categorized: bool,
+        download: &Download,
+    ) -> Vec<(&FileTypeRule, String)> {
+        let host = normalize_host(host);
+        self.rules
+            .iter()
+            .filter(|rule| rule.applies_to(&host, categorized))
+            .filter_map(|rule| rule.matches(download).map(|reason| (rule, reason)))
+            .collect()
+    }
+
+    /// 카테고리 조건이 있는 규칙 존재 여부
+    pub fn has_uncategorized_rules(&self) -> bool {
+        self.rules.iter().any(|rule| rule.uncategorized_only)
+    }
+}
+
+/// `Content-Disposition` 파일명 (`filename*` 우선)
+fn disposition_file_name(value: &str) -> Option<String> {
+    let mut file_name = None;
+    for param in value.split(';').map(str::trim) {
+        let Some((name, value)) = param.split_once('=') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            // RFC 5987 (`UTF-8''setup%2Eexe`), 인코딩 해제 후 확장자 비교
+            "filename*" => {
+                return value
+                    .rsplit('\'')
+                    .next()
+                    .map(|name| percent_decode(name.trim_matches('"'), false));
+            }
+            "filename" => file_name = Some(value.trim().trim_matches('"').to_string()),
+            _ => {}
+        }
+    }
+    file_name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decision::Action;
+
+    fn rule(match_type: FileMatchType, value: &str) -> FileTypeRule {
+        let meta = RuleMeta {
+            id: 1,
+            action: Action::Block,
+            priority: 0,
+            schedule_id: None,
+        };
+        FileTypeRule::new(meta, match_type, value, None, false).unwrap()
+    }
+
+    fn matches(rule: &FileTypeRule, download: &Download) -> bool {
+        FileTypeMatcher::new(vec![rule.clone()])
+            .find_all("example.com", false, download)
+            .len()
+            == 1
+    }
+
+    /// `e_lfanew`가 `offset`을 가리키는 최소 PE 헤더
+    fn pe_header(offset: usize) -> Vec<u8> {
+        let mut body = vec![0u8; offset + 4];
+        body[..2].copy_from_slice(b"MZ");
+        body[0x3c..0x40].copy_from_slice(&u32::try_from(offset).unwrap().to_le_bytes());
+        body[offset..].copy_from_slice(b"PE\0\0");
+        body
+    }
+
+    fn sniffed(body: &[u8]) -> Vec<&'static str> {
+        Download::new("/download", None, None, body).magic
+    }
+
+    #[test]
+    fn sniffs_pe_executable_by_pe_header() {
+        assert_eq!(sniffed(&pe_header(0x80)), ["exe"]);
+        // PE 헤더가 검사 길이를 벗어나거나 다른 위치에 있으면 판별하지 않음
+        assert!(sniffed(&pe_header(SNIFF_LEN)).is_empty());
+        let mut body = pe_header(0x80);
+        body[0x3c] = 0x40;
+        assert!(sniffed(&body).is_empty());
+    }
+
+    #[test]
+    fn plain_text_does_not_match_short_signatures() {
+        // 평문 시작이 짧은 시그니처와 같아도 형식으로 보지 않음
+        assert!(sniffed(b"MZ is a two letter code used in many plain text files.").is_empty());
+        assert!(sniffed(b"BZh... just some text that starts like bzip2").is_empty());
+        assert!(sniffed(b"MSCF report").is_empty());
+    }
+
+    #[test]
+    fn sniffs_archive_signatures() {
+        assert_eq!(sniffed(b"BZh91AY&SY\x00\x01"), ["bzip2"]);
+        assert_eq!(sniffed(b"\x1f\x8b\x08\x00\x00\x00"), ["gzip"]);
+        assert!(sniffed(b"\x1f\x8b\x00\x00").is_empty());
+        assert_eq!(sniffed(b"PK\x03\x04\x14\x00"), ["zip"]);
+        assert_eq!(sniffed(b"%PDF-1.7\n"), ["pdf"]);
+
+        let download = Download::new("/a", None, None, &pe_header(0x80));
+        assert!(matches(&rule(FileMatchType::Magic, "exe"), &download));
+        assert!(!matches(&rule(FileMatchType::Magic, "zip"), &download));
+    }
+
+    #[test]
+    fn unknown_magic_kind_is_rejected() {
+        let meta = RuleMeta {
+            id: 1,
+            action: Action::Block,
+            priority: 0,
+            schedule_id: None,
+        };
+        assert!(FileTypeRule::new(meta, FileMatchType::Magic, "jpeg", None, false).is_none());
+    }
+
+    #[test]
+    fn disposition_file_name_decodes_extended_parameter() {
+        assert_eq!(
+            disposition_file_name("attachment; filename*=UTF-8''setup%2Eexe").as_deref(),
+            Some("setup.exe")
+        );
+        // filename*가 filename보다 우선
+        assert_eq!(
+            disposition_file_name(
+                "attachment; filename=\"report.txt\"; filename*=UTF-8''%EB%B3%B4%EA%B3%A0%EC%84%9C.exe"
+            )
+            .as_deref(),
+            Some("보고서.exe")
+        );
+        assert_eq!(
+            disposition_file_name("attachment; filename=\"report.pdf\"").as_deref(),
+            Some("report.pdf")
+        );
+        assert_eq!(disposition_file_name("inline"), None);
+    }
+
+    #[test]
+    fn extension_rule_matches_decoded_disposition_name() {
+        let exe = rule(FileMatchType::Extension, ".EXE");
+        let download = Download::new(
+            "/download?id=1",
+            None,
+            Some("attachment; filename*=UTF-8''Setup%2EExe"),
+            b"",
+        );
+        assert!(matches(&exe, &download));
+        assert!(!matches(
+            &exe,
+            &Download::new("/setup.exe.txt", None, None, b"")
+        ));
+        assert!(matches(
+            &exe,
+            &Download::new("/files/setup.exe?v=2", None, None, b"")
+        ));
+    }
+
+    #[test]
+    fn content_type_wildcard_matches_top_level_type() {
+        let any_application = rule(FileMatchType::ContentType, "application/*");
+        let download = |content_type| Download::new("/a", Some(content_type), None, b"");
+        assert!(matches(
+            &any_application,
+            &download("Application/X-MSDownload; charset=binary")
+        ));
+        assert!(!matches(&any_application, &download("text/plain")));
+        assert!(!matches(&any_application, &download("applicationx/foo")));
+
+        let exact = rule(FileMatchType::ContentType, "application/zip");
+        assert!(matches(&exact, &download("application/zip")));
+        assert!(!matches(&exact, &download("application/zip-compressed")));
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+
+use crate::decision::host_rank;
+use crate::domain_trie::MatchType;
+use crate::host::normalize_host;
+
+/// 헤더 변경 대상
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderDirection {
+    /// 업스트림으로 보내는 요청 헤더
+    Request,
+    /// 클라이언트로 보내는 응답 헤더
+    Response,
+}
+
+impl HeaderDirection {
+    /// DB `direction` 값 변환
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "request" => Some(Self::Request),
+            "response" => Some(Self::Response),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HeaderDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Request => "request",
+            Self::Response => "response",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 헤더 변경 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderOperation {
+    /// 기존 값을 모두 지우고 설정
+    Set,
+    /// 기존 값 뒤에 추가
+    Add,
+    /// 삭제
+    Remove,
+    /// 변경하지 않음 (덜 구체적인 규칙의 예외)
+    Keep,
+}
+
+impl HeaderOperation {
+    /// DB `operation` 값 변환
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "set" => Some(Self::Set),
+            "add" => Some(Self::Add),
+            "remove" => Some(Self::Remove),
+            "keep" => Some(Self::Keep),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HeaderOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Set => "set",
+            Self::Add => "add",
+            Self::Remove => "remove",
+            Self::Keep => "keep",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 헤더 값 템플릿 조각
+#[derive(Debug, Clone)]
+enum TemplatePart {
+    Literal(String),
+    ClientIp,
+    User,
+    Host,
+}
+
+/// 헤더 값 템플릿 (`{client_ip}`, `{user}`, `{host}` 치환, `{{`, `}}`는 중괄호)
+#[derive(Debug, Clone)]
+pub struct HeaderTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+impl HeaderTemplate {
+    /// 템플릿 해석 (알 수 없는 변수나 닫히지 않은 중괄호면 None)
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut rest = value;
+        while let Some(index) = rest.find(['{', '}']) {
+            literal.push_str(&rest[..index]);
+            let tail = &rest[index..];
+            if let Some(tail) = tail.strip_prefix("{{") {
+                literal.push('{');
+                rest = tail;
+                continue;
+            }
+            if let Some(tail) = tail.strip_prefix("}}") {
+                literal.push('}');
+                rest = tail;
+                continue;
+            }
+            let (name, tail) = tail.strip_prefix('{')?.split_once('}')?;
+            let part = match name.trim() {
+                "client_ip" => TemplatePart::ClientIp,
+                "user" => TemplatePart::User,
+                "host" => TemplatePart::Host,
+                _ => return None,
+            };
+            if !literal.is_empty() {
+                parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+            }
+            parts.push(part);
+            rest = tail;
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+        Some(Self { parts })
+    }
+
+    /// 값 생성 (`{user}`를 쓰는데 사용자를 알 수 없으면 None)
+    fn render(&self, host: &str, context: &HeaderContext<'_>) -> Option<String> {
+        let mut value = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Literal(literal) => value.push_str(literal),
+                TemplatePart::ClientIp => value.push_str(&context.client_ip.to_string()),
+                TemplatePart::User => value.push_str(context.user?),
+                TemplatePart::Host => value.push_str(host),
+            }
+        }
+        Some(value)
+    }
+}
+
+/// 템플릿 치환 값
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderContext<'a> {
+    /// 클라이언트 IP
+    pub client_ip: IpAddr,
+    /// 인증된 사용자 (HTTPS 리스너 클라이언트 인증서, 없으면 None)
+    pub user: Option<&'a str>,
+}
+
+/// 헤더 변경 규칙
+#[derive(Debug, Clone)]
+pub struct HeaderRewriteRule {
+    /// 규칙 ID
+    pub id: i64,
+    /// 우선순위 (클수록 우선)
+    pub priority: i32,
+    /// 적용 스케줄 (None이면 항상 적용)
+    pub schedule_id: Option<i64>,
+    /// 적용 호스트 (없으면 모든 호스트)
+    pub host: Option<(String, MatchType)>,
+    pub direction: HeaderDirection,
+    pub operation: HeaderOperation,
+    /// 헤더 이름 (소문자)
+    pub name: String,
+    /// 설정/추가할 값 (`remove`, `keep`이면 None)
+    pub value: Option<HeaderTemplate>,
+}
+
+impl HeaderRewriteRule {
+    /// 규칙 비교 키 (우선순위, 호스트 구체성, ID 순)
+    fn rank(&self) -> (i32, u32, i64) {
+        let host = self.host.as_ref().map_or(0, |(domain, match_type)| {
+            host_rank(*match_type, domain.split('.').count())
+        });
+        (self.priority, host, self.id)
+    }
+
+    fn applies_to(&self, host: &str) -> bool {
+        self.host
+            .as_ref()
+            .is_none_or(|(domain, match_type)| match_type.matches(domain, host))
+    }
+}
+
+/// 적용할 헤더 변경
+#[derive(Debug, Clone)]
+pub struct HeaderEdit {
+    /// 규칙 ID
+    pub rule_id: i64,
+    pub operation: HeaderOperation,
+    /// 헤더 이름 (소문자)
+    pub name: String,
+    /// 설정/추가할 값
+    pub value: Option<String>,
+}
+
+/// 헤더 변경 규칙 매처
+///
+/// 같은 방향, 같은 헤더에 여러 규칙이 일치하면 우선순위, 더 구체적인 호스트,
+/// 나중에 만든 규칙 순으로 하나만 적용한다.
+#[derive(Debug, Default)]
+pub struct HeaderRewriter {
+    rules: Vec<HeaderRewriteRule>,
+}
+
+impl HeaderRewriter {
+    pub fn new(rules: Vec<HeaderRewriteRule>) -> Self {
+        Self { rules }
+    }
+
+    /// 규칙 수
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 호스트에 적용할 헤더 변경 (`is_scheduled`: 스케줄 ID 적용 여부)
+    pub fn edits(
+        &self,
+        direction: HeaderDirection,
+        host: &str,
+        context: &HeaderContext<'_>,
+        is_scheduled: impl Fn(Option<i64>) -> bool,
+    ) -> Vec<HeaderEdit> {
+        let host = normalize_host(host);
+        let mut winners: HashMap<&str, &HeaderRewriteRule> = HashMap::new();
+        for rule in self.rules.iter().filter(|rule| {
+            rule.direction == direction && rule.applies_to(&host) && is_scheduled(rule.schedule_id)
+        }) {
+            let winner = winners.entry(rule.name.as_str()).or_insert(rule);
+            if rule.rank() > winner.rank() {
+                *winner = rule;
+            }
+        }
+
+        winners
+            .into_values()
+            .filter(|rule| rule.operation != HeaderOperation::Keep)
+            .filter_map(|rule| {
+                let value = match &rule.value {
+                    Some(template) => Some(template.render(&host, context)?),
+                    None => None,
+                };
+                Some(HeaderEdit {
+                    rule_id: rule.id,
+                    operation: rule.operation,
+                    name: rule.name.clone(),
+                    value,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,63 @@
+use std::borrow::Cow;
+use std::net::Ipv6Addr;
+
+use idna::AsciiDenyList;
+use log::debug;
+
+/// 규칙 비교용 호스트 정규화
+///
+/// 소문자 변환, 끝 `.` 제거, 국제화 도메인은 퓨니코드(`xn--`) 변환,
+/// IPv6 리터럴은 대괄호를 제거한 표준 표기로 바꾼다.
+/// 요청 호스트와 DB 규칙 값 모두 이 함수로 정규화해야 같은 값으로 비교된다.
+pub fn normalize_host(host: &str) -> Cow<'_, str> {
+    let host = host.trim();
+    if let Some(addr) = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .and_then(|host| host.parse::<Ipv6Addr>().ok())
+    {
+        return Cow::Owned(addr.to_string());
+    }
+
+    let host = host.trim_end_matches('.');
+    match idna::domain_to_ascii_cow(host.as_bytes(), AsciiDenyList::URL) {
+        // 전각 마침표(`。`)가 끝에 있으면 변환 후 `.`이 남음
+        Ok(Cow::Borrowed(ascii)) => Cow::Borrowed(ascii.trim_end_matches('.')),
+        Ok(Cow::Owned(ascii)) => Cow::Owned(ascii.trim_end_matches('.').to_string()),
+        Err(_) => {
+            debug!("IDNA 변환 실패, 소문자로만 비교: {host}");
+            Cow::Owned(host.to_lowercase())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_and_trailing_dot() {
+        assert_eq!(normalize_host("WWW.Example.COM."), "www.example.com");
+        assert_eq!(normalize_host(" example.com.. "), "example.com");
+        assert!(matches!(normalize_host("example.com"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn converts_idn_to_punycode() {
+        assert_eq!(normalize_host("Bücher.example"), "xn--bcher-kva.example");
+        assert_eq!(normalize_host("광고.한국"), "xn--299aqd.xn--3e0b707e");
+        // 전각 마침표도 구분자로 처리
+        assert_eq!(normalize_host("例え。テスト。"), "xn--r8jz45g.xn--zckzah");
+        assert_eq!(
+            normalize_host("xn--bcher-kva.example"),
+            "xn--bcher-kva.example"
+        );
+    }
+
+    #[test]
+    fn strips_ipv6_brackets() {
+        assert_eq!(normalize_host("[2001:DB8:0::1]"), "2001:db8::1");
+        assert_eq!(normalize_host("[::ffff:10.0.0.1]"), "::ffff:10.0.0.1");
+        assert_eq!(normalize_host("10.0.0.1"), "10.0.0.1");
+    }
+}
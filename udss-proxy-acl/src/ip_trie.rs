@@ -0,0 +1,192 @@
+use std::net::IpAddr;
+
+use ipnet::IpNet;
+
+/// 비트 단위 트라이 노드
+#[derive(Debug)]
+struct Node<T> {
+    children: [Option<Box<Node<T>>>; 2],
+    value: Option<(IpNet, T)>,
+}
+
+impl<T> Default for Node<T> {
+    fn default() -> Self {
+        Self {
+            children: [None, None],
+            value: None,
+        }
+    }
+}
+
+/// IP 네트워크 프리픽스 트라이 (IPv4, IPv6 별도)
+///
+/// 조회 비용은 규칙 수와 무관하게 주소 비트 수(32, 128)에 비례한다.
+#[derive(Debug)]
+pub struct IpTrie<T> {
+    v4: Node<T>,
+    v6: Node<T>,
+    len: usize,
+}
+
+impl<T> Default for IpTrie<T> {
+    fn default() -> Self {
+        Self {
+            v4: Node::default(),
+            v6: Node::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> IpTrie<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 규칙 수
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 규칙 추가 (호스트 비트는 버림, 같은 네트워크가 있으면 교체)
+    pub fn insert(&mut self, network: IpNet, value: T) {
+        let network = network.trunc();
+        let (mut node, bits) = match network {
+            IpNet::V4(net) => (&mut self.v4, u128::from(net.addr().to_bits()) << 96),
+            IpNet::V6(net) => (&mut self.v6, net.addr().to_bits()),
+        };
+        for i in 0..network.prefix_len() {
+            node = node.children[bit(bits, i)].get_or_insert_with(Box::default);
+        }
+        if node.value.replace((network, value)).is_none() {
+            self.len += 1;
+        }
+    }
+
+    /// 주소를 포함하는 가장 긴 프리픽스 규칙
+    pub fn find(&self, addr: IpAddr) -> Option<(IpNet, &T)> {
+        self.find_all(addr).into_iter().next()
+    }
+
+    /// 주소를 포함하는 모든 규칙 (긴 프리픽스 우선)
+    pub fn find_all(&self, addr: IpAddr) -> Vec<(IpNet, &T)> {
+        if self.len == 0 {
+            return Vec::new();
+        }
+        // IPv4 매핑 IPv6 주소는 IPv4 규칙으로 비교
+        let (mut node, bits, len) = match addr.to_canonical() {
+            IpAddr::V4(addr) => (&self.v4, u128::from(addr.to_bits()) << 96, 32),
+            IpAddr::V6(addr) => (&self.v6, addr.to_bits(), 128),
+        };
+
+        let mut found = Vec::new();
+        for i in 0..=len {
+            if let Some((network, value)) = &node.value {
+                found.push((*network, value));
+            }
+            if i == len {
+                break;
+            }
+            match &node.children[bit(bits, i)] {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+
+        found.reverse();
+        found
+    }
+}
+
+/// 상위 비트부터 `i`번째 비트
+fn bit(bits: u128, i: u8) -> usize {
+    ((bits >> (127 - u32::from(i))) & 1) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn net(value: &str) -> IpNet {
+        value.parse().unwrap()
+    }
+
+    fn addr(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn find_all_orders_longest_prefix_first() {
+        let mut trie = IpTrie::new();
+        trie.insert(net("10.0.0.0/8"), "a");
+        trie.insert(net("10.1.2.0/24"), "c");
+        trie.insert(net("10.1.0.0/16"), "b");
+        trie.insert(net("192.168.0.0/16"), "x");
+
+        let found: Vec<_> = trie
+            .find_all(addr("10.1.2.3"))
+            .into_iter()
+            .map(|(network, value)| (network.to_string(), *value))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("10.1.2.0/24".to_string(), "c"),
+                ("10.1.0.0/16".to_string(), "b"),
+                ("10.0.0.0/8".to_string(), "a"),
+            ]
+        );
+        assert_eq!(trie.find(addr("10.9.9.9")).map(|(_, v)| *v), Some("a"));
+        assert!(trie.find(addr("11.0.0.1")).is_none());
+    }
+
+    #[test]
+    fn ipv4_mapped_ipv6_uses_ipv4_rules() {
+        let mut trie = IpTrie::new();
+        trie.insert(net("203.0.113.0/24"), "v4");
+        trie.insert(net("2001:db8::/32"), "v6");
+
+        assert_eq!(
+            trie.find(addr("::ffff:203.0.113.7")).map(|(_, v)| *v),
+            Some("v4")
+        );
+        assert_eq!(trie.find(addr("2001:db8::1")).map(|(_, v)| *v), Some("v6"));
+        assert!(trie.find(addr("::ffff:198.51.100.1")).is_none());
+    }
+
+    #[test]
+    fn zero_prefix_matches_whole_family() {
+        let mut trie = IpTrie::new();
+        trie.insert(net("0.0.0.0/0"), "any4");
+        trie.insert(net("10.0.0.0/8"), "ten");
+
+        assert_eq!(trie.find(addr("8.8.8.8")).map(|(_, v)| *v), Some("any4"));
+        assert_eq!(trie.find_all(addr("10.0.0.1")).len(), 2);
+        assert!(trie.find(addr("2001:db8::1")).is_none());
+
+        trie.insert(net("::/0"), "any6");
+        assert_eq!(
+            trie.find(addr("2001:db8::1")).map(|(_, v)| *v),
+            Some("any6")
+        );
+    }
+
+    #[test]
+    fn insert_truncates_host_bits() {
+        let mut trie = IpTrie::new();
+        trie.insert(net("10.1.2.3/16"), "first");
+        assert_eq!(
+            trie.find(addr("10.1.200.1")).map(|(network, _)| network),
+            Some(net("10.1.0.0/16"))
+        );
+
+        // 호스트 비트만 다른 네트워크는 같은 규칙으로 교체
+        trie.insert(net("10.1.99.99/16"), "second");
+        assert_eq!(trie.len(), 1);
+        assert_eq!(trie.find(addr("10.1.0.1")).map(|(_, v)| *v), Some("second"));
+    }
+}
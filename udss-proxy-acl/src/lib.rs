@@ -1,4 +1,18 @@
+pub mod access_policy;
 pub mod block_page;
+pub mod blocklist_import;
+pub mod content_restriction;
+pub mod decision;
 pub mod domain_blocker;
+pub mod domain_trie;
+pub mod file_type;
+pub mod header_rewrite;
+pub mod host;
+pub mod ip_trie;
+pub mod monitor_report;
+pub mod pattern_set;
+pub mod schedule;
+pub mod url_matcher;
+pub mod warn;
 
 mod sql;
@@ -0,0 +1,135 @@
+use std::fmt;
+
+use chrono::{DateTime, Local, Utc};
+
+use udss_proxy_db::DatabasePool;
+use udss_proxy_db::request_logs;
+use udss_proxy_error::Result;
+
+/// 모니터 규칙별 차단 예정 요약
+#[derive(Debug)]
+pub struct MonitorRuleSummary {
+    pub rule_type: String,
+    pub rule_id: i64,
+    /// 차단 예정 요청 수
+    pub hits: i64,
+    /// 영향받는 클라이언트 수
+    pub clients: i64,
+    /// 영향받는 호스트 수
+    pub hosts: i64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// 모니터 규칙, 클라이언트, 호스트별 차단 예정 요청 수
+#[derive(Debug)]
+pub struct MonitorHit {
+    pub rule_type: String,
+    pub rule_id: i64,
+    pub client_ip: String,
+    pub host: String,
+    pub hits: i64,
+}
+
+/// 모니터 규칙 차단 예정 보고서
+#[derive(Debug)]
+pub struct MonitorReport {
+    /// 집계 기간 (시간)
+    pub hours: i32,
+    pub rules: Vec<MonitorRuleSummary>,
+    /// 요청 수가 많은 순 상위 항목
+    pub top_hits: Vec<MonitorHit>,
+}
+
+impl MonitorReport {
+    /// 요청 로그에서 최근 `hours`시간 집계 (`top`: 클라이언트, 호스트별 최대 항목 수)
+    pub async fn load(pool: &DatabasePool, hours: i32, top: i64) -> Result<Self> {
+        let conn = pool.get_connection().await?;
+
+        let rules = conn
+            .query(request_logs::SELECT_MONITOR_SUMMARY, &[&hours])
+            .await?
+            .iter()
+            .map(|row| MonitorRuleSummary {
+                rule_type: row.get::<_, Option<String>>(0).unwrap_or_default(),
+                rule_id: row.get(1),
+                hits: row.get(2),
+                clients: row.get(3),
+                hosts: row.get(4),
+                first_seen: row.get(5),
+                last_seen: row.get(6),
+            })
+            .collect();
+
+        let top_hits = conn
+            .query(request_logs::SELECT_MONITOR_HITS, &[&hours, &top])
+            .await?
+            .iter()
+            .map(|row| MonitorHit {
+                rule_type: row.get::<_, Option<String>>(0).unwrap_or_default(),
+                rule_id: row.get(1),
+                client_ip: row.get(2),
+                host: row.get(3),
+                hits: row.get(4),
+            })
+            .collect();
+
+        Ok(Self {
+            hours,
+            rules,
+            top_hits,
+        })
+    }
+}
+
+impl fmt::Display for MonitorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "모니터 규칙 차단 예정 보고서 (최근 {}시간)", self.hours)?;
+        if self.rules.is_empty() {
+            return writeln!(f, "차단 예정 요청 없음");
+        }
+
+        // 표 머리글은 정렬을 위해 ASCII
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<10} {:>8} {:>10} {:>8} {:>8}  {:<19}  LAST SEEN",
+            "TYPE", "RULE", "HITS", "CLIENTS", "HOSTS", "FIRST SEEN"
+        )?;
+        for rule in &self.rules {
+            writeln!(
+                f,
+                "{:<10} {:>8} {:>10} {:>8} {:>8}  {}  {}",
+                rule.rule_type,
+                rule.rule_id,
+                rule.hits,
+                rule.clients,
+                rule.hosts,
+                format_time(rule.first_seen),
+                format_time(rule.last_seen),
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<10} {:>8} {:>10}  {:<39}  HOST",
+            "TYPE", "RULE", "HITS", "CLIENT"
+        )?;
+        for hit in &self.top_hits {
+            writeln!(
+                f,
+                "{:<10} {:>8} {:>10}  {:<39}  {}",
+                hit.rule_type, hit.rule_id, hit.hits, hit.client_ip, hit.host
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 로컬 시간 표시
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
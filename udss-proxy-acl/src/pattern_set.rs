@@ -0,0 +1,116 @@
+use regex::{Regex, RegexSet, RegexSetBuilder};
+
+use udss_proxy_error::{Result, config_err};
+
+// 대량 패턴 컴파일 허용 크기 (기본값 10MB로는 수천 개 패턴을 담지 못함)
+const SIZE_LIMIT: usize = 512 * 1024 * 1024;
+const DFA_SIZE_LIMIT: usize = 64 * 1024 * 1024;
+
+/// 도메인 정규식 패턴 집합 (패턴마다 값 `T`를 가짐)
+///
+/// 모든 패턴을 하나의 `RegexSet`으로 컴파일해 호스트당 한 번만 검사한다.
+/// 대소문자는 구분하지 않는다.
+pub struct PatternSet<T = ()> {
+    set: RegexSet,
+    patterns: Vec<(String, T)>,
+}
+
+impl<T> PatternSet<T> {
+    /// 빈 패턴 집합
+    pub fn empty() -> Self {
+        Self {
+            set: RegexSet::empty(),
+            patterns: Vec::new(),
+        }
+    }
+
+    /// 패턴 목록 컴파일 (잘못된 패턴은 건너뛰고 반환)
+    pub fn compile<I>(patterns: I) -> Result<(Self, Vec<(String, regex::Error)>)>
+    where
+        I: IntoIterator<Item = (String, T)>,
+    {
+        let mut valid = Vec::new();
+        let mut invalid = Vec::new();
+        for (pattern, value) in patterns {
+            // 하나라도 잘못되면 집합 전체가 실패하므로 개별 검증
+            match Regex::new(&pattern) {
+                Ok(_) => valid.push((pattern, value)),
+                Err(e) => invalid.push((pattern, e)),
+            }
+        }
+
+        let set = RegexSetBuilder::new(valid.iter().map(|(pattern, _)| pattern))
+            .case_insensitive(true)
+            .size_limit(SIZE_LIMIT)
+            .dfa_size_limit(DFA_SIZE_LIMIT)
+            .build()
+            .map_err(|e| config_err(format!("도메인 패턴 집합 컴파일 실패: {e}")))?;
+
+        Ok((
+            Self {
+                set,
+                patterns: valid,
+            },
+            invalid,
+        ))
+    }
+
+    /// 패턴 수
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// 일치하는 모든 패턴
+    pub fn find_all(&self, host: &str) -> Vec<(&str, &T)> {
+        if !self.set.is_match(host) {
+            return Vec::new();
+        }
+        self.set
+            .matches(host)
+            .iter()
+            .map(|index| {
+                let (pattern, value) = &self.patterns[index];
+                (pattern.as_str(), value)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_patterns_are_skipped() {
+        let (set, invalid) = PatternSet::compile(vec![
+            (r"^ads\d+\.".to_string(), 1),
+            ("(unclosed".to_string(), 2),
+            (r"tracker\.net$".to_string(), 3),
+        ])
+        .unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, "(unclosed");
+    }
+
+    #[test]
+    fn find_all_is_case_insensitive() {
+        let (set, _) = PatternSet::compile(vec![
+            (r"^ads\d+\.".to_string(), 1),
+            (r"\.example\.com$".to_string(), 2),
+        ])
+        .unwrap();
+        let matched: Vec<(&str, i32)> = set
+            .find_all("ADS12.Example.com")
+            .into_iter()
+            .map(|(pattern, value)| (pattern, *value))
+            .collect();
+        assert_eq!(matched, [(r"^ads\d+\.", 1), (r"\.example\.com$", 2)]);
+        assert!(set.find_all("www.example.org").is_empty());
+        assert!(PatternSet::<()>::empty().find_all("example.com").is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+use chrono::{DateTime, Datelike, Local, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+
+use udss_proxy_error::{Result, config_err};
+
+// 하루의 분 수
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// 규칙 적용 시간대 (요일, 시간 범위, 시간대)
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    /// 스케줄 이름
+    pub name: String,
+    // 월요일부터 적용 요일
+    days: [bool; 7],
+    // 자정 기준 분 단위 [시작, 종료), 시작 > 종료면 자정을 넘는 범위
+    ranges: Vec<(u32, u32)>,
+    // None이면 서버 로컬 시간대
+    timezone: Option<Tz>,
+}
+
+impl Schedule {
+    /// DB 값으로 생성 (`weekdays`, `time_ranges`, `timezone`이 없으면 매일, 종일, 로컬 시간대)
+    pub fn parse(
+        name: &str,
+        weekdays: Option<&str>,
+        time_ranges: Option<&str>,
+        timezone: Option<&str>,
+    ) -> Result<Self> {
+        let days = match weekdays.map(str::trim).filter(|value| !value.is_empty()) {
+            Some(weekdays) => parse_weekdays(weekdays)?,
+            None => [true; 7],
+        };
+        let ranges = match time_ranges.map(str::trim).filter(|value| !value.is_empty()) {
+            Some(time_ranges) => time_ranges
+                .split(',')
+                .map(parse_time_range)
+                .collect::<Result<_>>()?,
+            None => Vec::new(),
+        };
+        let timezone = match timezone.map(str::trim).filter(|value| !value.is_empty()) {
+            Some(timezone) => Some(
+                timezone
+                    .parse::<Tz>()
+                    .map_err(|e| config_err(format!("알 수 없는 시간대 {timezone}: {e}")))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            days,
+            ranges,
+            timezone,
+        })
+    }
+
+    /// 주어진 시각에 적용 중인지 여부
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        let (weekday, minute) = match self.timezone {
+            Some(timezone) => local_parts(now.with_timezone(&timezone)),
+            None => local_parts(now.with_timezone(&Local)),
+        };
+        let today = weekday.num_days_from_monday() as usize;
+        let yesterday = weekday.pred().num_days_from_monday() as usize;
+
+        if self.ranges.is_empty() {
+            return self.days[today];
+        }
+        self.ranges.iter().any(|&(start, end)| {
+            if start < end {
+                self.days[today] && (start..end).contains(&minute)
+            } else {
+                // 자정을 넘는 범위는 시작한 요일 기준
+                (self.days[today] && minute >= start) || (self.days[yesterday] && minute < end)
+            }
+        })
+    }
+}
+
+/// 현지 요일과 자정 기준 분
+fn local_parts<T: Datelike + Timelike>(time: T) -> (Weekday, u32) {
+    (time.weekday(), time.hour() * 60 + time.minute())
+}
+
+/// `mon-fri`, `sat,sun` 형식 요일 목록
+fn parse_weekdays(value: &str) -> Result<[bool; 7]> {
+    let mut days = [false; 7];
+    for item in value.split(',') {
+        let item = item.trim();
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let from = parse_weekday(from)?;
+                let to = parse_weekday(to)?;
+                // `fri-mon`처럼 주말을 넘는 범위 허용
+                let mut day = from;
+                loop {
+                    days[day.num_days_from_monday() as usize] = true;
+                    if day == to {
+                        break;
+                    }
+                    day = day.succ();
+                }
+            }
+            None => days[parse_weekday(item)?.num_days_from_monday() as usize] = true,
+        }
+    }
+    Ok(days)
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday> {
+    value
+        .trim()
+        .parse::<Weekday>()
+        .map_err(|_| config_err(format!("알 수 없는 요일: {value} (mon, tue, ..., sun)")))
+}
+
+/// `09:00-18:00` 형식 시간 범위 (종료 `24:00` 허용)
+fn parse_time_range(value: &str) -> Result<(u32, u32)> {
+    let invalid = || config_err(format!("잘못된 시간 범위: {value} (예: 09:00-18:00)"));
+    let (start, end) = value.trim().split_once('-').ok_or_else(invalid)?;
+    let start = parse_minute(start).ok_or_else(invalid)?;
+    let end = parse_minute(end).ok_or_else(invalid)?;
+    if start == end || start == MINUTES_PER_DAY {
+        return Err(invalid());
+    }
+    Ok((start, end % MINUTES_PER_DAY))
+}
+
+/// `HH:MM` -> 자정 기준 분
+fn parse_minute(value: &str) -> Option<u32> {
+    let (hour, minute) = value.trim().split_once(':')?;
+    let hour: u32 = hour.parse().ok()?;
+    let minute: u32 = minute.parse().ok()?;
+    let total = hour * 60 + minute;
+    (minute < 60 && total <= MINUTES_PER_DAY).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    /// 2024-01-01은 월요일
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, day, hour, minute, 0).unwrap()
+    }
+
+    fn utc_schedule(weekdays: &str, time_ranges: &str) -> Schedule {
+        Schedule::parse("test", Some(weekdays), Some(time_ranges), Some("UTC")).unwrap()
+    }
+
+    #[test]
+    fn overnight_range_counts_from_start_day() {
+        let schedule = utc_schedule("fri", "22:00-06:00");
+        assert!(schedule.is_active(at(5, 22, 0))); // 금 22:00
+        assert!(schedule.is_active(at(6, 5, 59))); // 토 05:59 (금요일 범위)
+        assert!(!schedule.is_active(at(6, 6, 0))); // 토 06:00
+        assert!(!schedule.is_active(at(6, 22, 0))); // 토 22:00
+        assert!(!schedule.is_active(at(5, 5, 0))); // 금 05:00 (목요일 범위)
+        assert!(!schedule.is_active(at(5, 21, 59)));
+    }
+
+    #[test]
+    fn end_of_day_is_allowed() {
+        assert_eq!(parse_time_range("18:00-24:00").unwrap(), (18 * 60, 0));
+        let schedule = utc_schedule("mon", "18:00-24:00");
+        assert!(schedule.is_active(at(1, 23, 59)));
+        assert!(!schedule.is_active(at(1, 17, 59)));
+        assert!(!schedule.is_active(at(2, 0, 0))); // 화 00:00
+    }
+
+    #[test]
+    fn weekday_range_wraps_around_weekend() {
+        let schedule = Schedule::parse("test", Some("fri-mon"), None, Some("UTC")).unwrap();
+        let active: Vec<bool> = (1..=7)
+            .map(|day| schedule.is_active(at(day, 12, 0)))
+            .collect();
+        assert_eq!(active, [true, false, false, false, true, true, true]);
+    }
+
+    #[test]
+    fn explicit_timezone_and_local_time() {
+        // UTC 월 15:00 = 서울 월 24:00 (화 00:00)
+        let seoul =
+            Schedule::parse("test", Some("tue"), Some("00:00-01:00"), Some("Asia/Seoul")).unwrap();
+        assert!(seoul.is_active(at(1, 15, 0)));
+        assert!(!utc_schedule("tue", "00:00-01:00").is_active(at(1, 15, 0)));
+
+        // 시간대가 없으면 서버 로컬 시간 기준
+        let now = at(3, 12, 30);
+        let local = now.with_timezone(&Local);
+        let start = local.hour() * 60 + local.minute();
+        let range = format!(
+            "{:02}:{:02}-{:02}:{:02}",
+            start / 60,
+            start % 60,
+            (start + 1) / 60,
+            (start + 1) % 60
+        );
+        let weekday = local.weekday().to_string();
+        let schedule = Schedule::parse("test", Some(&weekday), Some(&range), None).unwrap();
+        assert!(schedule.is_active(now));
+        assert!(!schedule.is_active(now + chrono::Duration::minutes(1)));
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        for range in [
+            "09:00",
+            "09:00-09:00",
+            "24:00-06:00",
+            "25:00-26:00",
+            "09:60-10:00",
+            "9-18",
+            "09:00-24:01",
+        ] {
+            assert!(parse_time_range(range).is_err(), "{range}");
+        }
+        assert!(Schedule::parse("test", Some("mon-funday"), None, None).is_err());
+        assert!(Schedule::parse("test", None, None, Some("Mars/Olympus")).is_err());
+        assert!(Schedule::parse("test", None, Some("09:00-18:00,bad"), None).is_err());
+    }
+}
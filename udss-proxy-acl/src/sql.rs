@@ -1,15 +1,95 @@
 /// 도메인 목록 조회 쿼리
 pub const SELECT_ACTIVE_DOMAINS: &str = "
-    SELECT domain
+    SELECT id, domain, match_type, action, priority, schedule_id
     FROM domain_blocks
-    WHERE active = TRUE
+    WHERE active = TRUE AND (expires_at IS NULL OR expires_at > NOW())
     ORDER BY domain
 ";
 
 /// 패턴 목록 조회 쿼리
 pub const SELECT_ACTIVE_PATTERNS: &str = "
-    SELECT pattern
+    SELECT id, pattern, action, priority, schedule_id
     FROM domain_pattern_blocks
-    WHERE active = TRUE
+    WHERE active = TRUE AND (expires_at IS NULL OR expires_at > NOW())
     ORDER BY pattern
 ";
+
+/// TLS 핑거프린트 목록 조회 쿼리
+pub const SELECT_ACTIVE_FINGERPRINTS: &str = "
+    SELECT fingerprint_type, fingerprint
+    FROM tls_fingerprint_blocks
+    WHERE active = TRUE AND (expires_at IS NULL OR expires_at > NOW())
+    ORDER BY fingerprint
+";
+
+/// URL 규칙 목록 조회 쿼리
+pub const SELECT_ACTIVE_URL_RULES: &str = "
+    SELECT id, host, method, path, path_match_type, query_param, case_sensitive, action, priority
+    FROM url_blocks
+    WHERE active = TRUE AND (expires_at IS NULL OR expires_at > NOW())
+    ORDER BY id
+";
+
+/// 활성 카테고리 도메인 조회 쿼리
+pub const SELECT_ENABLED_CATEGORY_DOMAINS: &str = "
+    SELECT cd.id, cd.domain, cd.match_type, c.name, c.action, c.priority, c.schedule_id
+    FROM category_domains cd
+    JOIN domain_categories c ON c.id = cd.category_id
+    WHERE c.enabled = TRUE AND (c.expires_at IS NULL OR c.expires_at > NOW())
+";
+
+/// 규칙 스케줄 조회 쿼리
+pub const SELECT_SCHEDULES: &str = "
+    SELECT id, name, weekdays, time_ranges, timezone
+    FROM acl_schedules
+    ORDER BY id
+";
+
+/// 목적지 IP 규칙 조회 쿼리
+pub const SELECT_ACTIVE_IP_RULES: &str = "
+    SELECT id, cidr::text, action, priority, schedule_id
+    FROM ip_blocks
+    WHERE active = TRUE AND (expires_at IS NULL OR expires_at > NOW())
+    ORDER BY id
+";
+
+/// 클라이언트 네트워크 규칙 조회 쿼리 (우선순위, 스케줄 없음)
+pub const SELECT_ACTIVE_CLIENT_NETWORKS: &str = "
+    SELECT id, cidr::text, action, 0 AS priority, NULL::BIGINT AS schedule_id
+    FROM client_networks
+    WHERE active = TRUE AND (expires_at IS NULL OR expires_at > NOW())
+    ORDER BY id
+";
+
+/// 다운로드 파일 형식 규칙 조회 쿼리
+pub const SELECT_ACTIVE_FILE_TYPE_RULES: &str = "
+    SELECT id, match_type, value, host, uncategorized_only, action, priority, schedule_id
+    FROM file_type_blocks
+    WHERE active = TRUE AND (expires_at IS NULL OR expires_at > NOW())
+    ORDER BY id
+";
+
+/// 헤더 변경 규칙 조회 쿼리
+pub const SELECT_ACTIVE_HEADER_RULES: &str = "
+    SELECT id, host, direction, operation, header_name, header_value, priority, schedule_id
+    FROM header_rewrite_rules
+    WHERE active = TRUE AND (expires_at IS NULL OR expires_at > NOW())
+    ORDER BY id
+";
+
+/// 다음 규칙 만료까지 남은 초 조회 쿼리 (만료 예정 규칙이 없으면 NULL)
+pub const SELECT_NEXT_EXPIRY_SECONDS: &str = "
+    SELECT EXTRACT(EPOCH FROM MIN(expires_at) - NOW())::FLOAT8
+    FROM (
+        SELECT expires_at FROM domain_blocks WHERE active = TRUE
+        UNION ALL SELECT expires_at FROM domain_pattern_blocks WHERE active = TRUE
+        UNION ALL SELECT expires_at FROM tls_fingerprint_blocks WHERE active = TRUE
+        UNION ALL SELECT expires_at FROM url_blocks WHERE active = TRUE
+        UNION ALL SELECT expires_at FROM domain_categories WHERE enabled = TRUE
+        UNION ALL SELECT expires_at FROM ip_blocks WHERE active = TRUE
+        UNION ALL SELECT expires_at FROM client_networks WHERE active = TRUE
+        UNION ALL SELECT expires_at FROM file_type_blocks WHERE active = TRUE
+        UNION ALL SELECT expires_at FROM header_rewrite_rules WHERE active = TRUE
+    ) rules
+    WHERE expires_at > NOW()
+";
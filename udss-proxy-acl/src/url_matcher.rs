@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+
+use crate::decision::RuleMeta;
+use crate::domain_trie::{DomainTrie, MatchType};
+use crate::host::normalize_host;
+
+// 이중 인코딩 해제 최대 횟수
+const MAX_DECODE_PASSES: usize = 3;
+
+/// URL 규칙 경로 비교 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathMatchType {
+    /// 경로 전체 일치
+    Exact,
+    /// 경로 접두어 일치
+    Prefix,
+    /// `*`(임의 문자열), `?`(임의 한 글자) 패턴
+    Glob,
+}
+
+impl PathMatchType {
+    /// DB `path_match_type` 값 변환
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "exact" => Some(Self::Exact),
+            "prefix" => Some(Self::Prefix),
+            "glob" => Some(Self::Glob),
+            _ => None,
+        }
+    }
+}
+
+/// URL 규칙
+#[derive(Debug, Clone)]
+pub struct UrlRule {
+    /// 규칙 ID, 동작, 우선순위
+    pub meta: RuleMeta,
+    /// HTTP 메서드 (없으면 모든 메서드)
+    pub method: Option<String>,
+    /// 정규화된 경로 패턴
+    pub path: String,
+    /// 경로 비교 방식
+    pub path_match_type: PathMatchType,
+    /// 필수 쿼리 파라미터 (이름, 값)
+    pub query_param: Option<(String, Option<String>)>,
+    /// 경로/쿼리 대소문자 구분 여부
+    pub case_sensitive: bool,
+}
+
+impl UrlRule {
+    /// 새 규칙 생성 (경로와 쿼리 파라미터는 요청과 같은 방식으로 정규화)
+    pub fn new(
+        meta: RuleMeta,
+        method: Option<&str>,
+        path: &str,
+        path_match_type: PathMatchType,
+        query_param: Option<&str>,
+        case_sensitive: bool,
+    ) -> Self {
+        let fold = |value: String| {
+            if case_sensitive {
+                value
+            } else {
+                value.to_lowercase()
+            }
+        };
+
+        // glob 패턴은 `*`, `?`가 경로 구분자 처리에 섞이지 않도록 디코딩만 적용
+        let path = match path_match_type {
+            PathMatchType::Glob => percent_decode(path, false),
+            PathMatchType::Exact => trim_trailing_slash(&normalize_path(path)).to_string(),
+            PathMatchType::Prefix => normalize_path(path),
+        };
+        let query_param = query_param.filter(|param| !param.is_empty()).map(|param| {
+            let (name, value) = split_query_pair(param);
+            (fold(name), value.map(fold))
+        });
+
+        Self {
+            meta,
+            method: method
+                .filter(|method| !method.is_empty())
+                .map(str::to_ascii_uppercase),
+            path: fold(path),
+            path_match_type,
+            query_param,
+            case_sensitive,
+        }
+    }
+
+    /// 정규화된 요청과 비교
+    fn matches(&self, method: &str, path: &NormalizedPath) -> bool {
+        if let Some(rule_method) = &self.method
+            && !rule_method.eq_ignore_ascii_case(method)
+        {
+            return false;
+        }
+
+        let (request_path, query) = if self.case_sensitive {
+            (&path.path, &path.query)
+        } else {
+            (&path.folded_path, &path.folded_query)
+        };
+
+        let path_matched = match self.path_match_type {
+            PathMatchType::Exact => trim_trailing_slash(request_path) == self.path,
+            PathMatchType::Prefix => request_path.starts_with(&self.path),
+            PathMatchType::Glob => glob_match(&self.path, request_path),
+        };
+        if !path_matched {
+            return false;
+        }
+
+        match &self.query_param {
+            Some((name, expected)) => query.iter().any(|(key, value)| {
+                key == name
+                    && expected
+                        .as_ref()
+                        .is_none_or(|expected| value.as_ref() == Some(expected))
+            }),
+            None => true,
+        }
+    }
+}
+
+/// 호스트 + 경로/쿼리/메서드 URL 규칙 매처
+#[derive(Debug, Default)]
+pub struct UrlMatcher {
+    hosts: DomainTrie<Vec<UrlRule>>,
+    len: usize,
+}
+
+impl UrlMatcher {
+    /// 규칙 목록으로 생성 (`host`는 `*.`, `.` 접두어 허용)
+    pub fn new(rules: Vec<(String, UrlRule)>) -> Self {
+        let len = rules.len();
+        let mut grouped: HashMap<(String, MatchType), Vec<UrlRule>> = HashMap::new();
+        for (host, rule) in rules {
+            let (domain, match_type) = MatchType::split_rule(&host, MatchType::Exact);
+            grouped
+                .entry((normalize_host(domain).into_owned(), match_type))
+                .or_default()
+                .push(rule);
+        }
+
+        let mut hosts = DomainTrie::new();
+        for ((domain, match_type), rules) in grouped {
+            hosts.insert(&domain, match_type, rules);
+        }
+        Self { hosts, len }
+    }
+
+    /// 규칙 수
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 요청에 일치하는 모든 규칙과 호스트 일치 방식, 규칙 도메인 레이블 수
+    pub fn find_all(
+        &self,
+        method: &str,
+        host: &str,
+        path_and_query: &str,
+    ) -> Vec<(MatchType, usize, &UrlRule)> {
+        if self.len == 0 {
+            return Vec::new();
+        }
+        let candidates = self.hosts.find_all(host);
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let path = NormalizedPath::new(path_and_query);
+        candidates
+            .into_iter()
+            .flat_map(|(match_type, depth, rules)| {
+                rules.iter().map(move |rule| (match_type, depth, rule))
+            })
+            .filter(|(_, _, rule)| rule.matches(method, &path))
+            .collect()
+    }
+}
+
+/// 비교용으로 정규화한 요청 경로와 쿼리
+struct NormalizedPath {
+    path: String,
+    query: Vec<(String, Option<String>)>,
+    folded_path: String,
+    folded_query: Vec<(String, Option<String>)>,
+}
+
+impl NormalizedPath {
+    fn new(path_and_query: &str) -> Self {
+        let (path, query) = path_and_query
+            .split_once('?')
+            .unwrap_or((path_and_query, ""));
+        // 프래그먼트는 서버로 전달되지 않음
+        let query = query.split('#').next().unwrap_or_default();
+
+        let path = normalize_path(path);
+        let query: Vec<_> = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(split_query_pair)
+            .collect();
+        let folded_query = query
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_lowercase(),
+                    value.as_ref().map(|value| value.to_lowercase()),
+                )
+            })
+            .collect();
+
+        Self {
+            folded_path: path.to_lowercase(),
+            path,
+            query,
+            folded_query,
+        }
+    }
+}
+
+/// 요청 경로 정규화
+///
+/// 퍼센트 인코딩 해제(이중 인코딩 포함), `\` -> `/` 변환, 중복 `/` 제거,
+/// `.`/`..` 세그먼트 제거를 순서대로 적용한다.
+pub fn normalize_path(path: &str) -> String {
+    let decoded = percent_decode(path, false).replace('\\', "/");
+
+    let mut segments: Vec<&str> = Vec::new();
+    let mut trailing_slash = decoded.ends_with('/');
+    for segment in decoded.split('/') {
+        match segment {
+            "" => {}
+            "." => trailing_slash = true,
+            ".." => {
+                segments.pop();
+                trailing_slash = true;
+            }
+            _ => {
+                segments.push(segment);
+                trailing_slash = false;
+            }
+        }
+    }
+    if decoded.ends_with('/') {
+        trailing_slash = true;
+    }
+
+    let mut normalized = String::with_capacity(decoded.len() + 1);
+    for segment in &segments {
+        normalized.push('/');
+        normalized.push_str(segment);
+    }
+    if normalized.is_empty() || trailing_slash {
+        normalized.push('/');
+    }
+    normalized
+}
+
+/// 끝 `/` 제거 (루트 `/`는 유지, `/api/upload/`와 `/api/upload`를 같은 경로로 비교)
+fn trim_trailing_slash(path: &str) -> &str {
+    match path.strip_suffix('/') {
+        Some(trimmed) if !trimmed.is_empty() => trimmed,
+        _ => path,
+    }
+}
+
+/// 퍼센트 인코딩 해제 (결과가 바뀌지 않을 때까지 반복)
+pub(crate) fn percent_decode(value: &str, plus_as_space: bool) -> String {
+    let mut current = if plus_as_space {
+        value.replace('+', " ")
+    } else {
+        value.to_string()
+    };
+
+    for _ in 0..MAX_DECODE_PASSES {
+        if !current.contains('%') {
+            break;
+        }
+        let bytes = current.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%'
+                && let Some(hex) = bytes.get(i + 1..i + 3)
+                && let Ok(hex) = std::str::from_utf8(hex)
+                && let Ok(byte) = u8::from_str_radix(hex, 16)
+            {
+                decoded.push(byte);
+                i += 3;
+            } else {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+        let decoded = String::from_utf8_lossy(&decoded).into_owned();
+        if decoded == current {
+            break;
+        }
+        current = decoded;
+    }
+    current
+}
+
+/// `name=value` 쿼리 파라미터 분리 및 디코딩
+fn split_query_pair(pair: &str) -> (String, Option<String>) {
+    match pair.split_once('=') {
+        Some((name, value)) => (
+            percent_decode(name, true),
+            Some(percent_decode(value, true)),
+        ),
+        None => (percent_decode(pair, true), None),
+    }
+}
+
+/// `*`, `?` glob 비교
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // 마지막 `*` 위치와 그때의 텍스트 위치 (되돌아가기용)
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decision::Action;
+
+    fn rule(path: &str, path_match_type: PathMatchType, case_sensitive: bool) -> UrlRule {
+        let meta = RuleMeta {
+            id: 1,
+            action: Action::Block,
+            priority: 0,
+            schedule_id: None,
+        };
+        UrlRule::new(meta, None, path, path_match_type, None, case_sensitive)
+    }
+
+    fn matches(rule: UrlRule, method: &str, path_and_query: &str) -> bool {
+        let matcher = UrlMatcher::new(vec![("example.com".to_string(), rule)]);
+        !matcher
+            .find_all(method, "example.com", path_and_query)
+            .is_empty()
+    }
+
+    #[test]
+    fn normalize_path_decodes_double_encoding() {
+        assert_eq!(normalize_path("/a/%252e%252e/admin"), "/admin");
+        assert_eq!(normalize_path("/%2561dmin"), "/admin");
+    }
+
+    #[test]
+    fn normalize_path_converts_backslashes() {
+        assert_eq!(normalize_path("/a\\b\\c"), "/a/b/c");
+        assert_eq!(normalize_path("/a%5c..%5cadmin"), "/admin");
+    }
+
+    #[test]
+    fn normalize_path_collapses_slash_runs() {
+        assert_eq!(normalize_path("//a///b"), "/a/b");
+        assert_eq!(normalize_path("/a//b//"), "/a/b/");
+        assert_eq!(normalize_path(""), "/");
+    }
+
+    #[test]
+    fn normalize_path_stops_dot_dot_at_root() {
+        assert_eq!(normalize_path("/../../etc/passwd"), "/etc/passwd");
+        assert_eq!(normalize_path("/a/./b/../.."), "/");
+        assert_eq!(normalize_path("/a/b/.."), "/a/");
+    }
+
+    #[test]
+    fn exact_ignores_trailing_slash() {
+        let upload = || rule("/api/upload", PathMatchType::Exact, false);
+        assert!(matches(upload(), "POST", "/api/upload"));
+        assert!(matches(upload(), "POST", "/api/upload/"));
+        assert!(matches(upload(), "POST", "/api//upload/?x=1"));
+        assert!(!matches(upload(), "POST", "/api/upload/file"));
+        assert!(matches(
+            rule("/api/upload/", PathMatchType::Exact, false),
+            "POST",
+            "/api/upload"
+        ));
+        assert!(matches(rule("/", PathMatchType::Exact, false), "GET", "/"));
+        assert!(!matches(
+            rule("/", PathMatchType::Exact, false),
+            "GET",
+            "/a"
+        ));
+    }
+
+    #[test]
+    fn case_folding_follows_rule() {
+        assert!(matches(
+            rule("/Admin", PathMatchType::Prefix, false),
+            "GET",
+            "/ADMIN/users"
+        ));
+        assert!(!matches(
+            rule("/Admin", PathMatchType::Prefix, true),
+            "GET",
+            "/ADMIN/users"
+        ));
+        assert!(matches(
+            rule("/Admin", PathMatchType::Prefix, true),
+            "GET",
+            "/%41dmin/users"
+        ));
+    }
+
+    #[test]
+    fn glob_match_backtracks() {
+        assert!(glob_match("/a*b*c", "/aXbYbZc"));
+        assert!(glob_match("*.exe", "/files/x.tar.exe"));
+        assert!(!glob_match("*.exe", "/files/x.exe.txt"));
+        assert!(glob_match("/a?c", "/abc"));
+        assert!(!glob_match("/a?c", "/ac"));
+        assert!(glob_match("/a**", "/a"));
+        assert!(!glob_match("/a*b", "/aXbY"));
+    }
+}
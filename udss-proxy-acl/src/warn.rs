@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use log::error;
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 경고 페이지 계속 진행 경로 (대상 호스트 아래 가상 경로, 프록시가 응답)
+pub const CONTINUE_PATH: &str = "/.uproxy/warn-continue";
+
+/// 계속 진행 토큰 유효 시간 (경고 페이지 표시 후)
+const TOKEN_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// 경고 규칙 계속 진행 기록 (클라이언트 IP, 규칙별)
+///
+/// 계속 진행 토큰은 프로세스마다 새로 만든 키로 서명하므로
+/// 다른 페이지가 임의로 계속 진행을 요청할 수 없다.
+pub struct WarnAcknowledgments {
+    key: [u8; 32],
+    ttl: Duration,
+    acks: Mutex<HashMap<(IpAddr, i64), Instant>>,
+}
+
+impl WarnAcknowledgments {
+    /// `ttl`: 계속 진행 후 경고를 다시 표시하지 않는 시간
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            key: rand::random(),
+            ttl,
+            acks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 계속 진행 기록이 유효한지 여부
+    pub fn is_acknowledged(&self, client_ip: IpAddr, rule_id: i64) -> bool {
+        match self.acks.lock() {
+            Ok(acks) => acks
+                .get(&(client_ip.to_canonical(), rule_id))
+                .is_some_and(|expires_at| *expires_at > Instant::now()),
+            Err(e) => {
+                error!("acks Mutex 잠금 실패 (is_acknowledged): {e}");
+                false
+            }
+        }
+    }
+
+    /// 경고 페이지에 넣을 계속 진행 토큰 (`발급 시각.서명`)
+    pub fn token(&self, client_ip: IpAddr, rule_id: i64) -> String {
+        let issued = unix_seconds();
+        let signature =
+            URL_SAFE_NO_PAD.encode(self.mac(client_ip, rule_id, issued).finalize().into_bytes());
+        format!("{issued}.{signature}")
+    }
+
+    /// 토큰 확인 후 계속 진행 기록 (토큰이 잘못됐거나 만료되면 false)
+    pub fn acknowledge(&self, client_ip: IpAddr, rule_id: i64, token: &str) -> bool {
+        let Some((issued, signature)) = token.split_once('.') else {
+            return false;
+        };
+        let Ok(issued) = issued.parse::<u64>() else {
+            return false;
+        };
+        if unix_seconds().saturating_sub(issued) > TOKEN_TTL.as_secs() {
+            return false;
+        }
+        let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+        if self
+            .mac(client_ip, rule_id, issued)
+            .verify_slice(&signature)
+            .is_err()
+        {
+            return false;
+        }
+
+        match self.acks.lock() {
+            Ok(mut acks) => {
+                let now = Instant::now();
+                acks.retain(|_, expires_at| *expires_at > now);
+                acks.insert((client_ip.to_canonical(), rule_id), now + self.ttl);
+                true
+            }
+            Err(e) => {
+                error!("acks Mutex 잠금 실패 (acknowledge): {e}");
+                false
+            }
+        }
+    }
+
+    fn mac(&self, client_ip: IpAddr, rule_id: i64, issued: u64) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC 키 길이 제한 없음");
+        mac.update(format!("{}|{rule_id}|{issued}", client_ip.to_canonical()).as_bytes());
+        mac
+    }
+}
+
+/// 계속 진행 요청 (`CONTINUE_PATH` 쿼리)
+#[derive(Debug)]
+pub struct ContinueRequest {
+    pub rule_id: i64,
+    pub token: String,
+    /// 원래 요청 URL
+    pub url: String,
+}
+
+impl ContinueRequest {
+    /// 쿼리 문자열 해석 (`rule`, `token`, `url` 중 하나라도 없으면 None)
+    pub fn parse(query: &str) -> Option<Self> {
+        let mut rule_id = None;
+        let mut token = None;
+        let mut url = None;
+        for pair in query.split('&') {
+            let Some((name, value)) = pair.split_once('=') else {
+                continue;
+            };
+            let value = decode_component(value);
+            match name {
+                "rule" => rule_id = value.parse().ok(),
+                "token" => token = Some(value),
+                "url" => url = Some(value),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            rule_id: rule_id?,
+            token: token?,
+            url: url?,
+        })
+    }
+}
+
+/// 폼 값 퍼센트 인코딩 해제 (한 번만 적용, `+`는 공백)
+fn decode_component(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', _) => decoded.push(b' '),
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn unix_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(10, 0, 0, 1));
+    const OTHER: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(10, 0, 0, 2));
+
+    fn acks() -> WarnAcknowledgments {
+        WarnAcknowledgments::new(Duration::from_secs(60))
+    }
+
+    /// 지정 발급 시각으로 서명한 토큰
+    fn token_issued_at(acks: &WarnAcknowledgments, issued: u64) -> String {
+        let signature = URL_SAFE_NO_PAD.encode(acks.mac(CLIENT, 7, issued).finalize().into_bytes());
+        format!("{issued}.{signature}")
+    }
+
+    #[test]
+    fn token_is_bound_to_client_and_rule() {
+        let acks = acks();
+        let token = acks.token(CLIENT, 7);
+        assert!(!acks.acknowledge(OTHER, 7, &token));
+        assert!(!acks.acknowledge(CLIENT, 8, &token));
+        assert!(!acks.is_acknowledged(CLIENT, 7));
+
+        assert!(acks.acknowledge(CLIENT, 7, &token));
+        assert!(acks.is_acknowledged(CLIENT, 7));
+        // IPv4 매핑 IPv6 주소도 같은 클라이언트
+        assert!(acks.is_acknowledged("::ffff:10.0.0.1".parse().unwrap(), 7));
+        assert!(!acks.is_acknowledged(OTHER, 7));
+        assert!(!acks.is_acknowledged(CLIENT, 8));
+    }
+
+    #[test]
+    fn token_from_another_process_is_rejected() {
+        let token = acks().token(CLIENT, 7);
+        assert!(!acks().acknowledge(CLIENT, 7, &token));
+    }
+
+    #[test]
+    fn expired_token_is_rejected() {
+        let acks = acks();
+        let now = unix_seconds();
+        assert!(acks.acknowledge(CLIENT, 7, &token_issued_at(&acks, now - 60)));
+        let expired = token_issued_at(&acks, now - TOKEN_TTL.as_secs() - 1);
+        assert!(!acks.acknowledge(CLIENT, 7, &expired));
+    }
+
+    #[test]
+    fn tampered_token_is_rejected() {
+        let acks = acks();
+        let token = acks.token(CLIENT, 7);
+        let (issued, signature) = token.split_once('.').unwrap();
+
+        // 발급 시각 변경
+        let shifted = format!("{}.{signature}", issued.parse::<u64>().unwrap() - 1);
+        assert!(!acks.acknowledge(CLIENT, 7, &shifted));
+
+        // 서명 한 글자 변경
+        let mut chars: Vec<char> = signature.chars().collect();
+        chars[0] = if chars[0] == 'A' { 'B' } else { 'A' };
+        let forged = format!("{issued}.{}", chars.into_iter().collect::<String>());
+        assert!(!acks.acknowledge(CLIENT, 7, &forged));
+
+        for malformed in ["", issued, "abc.def", &format!("{issued}.!!!")] {
+            assert!(!acks.acknowledge(CLIENT, 7, malformed), "{malformed}");
+        }
+        assert!(!acks.is_acknowledged(CLIENT, 7));
+    }
+
+    #[test]
+    fn continue_request_requires_all_fields() {
+        let request =
+            ContinueRequest::parse("rule=7&token=1.abc&url=http%3A%2F%2Fexample.com%2Fa+b")
+                .unwrap();
+        assert_eq!(request.rule_id, 7);
+        assert_eq!(request.token, "1.abc");
+        assert_eq!(request.url, "http://example.com/a b");
+
+        for query in [
+            "token=1.abc&url=http%3A%2F%2Fexample.com",
+            "rule=7&url=http%3A%2F%2Fexample.com",
+            "rule=7&token=1.abc",
+            "rule=x&token=1.abc&url=http%3A%2F%2Fexample.com",
+            "rule&token&url",
+            "",
+        ] {
+            assert!(ContinueRequest::parse(query).is_none(), "{query}");
+        }
+    }
+}
@@ -21,6 +21,295 @@ pub struct Config {
     pub cache_enabled: bool,
     pub cache_size: usize,
     pub cache_ttl_seconds: u64,
+    /// HTTPS 프록시 리스너 설정 (없으면 비활성화)
+    pub tls_listener: Option<TlsListenerConfig>,
+    /// 업스트림 TLS 정책
+    #[serde(default)]
+    pub upstream_tls: UpstreamTlsConfig,
+    /// CONNECT 터널 TLS 가로채기 여부
+    #[serde(default)]
+    pub tls_intercept: bool,
+    /// 인증서 고정(pinning) 목적지 자동 우회 설정
+    #[serde(default)]
+    pub pinning_bypass: PinningBypassConfig,
+    /// 루트 CA 발급 설정
+    #[serde(default)]
+    pub ca: CaCertConfig,
+    /// 가로채기 인증서 발급 설정
+    #[serde(default)]
+    pub leaf_cert: LeafCertConfig,
+    /// 규칙에 일치하지 않는 요청의 기본 동작
+    #[serde(default)]
+    pub access_policy: AccessPolicyConfig,
+    /// 경고(warn) 규칙 안내 페이지 설정
+    #[serde(default)]
+    pub warn_page: WarnPageConfig,
+    /// 세이프서치, 테넌트 제한 강제 설정
+    #[serde(default)]
+    pub content_restrictions: ContentRestrictionConfig,
+    /// ICAP 콘텐츠 검사 서비스 설정 (없으면 비활성화)
+    #[serde(default)]
+    pub icap: Option<IcapConfig>,
+}
+
+/// HTTPS 프록시 리스너 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsListenerConfig {
+    /// 리스너 포트 (`bind_host` 공유)
+    pub bind_port: u16,
+    /// 서버 인증서 체인 PEM 경로
+    pub cert_file: String,
+    /// 서버 개인키 PEM 경로
+    pub key_file: String,
+    /// 클라이언트 인증서 검증용 CA PEM 경로
+    pub client_ca_file: Option<String>,
+    /// 클라이언트 인증서 필수 여부
+    #[serde(default)]
+    pub require_client_cert: bool,
+    /// 리스너 기본 동작 ("allow", "block", 없으면 전역 설정)
+    #[serde(default)]
+    pub default_action: Option<String>,
+}
+
+/// 업스트림(프록시 -> 원 서버) TLS 정책
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpstreamTlsConfig {
+    /// 최소 프로토콜 버전 ("1.2", "1.3")
+    #[serde(default = "default_min_tls_version")]
+    pub min_version: String,
+    /// 허용 암호 스위트 (예: `TLS13_AES_128_GCM_SHA256`, 비어있으면 기본값)
+    #[serde(default)]
+    pub cipher_suites: Vec<String>,
+    /// 허용 키 교환 그룹 (예: `X25519`, `secp256r1`, 비어있으면 기본값)
+    #[serde(default)]
+    pub groups: Vec<String>,
+    /// 도메인별 예외 정책
+    #[serde(default)]
+    pub exceptions: Vec<UpstreamTlsException>,
+    /// 도메인별 클라이언트 인증서 (mTLS)
+    #[serde(default)]
+    pub client_certs: Vec<UpstreamClientCert>,
+}
+
+impl Default for UpstreamTlsConfig {
+    fn default() -> Self {
+        Self {
+            min_version: default_min_tls_version(),
+            cipher_suites: Vec::new(),
+            groups: Vec::new(),
+            exceptions: Vec::new(),
+            client_certs: Vec::new(),
+        }
+    }
+}
+
+fn default_min_tls_version() -> String {
+    "1.2".to_string()
+}
+
+/// 도메인별 업스트림 TLS 예외 (지정하지 않은 항목은 전역 정책 사용)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpstreamTlsException {
+    /// 대상 도메인 (하위 도메인 포함)
+    pub domain: String,
+    pub min_version: Option<String>,
+    pub cipher_suites: Option<Vec<String>>,
+    pub groups: Option<Vec<String>>,
+}
+
+/// 인증서 고정 목적지 자동 우회 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PinningBypassConfig {
+    /// 자동 우회 사용 여부
+    pub enabled: bool,
+    /// 우회 전환까지 허용하는 핸드셰이크 실패 횟수
+    pub failure_threshold: u32,
+    /// 실패 횟수 집계 구간 (초)
+    pub window_seconds: u64,
+    /// 자동 우회 유지 시간 (초)
+    pub ttl_seconds: u64,
+}
+
+impl Default for PinningBypassConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 3,
+            window_seconds: 300,
+            ttl_seconds: 86400,
+        }
+    }
+}
+
+/// 기본 동작 설정 (`block`이면 허용 규칙에 일치하는 요청만 통과)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessPolicyConfig {
+    /// 전역 기본 동작 ("allow", "block")
+    pub default_action: String,
+    /// 클라이언트 네트워크별 기본 동작 (가장 긴 프리픽스 우선, 리스너 설정보다 우선)
+    pub networks: Vec<NetworkAccessPolicy>,
+}
+
+impl Default for AccessPolicyConfig {
+    fn default() -> Self {
+        Self {
+            default_action: "allow".to_string(),
+            networks: Vec::new(),
+        }
+    }
+}
+
+/// 경고(warn) 규칙 안내 페이지 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WarnPageConfig {
+    /// 계속 진행 후 같은 클라이언트, 규칙에 경고를 다시 표시하지 않는 시간 (초)
+    pub ack_ttl_seconds: u64,
+}
+
+impl Default for WarnPageConfig {
+    fn default() -> Self {
+        Self {
+            ack_ttl_seconds: 3600,
+        }
+    }
+}
+
+/// 세이프서치, 테넌트 제한 강제 설정 (항목별로 켜고 끔)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentRestrictionConfig {
+    /// Google 검색 세이프서치 강제
+    pub google_safesearch: bool,
+    /// Bing 검색 세이프서치(엄격) 강제
+    pub bing_safesearch: bool,
+    /// YouTube 제한 모드 ("strict", "moderate", 없으면 사용 안 함)
+    pub youtube_restrict: Option<String>,
+    /// Microsoft 365 테넌트 제한 (없으면 사용 안 함)
+    pub microsoft_tenants: Option<MicrosoftTenantRestriction>,
+    /// Google Workspace 허용 도메인 (비어있으면 사용 안 함)
+    pub google_workspace_domains: Vec<String>,
+}
+
+/// Microsoft 365 테넌트 제한
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MicrosoftTenantRestriction {
+    /// 로그인 허용 테넌트 (도메인 또는 테넌트 ID)
+    pub allowed_tenants: Vec<String>,
+    /// 제한을 설정한 테넌트 ID (`Restrict-Access-Context`)
+    pub context_tenant_id: String,
+}
+
+/// ICAP(RFC 3507) 콘텐츠 검사 서비스 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IcapConfig {
+    /// 요청 검사 서비스 URL (예: `icap://127.0.0.1:1344/reqmod`, 없으면 요청 검사 안 함)
+    #[serde(default)]
+    pub reqmod_url: Option<String>,
+    /// 응답 검사 서비스 URL (예: `icap://127.0.0.1:1344/respmod`, 없으면 응답 검사 안 함)
+    #[serde(default)]
+    pub respmod_url: Option<String>,
+    /// 검사 한 건의 연결, 송수신 전체 제한 시간 (밀리초)
+    #[serde(default = "default_icap_timeout_ms")]
+    pub timeout_ms: u64,
+    /// 서비스 장애, 시간 초과시 동작 ("open": 검사 없이 통과, "closed": 차단)
+    #[serde(default = "default_icap_failure_policy")]
+    pub failure_policy: String,
+    /// 미리보기 크기 (바이트, 없으면 서비스 OPTIONS 응답의 `Preview` 사용)
+    #[serde(default)]
+    pub preview_bytes: Option<usize>,
+}
+
+fn default_icap_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_icap_failure_policy() -> String {
+    "open".to_string()
+}
+
+/// 클라이언트 네트워크별 기본 동작
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkAccessPolicy {
+    /// 클라이언트 네트워크 (예: `10.20.0.0/16`)
+    pub cidr: String,
+    /// 기본 동작 ("allow", "block")
+    pub default_action: String,
+}
+
+/// 업스트림 클라이언트 인증서 (경로는 `ssl_dir` 기준)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpstreamClientCert {
+    /// 대상 도메인 (하위 도메인 포함)
+    pub domain: String,
+    /// 인증서 체인 PEM 경로
+    pub cert_file: String,
+    /// 개인키 PEM 경로
+    pub key_file: String,
+}
+
+/// 루트 CA 발급 설정 (새 CA 생성시 적용)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaCertConfig {
+    /// 키 알고리즘 (ecdsa-p256, ecdsa-p384, ed25519, rsa-2048, rsa-4096)
+    pub key_algorithm: String,
+    /// 유효 기간 (일)
+    pub validity_days: u32,
+    pub common_name: String,
+    pub organization: Option<String>,
+    pub organizational_unit: Option<String>,
+    pub country: Option<String>,
+    /// 이름 제약 - 허용 DNS 이름 (하위 도메인 포함)
+    pub permitted_dns_names: Vec<String>,
+    /// 이름 제약 - 제외 DNS 이름 (하위 도메인 포함)
+    pub excluded_dns_names: Vec<String>,
+}
+
+impl Default for CaCertConfig {
+    fn default() -> Self {
+        Self {
+            key_algorithm: default_key_algorithm(),
+            validity_days: 3650,
+            common_name: "UDSS Proxy Root CA".to_string(),
+            organization: Some("CoremaxTech".to_string()),
+            organizational_unit: None,
+            country: Some("KR".to_string()),
+            permitted_dns_names: Vec::new(),
+            excluded_dns_names: Vec::new(),
+        }
+    }
+}
+
+/// 가로채기 인증서 발급 설정 (CN과 SAN은 대상 호스트)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeafCertConfig {
+    /// 키 알고리즘 (ecdsa-p256, ecdsa-p384, ed25519, rsa-2048, rsa-4096)
+    pub key_algorithm: String,
+    /// 유효 기간 (일)
+    pub validity_days: u32,
+    pub organization: Option<String>,
+    pub organizational_unit: Option<String>,
+    pub country: Option<String>,
+}
+
+impl Default for LeafCertConfig {
+    fn default() -> Self {
+        Self {
+            key_algorithm: default_key_algorithm(),
+            validity_days: 365,
+            organization: None,
+            organizational_unit: None,
+            country: None,
+        }
+    }
+}
+
+fn default_key_algorithm() -> String {
+    "ecdsa-p256".to_string()
 }
 
 impl Default for Config {
@@ -46,6 +335,16 @@ impl Config {
             cache_enabled: true,
             cache_size: 1000,
             cache_ttl_seconds: 300,
+            tls_listener: None,
+            upstream_tls: UpstreamTlsConfig::default(),
+            tls_intercept: false,
+            pinning_bypass: PinningBypassConfig::default(),
+            ca: CaCertConfig::default(),
+            leaf_cert: LeafCertConfig::default(),
+            access_policy: AccessPolicyConfig::default(),
+            warn_page: WarnPageConfig::default(),
+            content_restrictions: ContentRestrictionConfig::default(),
+            icap: None,
         }
     }
 
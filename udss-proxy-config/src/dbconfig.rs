@@ -15,6 +15,12 @@ pub struct DbConfig {
     pub partitioning: PartitionConfig,
     /// 연결 풀 설정
     pub pool: PoolConfig,
+    /// 차단 목록 변경 감지 설정
+    #[serde(default)]
+    pub reload: ReloadConfig,
+    /// 규칙 적중 기록 설정
+    #[serde(default)]
+    pub rule_hits: RuleHitsConfig,
 }
 
 impl DbConfig {
@@ -76,6 +82,38 @@ impl Default for PartitionConfig {
     }
 }
 
+/// 차단 목록 변경 감지 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReloadConfig {
+    /// 알림(LISTEN) 연결이 끊겼을 때 재로드 주기(초)
+    pub poll_interval_seconds: u64,
+}
+
+impl Default for ReloadConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_seconds: 30,
+        }
+    }
+}
+
+/// 규칙 적중 기록 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleHitsConfig {
+    /// 메모리에 누적한 적중 수를 `rule_hits` 테이블에 반영하는 주기(초)
+    pub flush_interval_seconds: u64,
+}
+
+impl Default for RuleHitsConfig {
+    fn default() -> Self {
+        Self {
+            flush_interval_seconds: 60,
+        }
+    }
+}
+
 /// 데이터베이스 연결 풀 설정
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolConfig {
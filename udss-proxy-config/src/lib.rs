@@ -2,6 +2,10 @@ pub mod config;
 pub mod dbconfig;
 pub mod setting;
 
-pub use config::Config;
+pub use config::{
+    AccessPolicyConfig, CaCertConfig, Config, ContentRestrictionConfig, IcapConfig, LeafCertConfig,
+    MicrosoftTenantRestriction, NetworkAccessPolicy, PinningBypassConfig, TlsListenerConfig,
+    UpstreamClientCert, UpstreamTlsConfig, UpstreamTlsException, WarnPageConfig,
+};
 pub use dbconfig::DbConfig;
 pub use setting::Settings;
@@ -6,8 +6,11 @@ use udss_proxy_error::Result;
 use crate::partitions::{TableType, create_partitions};
 use crate::pool::DatabasePool;
 use crate::sql::{
-    domain_blocks, domain_pattern_blocks, proxy_stats, proxy_stats_hourly, request_logs,
-    response_logs,
+    acl_schedules, blocklist_notify, category_domains, client_networks, domain_blocks,
+    domain_categories, domain_pattern_blocks, file_type_blocks, header_rewrite_rules, ip_blocks,
+    proxy_stats, proxy_stats_hourly, request_logs, response_logs, rule_audit_log, rule_hits,
+    tls_fingerprint_blocks, tls_intercept_bypass, upstream_client_certs, upstream_tls_logs,
+    url_blocks,
 };
 
 /// 데이터베이스 초기화
@@ -49,6 +52,13 @@ async fn create_tables(conn: &deadpool_postgres::Object) -> Result<()> {
         Ok(_) => {
             info!("request_logs 테이블 생성 완료");
 
+            // 컬럼 추가
+            for alter_query in request_logs::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("request_logs 컬럼 추가 실패: {e}");
+                }
+            }
+
             // 인덱싱
             // for index_query in request_logs::CREATE_INDICES {
             //     if let Err(e) = conn.execute(index_query, &[]).await {
@@ -78,6 +88,23 @@ async fn create_tables(conn: &deadpool_postgres::Object) -> Result<()> {
         }
     }
 
+    // upstream_tls_logs
+    match conn.execute(upstream_tls_logs::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("upstream_tls_logs 테이블 생성 완료");
+
+            // 컬럼 추가
+            for alter_query in upstream_tls_logs::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("upstream_tls_logs 컬럼 추가 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("upstream_tls_logs 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
     // proxy_stats
     match conn.execute(proxy_stats::CREATE_TABLE, &[]).await {
         Ok(_) => {
@@ -112,11 +139,28 @@ async fn create_tables(conn: &deadpool_postgres::Object) -> Result<()> {
         }
     }
 
+    // acl_schedules (규칙 테이블이 참조하므로 먼저 생성)
+    match conn.execute(acl_schedules::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("acl_schedules 테이블 생성 완료");
+        }
+        Err(e) => {
+            error!("acl_schedules 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
     // domain_blocks
     match conn.execute(domain_blocks::CREATE_TABLE, &[]).await {
         Ok(_) => {
             info!("domain_blocks 테이블 생성 완료");
 
+            // 컬럼 추가
+            for alter_query in domain_blocks::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("domain_blocks 컬럼 추가 실패: {e}");
+                }
+            }
+
             // 인덱싱
             for index_query in domain_blocks::CREATE_INDICES {
                 if let Err(e) = conn.execute(index_query, &[]).await {
@@ -134,6 +178,13 @@ async fn create_tables(conn: &deadpool_postgres::Object) -> Result<()> {
         Ok(_) => {
             info!("domain_pattern_blocks 테이블 생성 완료");
 
+            // 컬럼 추가
+            for alter_query in domain_pattern_blocks::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("domain_pattern_blocks 컬럼 추가 실패: {e}");
+                }
+            }
+
             // 인덱싱
             for index_query in domain_pattern_blocks::CREATE_INDICES {
                 if let Err(e) = conn.execute(index_query, &[]).await {
@@ -146,6 +197,278 @@ async fn create_tables(conn: &deadpool_postgres::Object) -> Result<()> {
         }
     }
 
+    // tls_fingerprint_blocks
+    match conn
+        .execute(tls_fingerprint_blocks::CREATE_TABLE, &[])
+        .await
+    {
+        Ok(_) => {
+            info!("tls_fingerprint_blocks 테이블 생성 완료");
+
+            // 컬럼 추가
+            for alter_query in tls_fingerprint_blocks::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("tls_fingerprint_blocks 컬럼 추가 실패: {e}");
+                }
+            }
+
+            // 인덱싱
+            for index_query in tls_fingerprint_blocks::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("tls_fingerprint_blocks 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("tls_fingerprint_blocks 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // domain_categories
+    match conn.execute(domain_categories::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("domain_categories 테이블 생성 완료");
+
+            // 컬럼 추가
+            for alter_query in domain_categories::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("domain_categories 컬럼 추가 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("domain_categories 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // category_domains
+    match conn.execute(category_domains::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("category_domains 테이블 생성 완료");
+
+            // 인덱싱
+            for index_query in category_domains::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("category_domains 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("category_domains 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // url_blocks
+    match conn.execute(url_blocks::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("url_blocks 테이블 생성 완료");
+
+            // 컬럼 추가
+            for alter_query in url_blocks::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("url_blocks 컬럼 추가 실패: {e}");
+                }
+            }
+
+            // 인덱싱
+            for index_query in url_blocks::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("url_blocks 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("url_blocks 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // ip_blocks
+    match conn.execute(ip_blocks::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("ip_blocks 테이블 생성 완료");
+
+            // 컬럼 변경
+            for alter_query in ip_blocks::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("ip_blocks 컬럼 변경 실패: {e}");
+                }
+            }
+
+            // 인덱싱
+            for index_query in ip_blocks::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("ip_blocks 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("ip_blocks 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // client_networks
+    match conn.execute(client_networks::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("client_networks 테이블 생성 완료");
+
+            // 컬럼 추가
+            for alter_query in client_networks::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("client_networks 컬럼 추가 실패: {e}");
+                }
+            }
+
+            // 인덱싱
+            for index_query in client_networks::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("client_networks 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("client_networks 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // file_type_blocks
+    match conn.execute(file_type_blocks::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("file_type_blocks 테이블 생성 완료");
+
+            // 컬럼 변경
+            for alter_query in file_type_blocks::ALTER_COLUMNS {
+                if let Err(e) = conn.execute(alter_query, &[]).await {
+                    error!("file_type_blocks 컬럼 변경 실패: {e}");
+                }
+            }
+
+            // 인덱싱
+            for index_query in file_type_blocks::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("file_type_blocks 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("file_type_blocks 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // header_rewrite_rules
+    match conn.execute(header_rewrite_rules::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("header_rewrite_rules 테이블 생성 완료");
+
+            // 인덱싱
+            for index_query in header_rewrite_rules::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("header_rewrite_rules 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("header_rewrite_rules 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // rule_audit_log
+    match conn.execute(rule_audit_log::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("rule_audit_log 테이블 생성 완료");
+
+            // 인덱싱
+            for index_query in rule_audit_log::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("rule_audit_log 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("rule_audit_log 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // rule_hits
+    match conn.execute(rule_hits::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("rule_hits 테이블 생성 완료");
+
+            // 인덱싱
+            for index_query in rule_hits::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("rule_hits 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("rule_hits 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // tls_intercept_bypass
+    match conn.execute(tls_intercept_bypass::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("tls_intercept_bypass 테이블 생성 완료");
+
+            // 인덱싱
+            for index_query in tls_intercept_bypass::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("tls_intercept_bypass 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("tls_intercept_bypass 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // upstream_client_certs
+    match conn.execute(upstream_client_certs::CREATE_TABLE, &[]).await {
+        Ok(_) => {
+            info!("upstream_client_certs 테이블 생성 완료");
+
+            // 인덱싱
+            for index_query in upstream_client_certs::CREATE_INDICES {
+                if let Err(e) = conn.execute(index_query, &[]).await {
+                    error!("upstream_client_certs 인덱스 생성 실패: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            error!("upstream_client_certs 테이블 생성중 오류 발생: {e}");
+        }
+    }
+
+    // 차단 목록 변경 알림 트리거
+    match conn.batch_execute(blocklist_notify::CREATE_FUNCTION).await {
+        Ok(()) => {
+            for trigger_query in blocklist_notify::CREATE_TRIGGERS {
+                if let Err(e) = conn.batch_execute(trigger_query).await {
+                    error!("차단 목록 알림 트리거 생성 실패: {e}");
+                }
+            }
+            info!("차단 목록 알림 트리거 생성 완료");
+        }
+        Err(e) => {
+            error!("차단 목록 알림 함수 생성중 오류 발생: {e}");
+        }
+    }
+
+    // 규칙 변경 기록 트리거
+    match conn.batch_execute(rule_audit_log::CREATE_FUNCTION).await {
+        Ok(()) => {
+            for trigger_query in rule_audit_log::CREATE_TRIGGERS {
+                if let Err(e) = conn.batch_execute(trigger_query).await {
+                    error!("규칙 변경 기록 트리거 생성 실패: {e}");
+                }
+            }
+            info!("규칙 변경 기록 트리거 생성 완료");
+        }
+        Err(e) => {
+            error!("규칙 변경 기록 함수 생성중 오류 발생: {e}");
+        }
+    }
+
     Ok(())
 }
 
@@ -176,16 +499,17 @@ async fn set_all_partitions(conn: &deadpool_postgres::Object, config: &DbConfig)
 
     // proxy_stats_hourly 파티셔닝
     debug!("proxy_stats_hourly 파티션 생성");
-    match create_partitions(
-        conn,
-        TableType::ProxyStatsHourly,
-        future_partitions + 1,
-    )
-    .await
-    {
+    match create_partitions(conn, TableType::ProxyStatsHourly, future_partitions + 1).await {
         Ok(_) => info!("proxy_stats_hourly 파티션 생성완료"),
         Err(e) => error!("proxy_stats_hourly 파티션 생성 실패: {e}"),
     }
 
+    // upstream_tls_logs 파티셔닝
+    debug!("upstream_tls_logs 파티션 생성");
+    match create_partitions(conn, TableType::UpstreamTlsLogs, future_partitions + 1).await {
+        Ok(_) => info!("upstream_tls_logs 파티션 생성완료"),
+        Err(e) => error!("upstream_tls_logs 파티션 생성 실패: {e}"),
+    }
+
     Ok(())
 }
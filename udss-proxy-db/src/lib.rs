@@ -1,12 +1,17 @@
 pub mod db;
+pub mod notify;
 pub mod partitions;
 pub mod pool;
 pub mod sql;
 
+pub use notify::NotifyListener;
 pub use pool::{DatabasePool, PoolStatus, initialize_dbpool};
 
 pub use db::initialize_db;
 
-pub use sql::request_logs;
+pub use sql::{
+    acl_schedules, blocklist_notify, category_domains, domain_categories, request_logs,
+    rule_audit_log, rule_hits, tls_intercept_bypass, upstream_client_certs, upstream_tls_logs,
+};
 
 pub use partitions::{TableType, create_partitions};
@@ -0,0 +1,69 @@
+use std::future::poll_fn;
+
+use log::{debug, warn};
+use tokio::sync::mpsc;
+use tokio_postgres::{AsyncMessage, Client, Config, NoTls};
+
+use udss_proxy_error::Result;
+
+/// 알림 채널 버퍼 크기
+const CHANNEL_CAPACITY: usize = 64;
+
+/// `LISTEN` 전용 연결 (풀과 별도)
+pub struct NotifyListener {
+    // 연결 유지용 클라이언트
+    _client: Client,
+    receiver: mpsc::Receiver<String>,
+}
+
+impl NotifyListener {
+    /// 채널 구독 연결 생성
+    pub(crate) async fn connect(pg_config: &Config, channel: &str) -> Result<Self> {
+        let (client, mut connection) = pg_config.connect(NoTls).await?;
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+
+        // 연결 구동 및 알림 전달 (연결 종료시 sender drop)
+        tokio::spawn(async move {
+            loop {
+                match poll_fn(|cx| connection.poll_message(cx)).await {
+                    Some(Ok(AsyncMessage::Notification(notification))) => {
+                        debug!(
+                            "DB 알림 수신 {}: {}",
+                            notification.channel(),
+                            notification.payload()
+                        );
+                        if sender
+                            .send(notification.payload().to_string())
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        warn!("DB 알림 연결 오류: {e}");
+                        break;
+                    }
+                    None => break,
+                }
+            }
+        });
+
+        client.batch_execute(&format!("LISTEN {channel}")).await?;
+        Ok(Self {
+            _client: client,
+            receiver,
+        })
+    }
+
+    /// 다음 알림 payload (연결이 끊기면 None)
+    pub async fn recv(&mut self) -> Option<String> {
+        self.receiver.recv().await
+    }
+
+    /// 대기중인 알림 모두 비우기
+    pub fn drain(&mut self) {
+        while self.receiver.try_recv().is_ok() {}
+    }
+}
@@ -9,6 +9,7 @@ pub enum TableType {
     ResponseLogs,
     ProxyStats,
     ProxyStatsHourly,
+    UpstreamTlsLogs,
 }
 
 impl TableType {
@@ -19,6 +20,7 @@ impl TableType {
             TableType::ResponseLogs => "response_logs",
             TableType::ProxyStats => "proxy_stats",
             TableType::ProxyStatsHourly => "proxy_stats_hourly",
+            TableType::UpstreamTlsLogs => "upstream_tls_logs",
         }
     }
 }
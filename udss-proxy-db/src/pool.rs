@@ -11,10 +11,14 @@ use tokio_postgres::{
 use udss_proxy_config::DbConfig;
 use udss_proxy_error::{ProxyError, Result};
 
+use crate::notify::NotifyListener;
+
 /// db 풀 인스턴스
 #[derive(Clone)]
 pub struct DatabasePool {
     pool: Arc<Pool>,
+    /// 풀 외부 연결용 설정
+    pg_config: Arc<Config>,
 }
 
 impl DatabasePool {
@@ -26,7 +30,7 @@ impl DatabasePool {
         let pg_config = Self::create_pg_config(dbconfig);
 
         // 연결 풀 생성
-        let pool = Self::create_connection_pool(pg_config, dbconfig).await?;
+        let pool = Self::create_connection_pool(pg_config.clone(), dbconfig).await?;
 
         info!(
             "데이터베이스 연결 풀 초기화 완료 (최대 연결 수: {})",
@@ -35,6 +39,7 @@ impl DatabasePool {
 
         Ok(Self {
             pool: Arc::new(pool),
+            pg_config: Arc::new(pg_config),
         })
     }
 
@@ -100,6 +105,11 @@ impl DatabasePool {
             .map_err(|e| ProxyError::Database(format!("연결 풀에서 연결 가져오기 실패: {e}")))
     }
 
+    /// 알림 채널 구독 (풀과 별도 연결)
+    pub async fn listen(&self, channel: &str) -> Result<NotifyListener> {
+        NotifyListener::connect(&self.pg_config, channel).await
+    }
+
     /// 연결 풀 상태 정보
     pub fn pool_status(&self) -> PoolStatus {
         let status = self.pool.status();
@@ -0,0 +1,14 @@
+/// 테이블 생성 쿼리
+/// (`weekdays`: `mon-fri`, `sat,sun` 형식, `time_ranges`: `09:00-12:00,13:00-18:00` 형식,
+/// `timezone`: IANA 이름, NULL이면 각각 매일/종일/서버 로컬 시간대)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS acl_schedules (
+        id BIGSERIAL PRIMARY KEY,
+        name VARCHAR(100) NOT NULL UNIQUE,
+        weekdays VARCHAR(64),
+        time_ranges TEXT,
+        timezone VARCHAR(64),
+        description TEXT,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    )
+";
@@ -0,0 +1,64 @@
+/// 차단 목록 변경 알림 채널
+pub const CHANNEL: &str = "udss_blocklist_changed";
+
+/// 변경 알림 함수 (payload: 테이블명)
+pub const CREATE_FUNCTION: &str = "
+    CREATE OR REPLACE FUNCTION udss_notify_blocklist_change() RETURNS trigger AS $$
+    BEGIN
+        PERFORM pg_notify('udss_blocklist_changed', TG_TABLE_NAME);
+        RETURN NULL;
+    END;
+    $$ LANGUAGE plpgsql
+";
+
+/// 차단 목록, 업스트림 클라이언트 인증서 테이블 트리거 (문장 단위)
+pub const CREATE_TRIGGERS: [&str; 12] = [
+    "DROP TRIGGER IF EXISTS domain_blocks_notify ON domain_blocks;
+    CREATE TRIGGER domain_blocks_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON domain_blocks
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS domain_pattern_blocks_notify ON domain_pattern_blocks;
+    CREATE TRIGGER domain_pattern_blocks_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON domain_pattern_blocks
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS tls_fingerprint_blocks_notify ON tls_fingerprint_blocks;
+    CREATE TRIGGER tls_fingerprint_blocks_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON tls_fingerprint_blocks
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS url_blocks_notify ON url_blocks;
+    CREATE TRIGGER url_blocks_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON url_blocks
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS domain_categories_notify ON domain_categories;
+    CREATE TRIGGER domain_categories_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON domain_categories
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS category_domains_notify ON category_domains;
+    CREATE TRIGGER category_domains_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON category_domains
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS acl_schedules_notify ON acl_schedules;
+    CREATE TRIGGER acl_schedules_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON acl_schedules
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS ip_blocks_notify ON ip_blocks;
+    CREATE TRIGGER ip_blocks_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON ip_blocks
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS client_networks_notify ON client_networks;
+    CREATE TRIGGER client_networks_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON client_networks
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS file_type_blocks_notify ON file_type_blocks;
+    CREATE TRIGGER file_type_blocks_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON file_type_blocks
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS header_rewrite_rules_notify ON header_rewrite_rules;
+    CREATE TRIGGER header_rewrite_rules_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON header_rewrite_rules
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+    "DROP TRIGGER IF EXISTS upstream_client_certs_notify ON upstream_client_certs;
+    CREATE TRIGGER upstream_client_certs_notify
+        AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON upstream_client_certs
+        FOR EACH STATEMENT EXECUTE FUNCTION udss_notify_blocklist_change()",
+];
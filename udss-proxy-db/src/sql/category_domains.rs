@@ -0,0 +1,33 @@
+/// 테이블 생성 쿼리
+///
+/// `match_type`은 `domain_blocks`와 같다 (`exact`, `suffix`, `wildcard`).
+/// `source`는 가져온 목록 파일 이름.
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS category_domains (
+        id BIGSERIAL PRIMARY KEY,
+        category_id BIGINT NOT NULL REFERENCES domain_categories(id) ON DELETE CASCADE,
+        domain VARCHAR(255) NOT NULL,
+        match_type VARCHAR(16) NOT NULL DEFAULT 'exact'
+            CHECK (match_type IN ('exact', 'suffix', 'wildcard')),
+        source VARCHAR(255),
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        UNIQUE (category_id, domain, match_type)
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 1] =
+    ["CREATE INDEX IF NOT EXISTS category_domains_category_idx ON category_domains(category_id)"];
+
+/// 도메인 일괄 추가 ($2: 도메인 배열, $3: 일치 방식 배열)
+pub const INSERT_BATCH: &str = "
+    INSERT INTO category_domains (category_id, domain, match_type, source)
+    SELECT $1, entry.domain, entry.match_type, $4
+    FROM UNNEST($2::TEXT[], $3::TEXT[]) AS entry(domain, match_type)
+    ON CONFLICT (category_id, domain, match_type) DO NOTHING
+";
+
+/// 카테고리 도메인 전체 삭제
+pub const DELETE_BY_CATEGORY: &str = "
+    DELETE FROM category_domains WHERE category_id = $1
+";
@@ -0,0 +1,23 @@
+/// 테이블 생성 쿼리 (프록시를 사용할 수 있는 클라이언트 네트워크, 가장 긴 프리픽스 규칙 적용,
+/// 일치하는 규칙이 없으면 허용)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS client_networks (
+        id BIGSERIAL PRIMARY KEY,
+        cidr CIDR NOT NULL,
+        action VARCHAR(16) NOT NULL DEFAULT 'block'
+            CHECK (action IN ('block', 'allow')),
+        created_by VARCHAR(100) NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ,
+        description TEXT,
+        active BOOLEAN NOT NULL DEFAULT TRUE
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 1] =
+    ["CREATE INDEX IF NOT EXISTS client_networks_active_idx ON client_networks(active)"];
+
+/// 기존 테이블 컬럼 추가 쿼리
+pub const ALTER_COLUMNS: [&str; 1] =
+    ["ALTER TABLE client_networks ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ"];
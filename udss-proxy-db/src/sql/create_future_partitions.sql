@@ -46,6 +46,16 @@ BEGIN
             
             index_name := partition_name || '_status_code_idx';
             EXECUTE 'CREATE INDEX IF NOT EXISTS ' || index_name || ' ON ' || partition_name || '(status_code)';
+        ELSIF table_prefix = 'upstream_tls_logs' THEN
+            -- upstream_tls_logs 테이블의 파티션별 인덱스 생성
+            index_name := partition_name || '_host_idx';
+            EXECUTE 'CREATE INDEX IF NOT EXISTS ' || index_name || ' ON ' || partition_name || '(host)';
+            
+            index_name := partition_name || '_timestamp_idx';
+            EXECUTE 'CREATE INDEX IF NOT EXISTS ' || index_name || ' ON ' || partition_name || '(timestamp)';
+            
+            index_name := partition_name || '_protocol_version_idx';
+            EXECUTE 'CREATE INDEX IF NOT EXISTS ' || index_name || ' ON ' || partition_name || '(protocol_version)';
         ELSIF table_prefix = 'proxy_stats' THEN
             -- proxy_stats 테이블의 파티션별 인덱스 생성
             index_name := partition_name || '_timestamp_idx';
@@ -3,8 +3,15 @@ pub const CREATE_TABLE: &str = "
     CREATE TABLE IF NOT EXISTS domain_blocks (
         id BIGSERIAL PRIMARY KEY,
         domain VARCHAR(255) NOT NULL,
+        match_type VARCHAR(16) NOT NULL DEFAULT 'exact'
+            CHECK (match_type IN ('exact', 'suffix', 'wildcard')),
+        action VARCHAR(16) NOT NULL DEFAULT 'block'
+            CHECK (action IN ('block', 'allow', 'monitor', 'warn')),
+        priority INTEGER NOT NULL DEFAULT 0,
+        schedule_id BIGINT REFERENCES acl_schedules(id),
         created_by VARCHAR(100) NOT NULL,
         created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ,
         description TEXT,
         active BOOLEAN NOT NULL DEFAULT TRUE
     )
@@ -15,3 +22,20 @@ pub const CREATE_INDICES: [&str; 2] = [
     "CREATE INDEX IF NOT EXISTS domain_blocks_domain_idx ON domain_blocks(domain)",
     "CREATE INDEX IF NOT EXISTS domain_blocks_active_idx ON domain_blocks(active)",
 ];
+
+/// 기존 테이블 컬럼 추가 쿼리
+/// (`exact`: 도메인만, `suffix`: 도메인과 하위 도메인, `wildcard`: 하위 도메인만,
+/// `action`: `block`/`allow`/`monitor`(허용하고 차단 예정으로 기록)/
+/// `warn`(경고 페이지 확인 후 허용), `priority`: 클수록 우선,
+/// `schedule_id`: 적용 시간대, NULL이면 항상 적용, `expires_at`: 만료 시각, NULL이면 만료 없음)
+pub const ALTER_COLUMNS: [&str; 6] = [
+    "ALTER TABLE domain_blocks ADD COLUMN IF NOT EXISTS match_type VARCHAR(16) NOT NULL DEFAULT 'exact'
+        CHECK (match_type IN ('exact', 'suffix', 'wildcard'))",
+    "ALTER TABLE domain_blocks ADD COLUMN IF NOT EXISTS action VARCHAR(16) NOT NULL DEFAULT 'block'
+        CHECK (action IN ('block', 'allow', 'monitor', 'warn'))",
+    "ALTER TABLE domain_blocks ADD COLUMN IF NOT EXISTS priority INTEGER NOT NULL DEFAULT 0",
+    "ALTER TABLE domain_blocks ADD COLUMN IF NOT EXISTS schedule_id BIGINT REFERENCES acl_schedules(id)",
+    "ALTER TABLE domain_blocks DROP CONSTRAINT IF EXISTS domain_blocks_action_check,
+        ADD CONSTRAINT domain_blocks_action_check CHECK (action IN ('block', 'allow', 'monitor', 'warn'))",
+    "ALTER TABLE domain_blocks ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ",
+];
@@ -0,0 +1,41 @@
+/// 테이블 생성 쿼리 (`enabled`가 FALSE면 카테고리 도메인 전체 미적용,
+/// `action`/`priority`는 카테고리 도메인 전체에 적용)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS domain_categories (
+        id BIGSERIAL PRIMARY KEY,
+        name VARCHAR(100) NOT NULL UNIQUE,
+        description TEXT,
+        enabled BOOLEAN NOT NULL DEFAULT TRUE,
+        action VARCHAR(16) NOT NULL DEFAULT 'block'
+            CHECK (action IN ('block', 'allow', 'monitor', 'warn')),
+        priority INTEGER NOT NULL DEFAULT 0,
+        schedule_id BIGINT REFERENCES acl_schedules(id),
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ
+    )
+";
+
+/// 기존 테이블 컬럼 추가 쿼리
+pub const ALTER_COLUMNS: [&str; 5] = [
+    "ALTER TABLE domain_categories ADD COLUMN IF NOT EXISTS action VARCHAR(16) NOT NULL DEFAULT 'block'
+        CHECK (action IN ('block', 'allow', 'monitor', 'warn'))",
+    "ALTER TABLE domain_categories ADD COLUMN IF NOT EXISTS priority INTEGER NOT NULL DEFAULT 0",
+    "ALTER TABLE domain_categories ADD COLUMN IF NOT EXISTS schedule_id BIGINT REFERENCES acl_schedules(id)",
+    "ALTER TABLE domain_categories DROP CONSTRAINT IF EXISTS domain_categories_action_check,
+        ADD CONSTRAINT domain_categories_action_check CHECK (action IN ('block', 'allow', 'monitor', 'warn'))",
+    "ALTER TABLE domain_categories ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ",
+];
+
+/// 카테고리 생성 (이미 있으면 ID 반환)
+pub const UPSERT: &str = "
+    INSERT INTO domain_categories (name, description)
+    VALUES ($1, $2)
+    ON CONFLICT (name) DO UPDATE
+        SET description = COALESCE(EXCLUDED.description, domain_categories.description)
+    RETURNING id
+";
+
+/// 카테고리 활성/비활성
+pub const SET_ENABLED: &str = "
+    UPDATE domain_categories SET enabled = $2 WHERE name = $1
+";
@@ -1,10 +1,16 @@
 /// 테이블 생성 쿼리
+/// (`pattern`은 정규화된 호스트(소문자, 끝 `.` 제거, 국제화 도메인은 `xn--` 퓨니코드)와 비교)
 pub const CREATE_TABLE: &str = "
     CREATE TABLE IF NOT EXISTS domain_pattern_blocks (
         id BIGSERIAL PRIMARY KEY,
         pattern VARCHAR(255) NOT NULL,
+        action VARCHAR(16) NOT NULL DEFAULT 'block'
+            CHECK (action IN ('block', 'allow', 'monitor', 'warn')),
+        priority INTEGER NOT NULL DEFAULT 0,
+        schedule_id BIGINT REFERENCES acl_schedules(id),
         created_by VARCHAR(100) NOT NULL,
         created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ,
         description TEXT,
         active BOOLEAN NOT NULL DEFAULT TRUE
     )
@@ -15,3 +21,14 @@ pub const CREATE_INDICES: [&str; 2] = [
     "CREATE INDEX IF NOT EXISTS domain_pattern_blocks_pattern_idx ON domain_pattern_blocks(pattern)",
     "CREATE INDEX IF NOT EXISTS domain_pattern_blocks_active_idx ON domain_pattern_blocks(active)",
 ];
+
+/// 기존 테이블 컬럼 추가 쿼리
+pub const ALTER_COLUMNS: [&str; 5] = [
+    "ALTER TABLE domain_pattern_blocks ADD COLUMN IF NOT EXISTS action VARCHAR(16) NOT NULL DEFAULT 'block'
+        CHECK (action IN ('block', 'allow', 'monitor', 'warn'))",
+    "ALTER TABLE domain_pattern_blocks ADD COLUMN IF NOT EXISTS priority INTEGER NOT NULL DEFAULT 0",
+    "ALTER TABLE domain_pattern_blocks ADD COLUMN IF NOT EXISTS schedule_id BIGINT REFERENCES acl_schedules(id)",
+    "ALTER TABLE domain_pattern_blocks DROP CONSTRAINT IF EXISTS domain_pattern_blocks_action_check,
+        ADD CONSTRAINT domain_pattern_blocks_action_check CHECK (action IN ('block', 'allow', 'monitor', 'warn'))",
+    "ALTER TABLE domain_pattern_blocks ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ",
+];
@@ -0,0 +1,35 @@
+/// 테이블 생성 쿼리
+/// (`match_type`: `extension`(예: `exe`), `content_type`(예: `application/x-msdownload`, `application/*`),
+/// `magic`(`exe`, `elf`, `macho`, `ole`, `zip`, `rar`, `7z`, `gzip`, `bzip2`, `xz`, `cab`, `pdf`),
+/// `host`: 적용 호스트(`*.`, `.` 접두어 허용), NULL이면 모든 호스트,
+/// `uncategorized_only`: 카테고리에 속하지 않은 호스트에만 적용)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS file_type_blocks (
+        id BIGSERIAL PRIMARY KEY,
+        match_type VARCHAR(16) NOT NULL
+            CHECK (match_type IN ('extension', 'content_type', 'magic')),
+        value VARCHAR(255) NOT NULL,
+        host VARCHAR(255),
+        uncategorized_only BOOLEAN NOT NULL DEFAULT FALSE,
+        action VARCHAR(16) NOT NULL DEFAULT 'block'
+            CHECK (action IN ('block', 'allow', 'monitor')),
+        priority INTEGER NOT NULL DEFAULT 0,
+        schedule_id BIGINT REFERENCES acl_schedules(id),
+        created_by VARCHAR(100) NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ,
+        description TEXT,
+        active BOOLEAN NOT NULL DEFAULT TRUE
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 1] =
+    ["CREATE INDEX IF NOT EXISTS file_type_blocks_active_idx ON file_type_blocks(active)"];
+
+/// 기존 테이블 변경 쿼리 (`monitor` 동작, `expires_at` 컬럼 추가)
+pub const ALTER_COLUMNS: [&str; 2] = [
+    "ALTER TABLE file_type_blocks DROP CONSTRAINT IF EXISTS file_type_blocks_action_check,
+        ADD CONSTRAINT file_type_blocks_action_check CHECK (action IN ('block', 'allow', 'monitor'))",
+    "ALTER TABLE file_type_blocks ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ",
+];
@@ -0,0 +1,30 @@
+/// 테이블 생성 쿼리
+/// (`host`: 적용 호스트(`*.`, `.` 접두어 허용, 기본은 하위 도메인 포함), NULL이면 모든 호스트,
+/// `direction`: `request`(업스트림 요청), `response`(클라이언트 응답),
+/// `operation`: `set`(교체), `add`(추가), `remove`(삭제), `keep`(덜 구체적인 규칙의 예외),
+/// `header_value`: `set`, `add`의 값 템플릿 (`{client_ip}`, `{user}`, `{host}`),
+/// 같은 헤더에 여러 규칙이 일치하면 `priority`, 구체적인 호스트 순으로 하나만 적용)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS header_rewrite_rules (
+        id BIGSERIAL PRIMARY KEY,
+        host VARCHAR(255),
+        direction VARCHAR(16) NOT NULL
+            CHECK (direction IN ('request', 'response')),
+        operation VARCHAR(16) NOT NULL
+            CHECK (operation IN ('set', 'add', 'remove', 'keep')),
+        header_name VARCHAR(255) NOT NULL,
+        header_value TEXT,
+        priority INTEGER NOT NULL DEFAULT 0,
+        schedule_id BIGINT REFERENCES acl_schedules(id),
+        created_by VARCHAR(100) NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ,
+        description TEXT,
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        CHECK (operation IN ('remove', 'keep') OR header_value IS NOT NULL)
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 1] =
+    ["CREATE INDEX IF NOT EXISTS header_rewrite_rules_active_idx ON header_rewrite_rules(active)"];
@@ -0,0 +1,27 @@
+/// 테이블 생성 쿼리 (목적지 IP/CIDR 규칙, IP 리터럴 호스트와 DNS 조회 결과 주소에 적용)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS ip_blocks (
+        id BIGSERIAL PRIMARY KEY,
+        cidr CIDR NOT NULL,
+        action VARCHAR(16) NOT NULL DEFAULT 'block'
+            CHECK (action IN ('block', 'allow', 'monitor', 'warn')),
+        priority INTEGER NOT NULL DEFAULT 0,
+        schedule_id BIGINT REFERENCES acl_schedules(id),
+        created_by VARCHAR(100) NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ,
+        description TEXT,
+        active BOOLEAN NOT NULL DEFAULT TRUE
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 1] =
+    ["CREATE INDEX IF NOT EXISTS ip_blocks_active_idx ON ip_blocks(active)"];
+
+/// 기존 테이블 변경 쿼리 (`monitor`, `warn` 동작, `expires_at` 컬럼 추가)
+pub const ALTER_COLUMNS: [&str; 2] = [
+    "ALTER TABLE ip_blocks DROP CONSTRAINT IF EXISTS ip_blocks_action_check,
+        ADD CONSTRAINT ip_blocks_action_check CHECK (action IN ('block', 'allow', 'monitor', 'warn'))",
+    "ALTER TABLE ip_blocks ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ",
+];
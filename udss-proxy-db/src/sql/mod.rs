@@ -1,6 +1,21 @@
+pub mod acl_schedules;
+pub mod blocklist_notify;
+pub mod category_domains;
+pub mod client_networks;
 pub mod domain_blocks;
+pub mod domain_categories;
 pub mod domain_pattern_blocks;
+pub mod file_type_blocks;
+pub mod header_rewrite_rules;
+pub mod ip_blocks;
 pub mod proxy_stats;
 pub mod proxy_stats_hourly;
 pub mod request_logs;
 pub mod response_logs;
+pub mod rule_audit_log;
+pub mod rule_hits;
+pub mod tls_fingerprint_blocks;
+pub mod tls_intercept_bypass;
+pub mod upstream_client_certs;
+pub mod upstream_tls_logs;
+pub mod url_blocks;
@@ -13,6 +13,15 @@ pub const CREATE_TABLE: &str = "
         target_ip TEXT NOT NULL,
         is_rejected BOOLEAN NOT NULL DEFAULT FALSE,
         is_tls BOOLEAN NOT NULL DEFAULT FALSE,
+        ja3_hash TEXT,
+        ja4 TEXT,
+        rule_id BIGINT,
+        rule_type TEXT,
+        rule_action TEXT,
+        rule_reason TEXT,
+        monitor_rule_id BIGINT,
+        monitor_rule_type TEXT,
+        monitor_reason TEXT,
         PRIMARY KEY (id, timestamp)
     ) PARTITION BY RANGE (timestamp)";
 
@@ -25,3 +34,51 @@ pub const CREATE_INDICES: [&str; 6] = [
     "CREATE INDEX IF NOT EXISTS request_logs_client_ip_idx ON request_logs(client_ip)",
     "CREATE INDEX IF NOT EXISTS request_logs_target_ip_idx ON request_logs(target_ip)",
 ];
+
+/// 기존 테이블 컬럼 추가 쿼리
+pub const ALTER_COLUMNS: [&str; 9] = [
+    "ALTER TABLE request_logs ADD COLUMN IF NOT EXISTS ja3_hash TEXT",
+    "ALTER TABLE request_logs ADD COLUMN IF NOT EXISTS ja4 TEXT",
+    "ALTER TABLE request_logs ADD COLUMN IF NOT EXISTS rule_id BIGINT",
+    "ALTER TABLE request_logs ADD COLUMN IF NOT EXISTS rule_type TEXT",
+    "ALTER TABLE request_logs ADD COLUMN IF NOT EXISTS rule_action TEXT",
+    "ALTER TABLE request_logs ADD COLUMN IF NOT EXISTS rule_reason TEXT",
+    "ALTER TABLE request_logs ADD COLUMN IF NOT EXISTS monitor_rule_id BIGINT",
+    "ALTER TABLE request_logs ADD COLUMN IF NOT EXISTS monitor_rule_type TEXT",
+    "ALTER TABLE request_logs ADD COLUMN IF NOT EXISTS monitor_reason TEXT",
+];
+
+/// 요청 로그 저장 쿼리
+pub const INSERT: &str = "
+    INSERT INTO request_logs (
+        host, method, path, header, body, session_id, client_ip, target_ip,
+        is_rejected, is_tls, ja3_hash, ja4, rule_id, rule_type, rule_action, rule_reason,
+        monitor_rule_id, monitor_rule_type, monitor_reason
+    )
+    VALUES (
+        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19
+    )
+";
+
+/// 모니터 규칙별 차단 예정 요약 쿼리 (`$1`: 최근 시간 수)
+pub const SELECT_MONITOR_SUMMARY: &str = "
+    SELECT monitor_rule_type, monitor_rule_id, COUNT(*) AS hits,
+           COUNT(DISTINCT client_ip) AS clients, COUNT(DISTINCT host) AS hosts,
+           MIN(timestamp) AS first_seen, MAX(timestamp) AS last_seen
+    FROM request_logs
+    WHERE monitor_rule_id IS NOT NULL
+      AND timestamp >= NOW() - make_interval(hours => $1)
+    GROUP BY monitor_rule_type, monitor_rule_id
+    ORDER BY hits DESC
+";
+
+/// 모니터 규칙별 차단 예정 클라이언트, 호스트 쿼리 (`$1`: 최근 시간 수, `$2`: 최대 행 수)
+pub const SELECT_MONITOR_HITS: &str = "
+    SELECT monitor_rule_type, monitor_rule_id, client_ip, host, COUNT(*) AS hits
+    FROM request_logs
+    WHERE monitor_rule_id IS NOT NULL
+      AND timestamp >= NOW() - make_interval(hours => $1)
+    GROUP BY monitor_rule_type, monitor_rule_id, client_ip, host
+    ORDER BY hits DESC
+    LIMIT $2
+";
@@ -0,0 +1,126 @@
+/// 테이블 생성 쿼리 (규칙 테이블 변경 이력, 트리거로만 기록)
+/// (`operation`: `INSERT`, `UPDATE`, `DELETE`, `old_values`/`new_values`: 변경 전후 행 전체,
+/// 목록 가져오기는 `IMPORT`, `row_id`: 카테고리 ID, `new_values`: 가져오기 요약)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS rule_audit_log (
+        id BIGSERIAL PRIMARY KEY,
+        table_name VARCHAR(64) NOT NULL,
+        operation VARCHAR(8) NOT NULL,
+        row_id BIGINT,
+        old_values JSONB,
+        new_values JSONB,
+        actor TEXT NOT NULL,
+        changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 2] = [
+    "CREATE INDEX IF NOT EXISTS rule_audit_log_changed_at_idx ON rule_audit_log(changed_at)",
+    "CREATE INDEX IF NOT EXISTS rule_audit_log_row_idx ON rule_audit_log(table_name, row_id)",
+];
+
+/// 변경 기록 함수 (행 단위)
+///
+/// 변경자는 세션 설정 `udss.actor`(`SET udss.actor = 'admin'`), 추가 시 `created_by`,
+/// DB 사용자 순으로 정한다. 값이 바뀌지 않은 UPDATE는 기록하지 않는다.
+pub const CREATE_FUNCTION: &str = "
+    CREATE OR REPLACE FUNCTION udss_audit_rule_change() RETURNS trigger AS $$
+    DECLARE
+        old_values JSONB;
+        new_values JSONB;
+    BEGIN
+        IF TG_OP <> 'INSERT' THEN
+            old_values := to_jsonb(OLD);
+        END IF;
+        IF TG_OP <> 'DELETE' THEN
+            new_values := to_jsonb(NEW);
+        END IF;
+        IF TG_OP = 'UPDATE' AND old_values = new_values THEN
+            RETURN NULL;
+        END IF;
+
+        INSERT INTO rule_audit_log (table_name, operation, row_id, old_values, new_values, actor)
+        VALUES (
+            TG_TABLE_NAME,
+            TG_OP,
+            (COALESCE(new_values, old_values)->>'id')::BIGINT,
+            old_values,
+            new_values,
+            COALESCE(
+                NULLIF(current_setting('udss.actor', true), ''),
+                CASE WHEN TG_OP = 'INSERT' THEN NULLIF(new_values->>'created_by', '') END,
+                session_user
+            )
+        );
+        RETURN NULL;
+    END;
+    $$ LANGUAGE plpgsql
+";
+
+/// 현재 트랜잭션의 변경자 지정 (`$1`: 변경자)
+pub const SET_ACTOR: &str = "SELECT set_config('udss.actor', $1, true)";
+
+/// 목록 가져오기 요약 기록
+/// (`$1`: 카테고리 ID, `$2`: 카테고리, `$3`: 출처, `$4`: 교체 여부, `$5`: 삭제 수, `$6`: 추가 수, `$7`: 변경자)
+pub const INSERT_IMPORT_SUMMARY: &str = "
+    INSERT INTO rule_audit_log (table_name, operation, row_id, new_values, actor)
+    VALUES (
+        'category_domains',
+        'IMPORT',
+        $1,
+        jsonb_build_object(
+            'category', $2::TEXT,
+            'source', $3::TEXT,
+            'replace', $4::BOOLEAN,
+            'deleted', $5::BIGINT,
+            'inserted', $6::BIGINT
+        ),
+        $7
+    )
+";
+
+/// 규칙 테이블 변경 기록 트리거 (행 단위)
+/// (`category_domains`는 목록 가져오기로 대량 변경되므로 제외, 가져오기마다 요약 한 건 기록)
+pub const CREATE_TRIGGERS: [&str; 10] = [
+    "DROP TRIGGER IF EXISTS domain_blocks_audit ON domain_blocks;
+    CREATE TRIGGER domain_blocks_audit
+        AFTER INSERT OR UPDATE OR DELETE ON domain_blocks
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+    "DROP TRIGGER IF EXISTS domain_pattern_blocks_audit ON domain_pattern_blocks;
+    CREATE TRIGGER domain_pattern_blocks_audit
+        AFTER INSERT OR UPDATE OR DELETE ON domain_pattern_blocks
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+    "DROP TRIGGER IF EXISTS tls_fingerprint_blocks_audit ON tls_fingerprint_blocks;
+    CREATE TRIGGER tls_fingerprint_blocks_audit
+        AFTER INSERT OR UPDATE OR DELETE ON tls_fingerprint_blocks
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+    "DROP TRIGGER IF EXISTS url_blocks_audit ON url_blocks;
+    CREATE TRIGGER url_blocks_audit
+        AFTER INSERT OR UPDATE OR DELETE ON url_blocks
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+    "DROP TRIGGER IF EXISTS domain_categories_audit ON domain_categories;
+    CREATE TRIGGER domain_categories_audit
+        AFTER INSERT OR UPDATE OR DELETE ON domain_categories
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+    "DROP TRIGGER IF EXISTS acl_schedules_audit ON acl_schedules;
+    CREATE TRIGGER acl_schedules_audit
+        AFTER INSERT OR UPDATE OR DELETE ON acl_schedules
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+    "DROP TRIGGER IF EXISTS ip_blocks_audit ON ip_blocks;
+    CREATE TRIGGER ip_blocks_audit
+        AFTER INSERT OR UPDATE OR DELETE ON ip_blocks
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+    "DROP TRIGGER IF EXISTS client_networks_audit ON client_networks;
+    CREATE TRIGGER client_networks_audit
+        AFTER INSERT OR UPDATE OR DELETE ON client_networks
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+    "DROP TRIGGER IF EXISTS file_type_blocks_audit ON file_type_blocks;
+    CREATE TRIGGER file_type_blocks_audit
+        AFTER INSERT OR UPDATE OR DELETE ON file_type_blocks
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+    "DROP TRIGGER IF EXISTS header_rewrite_rules_audit ON header_rewrite_rules;
+    CREATE TRIGGER header_rewrite_rules_audit
+        AFTER INSERT OR UPDATE OR DELETE ON header_rewrite_rules
+        FOR EACH ROW EXECUTE FUNCTION udss_audit_rule_change()",
+];
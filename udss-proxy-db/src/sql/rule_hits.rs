@@ -0,0 +1,28 @@
+/// 테이블 생성 쿼리 (규칙 종류, ID별 적중 수, 차단/허용/모니터/경고 판정에 쓰인 규칙 기준)
+/// (`rule_type`: `domain`, `category`, `pattern`, `url`, `ip`, `client`, `file_type`,
+/// `category`의 `rule_id`는 `category_domains` ID, 적중 기록이 없는 규칙은 한 번도 일치하지 않은 규칙)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS rule_hits (
+        rule_type VARCHAR(16) NOT NULL,
+        rule_id BIGINT NOT NULL,
+        hit_count BIGINT NOT NULL DEFAULT 0,
+        first_hit_at TIMESTAMPTZ NOT NULL,
+        last_hit_at TIMESTAMPTZ NOT NULL,
+        PRIMARY KEY (rule_type, rule_id)
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 1] =
+    ["CREATE INDEX IF NOT EXISTS rule_hits_last_hit_at_idx ON rule_hits(last_hit_at)"];
+
+/// 적중 수 누적 (`$1`~`$5`: 규칙 종류, 규칙 ID, 적중 수, 처음/마지막 적중 시각 배열)
+pub const UPSERT_BATCH: &str = "
+    INSERT INTO rule_hits (rule_type, rule_id, hit_count, first_hit_at, last_hit_at)
+    SELECT * FROM UNNEST(
+        $1::VARCHAR[], $2::BIGINT[], $3::BIGINT[], $4::TIMESTAMPTZ[], $5::TIMESTAMPTZ[]
+    )
+    ON CONFLICT (rule_type, rule_id) DO UPDATE SET
+        hit_count = rule_hits.hit_count + EXCLUDED.hit_count,
+        last_hit_at = GREATEST(rule_hits.last_hit_at, EXCLUDED.last_hit_at)
+";
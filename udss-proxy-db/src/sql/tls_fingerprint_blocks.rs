@@ -0,0 +1,23 @@
+/// 테이블 생성 쿼리
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS tls_fingerprint_blocks (
+        id BIGSERIAL PRIMARY KEY,
+        fingerprint_type VARCHAR(8) NOT NULL CHECK (fingerprint_type IN ('ja3', 'ja4')),
+        fingerprint VARCHAR(255) NOT NULL,
+        created_by VARCHAR(100) NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ,
+        description TEXT,
+        active BOOLEAN NOT NULL DEFAULT TRUE
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 2] = [
+    "CREATE INDEX IF NOT EXISTS tls_fingerprint_blocks_fingerprint_idx ON tls_fingerprint_blocks(fingerprint)",
+    "CREATE INDEX IF NOT EXISTS tls_fingerprint_blocks_active_idx ON tls_fingerprint_blocks(active)",
+];
+
+/// 기존 테이블 컬럼 추가 쿼리
+pub const ALTER_COLUMNS: [&str; 1] =
+    ["ALTER TABLE tls_fingerprint_blocks ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ"];
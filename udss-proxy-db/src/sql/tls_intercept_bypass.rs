@@ -0,0 +1,31 @@
+/// 테이블 생성 쿼리 (`expires_at`이 NULL이면 영구 우회)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS tls_intercept_bypass (
+        id BIGSERIAL PRIMARY KEY,
+        host VARCHAR(255) NOT NULL,
+        failure_count INTEGER NOT NULL DEFAULT 0,
+        created_by VARCHAR(100) NOT NULL DEFAULT 'auto',
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ,
+        description TEXT,
+        active BOOLEAN NOT NULL DEFAULT TRUE
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 2] = [
+    "CREATE INDEX IF NOT EXISTS tls_intercept_bypass_host_idx ON tls_intercept_bypass(host)",
+    "CREATE INDEX IF NOT EXISTS tls_intercept_bypass_active_idx ON tls_intercept_bypass(active)",
+];
+
+/// 자동 우회 기록
+pub const INSERT: &str = "
+    INSERT INTO tls_intercept_bypass (host, failure_count, expires_at, description)
+    VALUES ($1, $2, $3, $4)
+";
+
+/// 유효한 우회 목록 조회
+pub const SELECT_ACTIVE: &str = "
+    SELECT host, expires_at FROM tls_intercept_bypass
+    WHERE active = true AND (expires_at IS NULL OR expires_at > NOW())
+";
@@ -0,0 +1,27 @@
+/// 테이블 이름 (변경 알림 payload)
+pub const TABLE: &str = "upstream_client_certs";
+
+/// 테이블 생성 쿼리
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS upstream_client_certs (
+        id BIGSERIAL PRIMARY KEY,
+        domain VARCHAR(255) NOT NULL,
+        cert_pem TEXT NOT NULL,
+        key_pem TEXT NOT NULL,
+        created_by VARCHAR(100) NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        description TEXT,
+        active BOOLEAN NOT NULL DEFAULT TRUE
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 2] = [
+    "CREATE INDEX IF NOT EXISTS upstream_client_certs_domain_idx ON upstream_client_certs(domain)",
+    "CREATE INDEX IF NOT EXISTS upstream_client_certs_active_idx ON upstream_client_certs(active)",
+];
+
+/// 활성 클라이언트 인증서 조회
+pub const SELECT_ACTIVE: &str = "
+    SELECT domain, cert_pem, key_pem FROM upstream_client_certs WHERE active = true
+";
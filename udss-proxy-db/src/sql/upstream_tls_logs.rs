@@ -0,0 +1,40 @@
+/// 테이블 생성 쿼리
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS upstream_tls_logs (
+        id BIGSERIAL,
+        host TEXT NOT NULL,
+        target_ip TEXT NOT NULL,
+        protocol_version TEXT NOT NULL,
+        cipher_suite TEXT NOT NULL,
+        subject TEXT,
+        issuer TEXT,
+        san TEXT,
+        not_before TIMESTAMPTZ,
+        not_after TIMESTAMPTZ,
+        policy TEXT,
+        error TEXT,
+        timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (id, timestamp)
+    ) PARTITION BY RANGE (timestamp)";
+
+/// 기존 테이블 컬럼 추가 쿼리
+pub const ALTER_COLUMNS: [&str; 2] = [
+    "ALTER TABLE upstream_tls_logs ADD COLUMN IF NOT EXISTS policy TEXT",
+    "ALTER TABLE upstream_tls_logs ADD COLUMN IF NOT EXISTS error TEXT",
+];
+
+/// 기본 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 3] = [
+    "CREATE INDEX IF NOT EXISTS upstream_tls_logs_host_idx ON upstream_tls_logs(host)",
+    "CREATE INDEX IF NOT EXISTS upstream_tls_logs_timestamp_idx ON upstream_tls_logs(timestamp)",
+    "CREATE INDEX IF NOT EXISTS upstream_tls_logs_protocol_version_idx ON upstream_tls_logs(protocol_version)",
+];
+
+/// 핸드셰이크 로그 저장 쿼리 (실패한 핸드셰이크는 `error`에 사유)
+pub const INSERT: &str = "
+    INSERT INTO upstream_tls_logs (
+        host, target_ip, protocol_version, cipher_suite, subject, issuer, san,
+        not_before, not_after, policy, error
+    )
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+";
@@ -0,0 +1,42 @@
+/// 테이블 생성 쿼리
+///
+/// - `host`: 대상 호스트 (`*.example.com`, `.example.com` 형식 허용)
+/// - `method`: HTTP 메서드 (NULL이면 모든 메서드)
+/// - `path`, `path_match_type`: 정규화된 경로와 비교 (`exact`, `prefix`, `glob`)
+/// - `query_param`: 필수 쿼리 파라미터 (`name` 또는 `name=value`, NULL이면 검사 안함)
+pub const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS url_blocks (
+        id BIGSERIAL PRIMARY KEY,
+        host VARCHAR(255) NOT NULL,
+        method VARCHAR(16),
+        path VARCHAR(2048) NOT NULL DEFAULT '/',
+        path_match_type VARCHAR(16) NOT NULL DEFAULT 'prefix'
+            CHECK (path_match_type IN ('exact', 'prefix', 'glob')),
+        query_param VARCHAR(255),
+        case_sensitive BOOLEAN NOT NULL DEFAULT FALSE,
+        action VARCHAR(16) NOT NULL DEFAULT 'block'
+            CHECK (action IN ('block', 'allow', 'monitor', 'warn')),
+        priority INTEGER NOT NULL DEFAULT 0,
+        created_by VARCHAR(100) NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        expires_at TIMESTAMPTZ,
+        description TEXT,
+        active BOOLEAN NOT NULL DEFAULT TRUE
+    )
+";
+
+/// 인덱스 생성 쿼리
+pub const CREATE_INDICES: [&str; 2] = [
+    "CREATE INDEX IF NOT EXISTS url_blocks_host_idx ON url_blocks(host)",
+    "CREATE INDEX IF NOT EXISTS url_blocks_active_idx ON url_blocks(active)",
+];
+
+/// 기존 테이블 컬럼 추가 쿼리
+pub const ALTER_COLUMNS: [&str; 4] = [
+    "ALTER TABLE url_blocks ADD COLUMN IF NOT EXISTS action VARCHAR(16) NOT NULL DEFAULT 'block'
+        CHECK (action IN ('block', 'allow', 'monitor', 'warn'))",
+    "ALTER TABLE url_blocks ADD COLUMN IF NOT EXISTS priority INTEGER NOT NULL DEFAULT 0",
+    "ALTER TABLE url_blocks DROP CONSTRAINT IF EXISTS url_blocks_action_check,
+        ADD CONSTRAINT url_blocks_action_check CHECK (action IN ('block', 'allow', 'monitor', 'warn'))",
+    "ALTER TABLE url_blocks ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ",
+];
@@ -4,9 +4,9 @@ use std::io;
 use std::net::AddrParseError;
 use std::sync::PoisonError;
 use tokio::time::error::Elapsed;
-// use tokio_rustls::rustls;
 use deadpool_postgres::PoolError;
 use rcgen::Error as RcgenError;
+use rustls::pki_types::pem::Error as PemError;
 use serde_yml::Error as YmlError;
 use tokio_postgres::Error as PgError;
 
@@ -86,11 +86,17 @@ impl From<AddrParseError> for ProxyError {
     }
 }
 
-// impl From<rustls::Error> for ProxyError {
-//     fn from(err: rustls::Error) -> Self {
-//         ProxyError::Tls(format!("TLS 에러: {}", err))
-//     }
-// }
+impl From<rustls::Error> for ProxyError {
+    fn from(err: rustls::Error) -> Self {
+        ProxyError::Tls(format!("TLS 에러: {err}"))
+    }
+}
+
+impl From<PemError> for ProxyError {
+    fn from(err: PemError) -> Self {
+        ProxyError::Tls(format!("PEM 파싱 에러: {err}"))
+    }
+}
 
 impl From<PoolError> for ProxyError {
     fn from(err: PoolError) -> Self {
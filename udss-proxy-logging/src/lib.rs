@@ -1,3 +1,9 @@
+pub mod request_log;
+pub mod upstream_tls_log;
+
+pub use request_log::{RequestLog, RequestLogger, next_session_id};
+pub use upstream_tls_log::{UpstreamTlsLog, UpstreamTlsLogger};
+
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
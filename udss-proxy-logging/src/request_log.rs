@@ -0,0 +1,121 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::{debug, error, warn};
+use tokio::sync::mpsc;
+
+use udss_proxy_db::DatabasePool;
+use udss_proxy_db::request_logs;
+
+/// 로그 채널 버퍼 크기
+const CHANNEL_CAPACITY: usize = 10_000;
+
+/// 세션 ID 순번
+static SESSION_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// `request_logs` 테이블 한 행
+#[derive(Debug, Clone, Default)]
+pub struct RequestLog {
+    pub host: String,
+    pub method: String,
+    pub path: String,
+    pub header: String,
+    pub body: Option<String>,
+    pub session_id: String,
+    pub client_ip: String,
+    pub target_ip: String,
+    pub is_rejected: bool,
+    pub is_tls: bool,
+    pub ja3_hash: Option<String>,
+    pub ja4: Option<String>,
+    /// 판정 규칙 ID
+    pub rule_id: Option<i64>,
+    /// 판정 규칙 종류
+    pub rule_type: Option<String>,
+    /// 판정 동작
+    pub rule_action: Option<String>,
+    /// 판정 사유
+    pub rule_reason: Option<String>,
+    /// 차단 예정 모니터 규칙 ID
+    pub monitor_rule_id: Option<i64>,
+    /// 차단 예정 모니터 규칙 종류
+    pub monitor_rule_type: Option<String>,
+    /// 차단 예정 사유
+    pub monitor_reason: Option<String>,
+}
+
+/// 요청 로그 비동기 기록기
+#[derive(Clone)]
+pub struct RequestLogger {
+    sender: mpsc::Sender<RequestLog>,
+}
+
+impl RequestLogger {
+    /// 기록 태스크 시작
+    pub fn start(pool: DatabasePool) -> Self {
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        tokio::spawn(write_loop(pool, receiver));
+        Self { sender }
+    }
+
+    /// 요청 로그 기록 (채널이 가득 차면 버림)
+    pub fn log(&self, entry: RequestLog) {
+        if let Err(e) = self.sender.try_send(entry) {
+            warn!("요청 로그 채널 전송 실패: {e}");
+        }
+    }
+}
+
+/// 새 세션 ID 생성
+pub fn next_session_id() -> String {
+    let seq = SESSION_SEQ.fetch_add(1, Ordering::Relaxed);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    format!("{nanos:x}-{seq:x}")
+}
+
+/// 로그 DB 기록 루프
+async fn write_loop(pool: DatabasePool, mut receiver: mpsc::Receiver<RequestLog>) {
+    while let Some(entry) = receiver.recv().await {
+        let conn = match pool.get_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                error!("요청 로그 기록용 연결 가져오기 실패: {e}");
+                continue;
+            }
+        };
+
+        if let Err(e) = conn
+            .execute(
+                request_logs::INSERT,
+                &[
+                    &entry.host,
+                    &entry.method,
+                    &entry.path,
+                    &entry.header,
+                    &entry.body,
+                    &entry.session_id,
+                    &entry.client_ip,
+                    &entry.target_ip,
+                    &entry.is_rejected,
+                    &entry.is_tls,
+                    &entry.ja3_hash,
+                    &entry.ja4,
+                    &entry.rule_id,
+                    &entry.rule_type,
+                    &entry.rule_action,
+                    &entry.rule_reason,
+                    &entry.monitor_rule_id,
+                    &entry.monitor_rule_type,
+                    &entry.monitor_reason,
+                ],
+            )
+            .await
+        {
+            error!("요청 로그 저장 실패: {e}");
+        } else {
+            debug!("요청 로그 저장: {} {}", entry.method, entry.host);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use log::{debug, error, warn};
+use tokio::sync::mpsc;
+
+use udss_proxy_db::DatabasePool;
+use udss_proxy_db::upstream_tls_logs;
+
+/// 로그 채널 버퍼 크기
+const CHANNEL_CAPACITY: usize = 10_000;
+
+/// `upstream_tls_logs` 테이블 한 행
+#[derive(Debug, Clone, Default)]
+pub struct UpstreamTlsLog {
+    pub host: String,
+    pub target_ip: String,
+    pub protocol_version: String,
+    pub cipher_suite: String,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    pub san: Option<String>,
+    pub not_before: Option<DateTime<Utc>>,
+    pub not_after: Option<DateTime<Utc>>,
+    /// 적용한 업스트림 TLS 정책
+    pub policy: Option<String>,
+    /// 핸드셰이크 실패 사유 (성공하면 None)
+    pub error: Option<String>,
+}
+
+/// 업스트림 TLS 핸드셰이크 로그 비동기 기록기
+#[derive(Clone)]
+pub struct UpstreamTlsLogger {
+    sender: mpsc::Sender<UpstreamTlsLog>,
+}
+
+impl UpstreamTlsLogger {
+    /// 기록 태스크 시작
+    pub fn start(pool: DatabasePool) -> Self {
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        tokio::spawn(write_loop(pool, receiver));
+        Self { sender }
+    }
+
+    /// 핸드셰이크 로그 기록 (채널이 가득 차면 버림)
+    pub fn log(&self, entry: UpstreamTlsLog) {
+        if let Err(e) = self.sender.try_send(entry) {
+            warn!("업스트림 TLS 로그 채널 전송 실패: {e}");
+        }
+    }
+}
+
+/// 로그 DB 기록 루프
+async fn write_loop(pool: DatabasePool, mut receiver: mpsc::Receiver<UpstreamTlsLog>) {
+    while let Some(entry) = receiver.recv().await {
+        let conn = match pool.get_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                error!("업스트림 TLS 로그 기록용 연결 가져오기 실패: {e}");
+                continue;
+            }
+        };
+
+        if let Err(e) = conn
+            .execute(
+                upstream_tls_logs::INSERT,
+                &[
+                    &entry.host,
+                    &entry.target_ip,
+                    &entry.protocol_version,
+                    &entry.cipher_suite,
+                    &entry.subject,
+                    &entry.issuer,
+                    &entry.san,
+                    &entry.not_before,
+                    &entry.not_after,
+                    &entry.policy,
+                    &entry.error,
+                ],
+            )
+            .await
+        {
+            error!("업스트림 TLS 로그 저장 실패: {e}");
+        } else {
+            debug!(
+                "업스트림 TLS 로그 저장: {} {}",
+                entry.host, entry.protocol_version
+            );
+        }
+    }
+}
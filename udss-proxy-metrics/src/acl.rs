@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// 접근 제어 판정 지표
+#[derive(Debug, Default)]
+pub struct AclMetrics {
+    // 규칙 종류별 차단 수
+    blocked: Mutex<HashMap<String, u64>>,
+    // 모니터 규칙(종류, ID)별 차단 예정 수
+    would_block: Mutex<HashMap<(String, i64), u64>>,
+}
+
+impl AclMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 차단 기록
+    pub fn record_blocked(&self, rule_type: &str) {
+        if let Ok(mut blocked) = self.blocked.lock() {
+            *blocked.entry(rule_type.to_string()).or_default() += 1;
+        }
+    }
+
+    /// 모니터 규칙 차단 예정 기록
+    pub fn record_would_block(&self, rule_type: &str, rule_id: i64) {
+        if let Ok(mut would_block) = self.would_block.lock() {
+            *would_block
+                .entry((rule_type.to_string(), rule_id))
+                .or_default() += 1;
+        }
+    }
+
+    /// Prometheus 텍스트 형식
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP uproxy_acl_blocked_total Requests blocked by access control.\n");
+        out.push_str("# TYPE uproxy_acl_blocked_total counter\n");
+        if let Ok(blocked) = self.blocked.lock() {
+            let mut rows: Vec<_> = blocked.iter().collect();
+            rows.sort();
+            for (rule_type, count) in rows {
+                let _ = writeln!(
+                    out,
+                    "uproxy_acl_blocked_total{{rule_type=\"{rule_type}\"}} {count}"
+                );
+            }
+        }
+
+        out.push_str(
+            "# HELP uproxy_acl_would_block_total Requests allowed by monitor rules that would have been blocked.\n",
+        );
+        out.push_str("# TYPE uproxy_acl_would_block_total counter\n");
+        if let Ok(would_block) = self.would_block.lock() {
+            let mut rows: Vec<_> = would_block.iter().collect();
+            rows.sort();
+            for ((rule_type, rule_id), count) in rows {
+                let _ = writeln!(
+                    out,
+                    "uproxy_acl_would_block_total{{rule_type=\"{rule_type}\",rule_id=\"{rule_id}\"}} {count}"
+                );
+            }
+        }
+
+        out
+    }
+}
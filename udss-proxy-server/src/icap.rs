@@ -0,0 +1,956 @@
+use std::time::Duration;
+
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{CONTENT_LENGTH, HeaderMap, HeaderName, HeaderValue, TRANSFER_ENCODING};
+use hyper::http::{request, response};
+use hyper::{Method, Request, Response, StatusCode, Uri};
+use log::{debug, warn};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::OnceCell;
+use tokio::time::timeout;
+
+use udss_proxy_config::IcapConfig;
+use udss_proxy_error::{ProxyError, Result, config_err};
+
+/// ICAP 기본 포트
+const DEFAULT_PORT: u16 = 1344;
+/// 캡슐화된 HTTP 헤더 최대 크기
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+/// 캡슐화된 HTTP 바디 최대 크기
+const MAX_BODY_BYTES: usize = 64 * 1024 * 1024;
+/// 차단 사유로 쓰는 ICAP 응답 헤더 (백신, DLP 서비스 관례)
+const VIOLATION_HEADERS: [&str; 3] = ["x-infection-found", "x-violations-found", "x-virus-id"];
+
+/// 검사 실패시 동작
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FailurePolicy {
+    /// 검사 없이 통과
+    Open,
+    /// 차단
+    Closed,
+}
+
+/// ICAP 서비스 옵션 (OPTIONS 응답)
+#[derive(Debug, Clone, Copy)]
+struct ServiceOptions {
+    /// 미리보기 크기 (없으면 미리보기 없이 전체 전송)
+    preview: Option<usize>,
+}
+
+/// ICAP 서비스 (OPTIONS 응답은 처음 사용할 때 조회 후 유지)
+struct IcapService {
+    url: String,
+    /// `Host` 헤더 값
+    host: String,
+    /// 연결 주소 (`host:port`)
+    addr: String,
+    options: OnceCell<ServiceOptions>,
+}
+
+impl IcapService {
+    /// `icap://host[:port]/service` 형식 URL 해석
+    fn parse(url: &str) -> Result<Self> {
+        let invalid = || {
+            config_err(format!(
+                "잘못된 ICAP 서비스 URL: {url} (예: icap://127.0.0.1:1344/reqmod)"
+            ))
+        };
+        let rest = url.strip_prefix("icap://").ok_or_else(invalid)?;
+        let host = rest.split('/').next().unwrap_or_default();
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        let has_port = host
+            .rsplit_once(':')
+            .is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+        let addr = if has_port {
+            host.to_string()
+        } else {
+            format!("{host}:{DEFAULT_PORT}")
+        };
+
+        Ok(Self {
+            url: url.to_string(),
+            host: host.to_string(),
+            addr,
+            options: OnceCell::new(),
+        })
+    }
+
+    /// 서비스 옵션 (실패하면 다음 검사에서 다시 조회)
+    async fn options(&self) -> Result<ServiceOptions> {
+        self.options
+            .get_or_try_init(|| async {
+                let mut stream = BufReader::new(TcpStream::connect(&self.addr).await?);
+                let request = format!(
+                    "OPTIONS {} ICAP/1.0\r\nHost: {}\r\nEncapsulated: null-body=0\r\n\r\n",
+                    self.url, self.host
+                );
+                stream.get_mut().write_all(request.as_bytes()).await?;
+                let response = read_response(&mut stream).await?;
+                if response.status != 200 {
+                    return Err(ProxyError::Http(format!(
+                        "ICAP OPTIONS 실패 {}: {}",
+                        self.url, response.status
+                    )));
+                }
+                let preview = response
+                    .header("preview")
+                    .and_then(|value| value.trim().parse().ok());
+                debug!("ICAP 서비스 옵션 {}: preview={preview:?}", self.url);
+                Ok(ServiceOptions { preview })
+            })
+            .await
+            .copied()
+    }
+}
+
+/// ICAP 응답
+struct IcapResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// 캡슐화된 HTTP 헤더 (`req-hdr`, `res-hdr`)
+    sections: Vec<(String, Vec<u8>)>,
+    /// 캡슐화된 HTTP 바디
+    body: Option<Vec<u8>>,
+}
+
+impl IcapResponse {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn section(&self, name: &str) -> Option<&[u8]> {
+        self.sections
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, head)| head.as_slice())
+    }
+
+    fn body(&self) -> Bytes {
+        self.body.clone().map(Bytes::from).unwrap_or_default()
+    }
+
+    /// 서비스가 알려준 차단 사유
+    fn violation(&self) -> Option<&str> {
+        VIOLATION_HEADERS.iter().find_map(|name| self.header(name))
+    }
+}
+
+/// ICAP 검사 결과
+pub(crate) enum IcapOutcome<P> {
+    /// 변경 없음 (204, 검사 미사용, fail-open 실패)
+    Unmodified,
+    /// 서비스가 변경한 요청/응답
+    Modified(P, Bytes),
+    /// 차단 (클라이언트에 보낼 응답, 사유)
+    Blocked(Response<Full<Bytes>>, String),
+}
+
+/// ICAP(RFC 3507) 클라이언트
+pub(crate) struct IcapClient {
+    reqmod: Option<IcapService>,
+    respmod: Option<IcapService>,
+    timeout: Duration,
+    failure_policy: FailurePolicy,
+    preview_bytes: Option<usize>,
+}
+
+impl IcapClient {
+    /// 설정으로 생성
+    pub(crate) fn new(config: &IcapConfig) -> Result<Self> {
+        let failure_policy = match config.failure_policy.as_str() {
+            "open" => FailurePolicy::Open,
+            "closed" => FailurePolicy::Closed,
+            other => {
+                return Err(config_err(format!(
+                    "알 수 없는 ICAP 실패 정책: {other} (open, closed)"
+                )));
+            }
+        };
+
+        Ok(Self {
+            reqmod: config
+                .reqmod_url
+                .as_deref()
+                .map(IcapService::parse)
+                .transpose()?,
+            respmod: config
+                .respmod_url
+                .as_deref()
+                .map(IcapService::parse)
+                .transpose()?,
+            timeout: Duration::from_millis(config.timeout_ms),
+            failure_policy,
+            preview_bytes: config.preview_bytes,
+        })
+    }
+
+    /// 업스트림으로 보낼 요청 검사 (REQMOD)
+    ///
+    /// 서비스가 HTTP 응답을 돌려주면 차단으로 처리한다.
+    pub(crate) async fn reqmod(
+        &self,
+        parts: &request::Parts,
+        body: &Bytes,
+    ) -> IcapOutcome<request::Parts> {
+        let Some(service) = &self.reqmod else {
+            return IcapOutcome::Unmodified;
+        };
+
+        let sections = [(
+            "req-hdr",
+            request_head(&parts.method, &parts.uri, &parts.headers),
+        )];
+        let body = (!body.is_empty()).then_some(body.as_ref());
+        let response = match self
+            .transaction(service, "REQMOD", &sections, "req-body", body)
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => return self.failure(service, &e),
+        };
+
+        match response.status {
+            204 => IcapOutcome::Unmodified,
+            200 => {
+                if let Some(head) = response.section("res-hdr") {
+                    return match build_response(head) {
+                        Ok(parts) => IcapOutcome::Blocked(
+                            Response::from_parts(parts, Full::new(response.body())),
+                            blocked_reason("reqmod", &response),
+                        ),
+                        Err(e) => self.failure(service, &e),
+                    };
+                }
+                match response.section("req-hdr") {
+                    Some(head) => match build_request(head, parts) {
+                        Ok(modified) => IcapOutcome::Modified(modified, response.body()),
+                        Err(e) => self.failure(service, &e),
+                    },
+                    None => IcapOutcome::Unmodified,
+                }
+            }
+            status => self.failure(
+                service,
+                &ProxyError::Http(format!("ICAP 응답 상태 {status}")),
+            ),
+        }
+    }
+
+    /// 클라이언트로 보낼 응답 검사 (RESPMOD)
+    ///
+    /// 변경된 응답에 감염, 위반 헤더가 있거나 서비스가 원래와 다른 4xx, 5xx 응답으로
+    /// 바꾸면(차단 페이지) 차단으로 기록한다.
+    pub(crate) async fn respmod(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        parts: &response::Parts,
+        body: &Bytes,
+    ) -> IcapOutcome<response::Parts> {
+        let Some(service) = &self.respmod else {
+            return IcapOutcome::Unmodified;
+        };
+
+        let mut req_head = format!("{method} {uri} HTTP/1.1\r\n");
+        if let Some(host) = uri.authority() {
+            req_head.push_str(&format!("Host: {host}\r\n"));
+        }
+        req_head.push_str("\r\n");
+        let sections = [
+            ("req-hdr", req_head.into_bytes()),
+            ("res-hdr", response_head(parts.status, &parts.headers)),
+        ];
+        let body = (!body.is_empty()).then_some(body.as_ref());
+        let response = match self
+            .transaction(service, "RESPMOD", &sections, "res-body", body)
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => return self.failure(service, &e),
+        };
+
+        match response.status {
+            204 => IcapOutcome::Unmodified,
+            200 => {
+                let Some(head) = response.section("res-hdr") else {
+                    return IcapOutcome::Unmodified;
+                };
+                match build_response(head) {
+                    Ok(modified)
+                        if response.violation().is_some()
+                            || is_block_page(parts.status, modified.status) =>
+                    {
+                        IcapOutcome::Blocked(
+                            Response::from_parts(modified, Full::new(response.body())),
+                            blocked_reason("respmod", &response),
+                        )
+                    }
+                    Ok(modified) => IcapOutcome::Modified(modified, response.body()),
+                    Err(e) => self.failure(service, &e),
+                }
+            }
+            status => self.failure(
+                service,
+                &ProxyError::Http(format!("ICAP 응답 상태 {status}")),
+            ),
+        }
+    }
+
+    /// 검사 실패 처리 (실패 정책에 따라 통과 또는 차단)
+    fn failure<P>(&self, service: &IcapService, e: &ProxyError) -> IcapOutcome<P> {
+        match self.failure_policy {
+            FailurePolicy::Open => {
+                warn!("ICAP 검사 실패, 검사 없이 통과 {}: {e}", service.url);
+                IcapOutcome::Unmodified
+            }
+            FailurePolicy::Closed => {
+                warn!("ICAP 검사 실패, 차단 {}: {e}", service.url);
+                let response = Response::builder()
+                    .status(StatusCode::SERVICE_UNAVAILABLE)
+                    .header("Content-Type", "text/plain")
+                    .body(Full::new(Bytes::from("Content inspection is unavailable")))
+                    .unwrap();
+                IcapOutcome::Blocked(response, format!("icap service unavailable: {e}"))
+            }
+        }
+    }
+
+    /// 제한 시간 안에 ICAP 요청 한 건 처리
+    async fn transaction(
+        &self,
+        service: &IcapService,
+        method: &str,
+        sections: &[(&str, Vec<u8>)],
+        body_name: &str,
+        body: Option<&[u8]>,
+    ) -> Result<IcapResponse> {
+        timeout(
+            self.timeout,
+            self.exchange(service, method, sections, body_name, body),
+        )
+        .await?
+    }
+
+    /// ICAP 요청 송신, 응답 수신 (미리보기 후 100 Continue면 나머지 바디 전송)
+    async fn exchange(
+        &self,
+        service: &IcapService,
+        method: &str,
+        sections: &[(&str, Vec<u8>)],
+        body_name: &str,
+        body: Option<&[u8]>,
+    ) -> Result<IcapResponse> {
+        let preview = match (body, self.preview_bytes) {
+            (None, _) => None,
+            (Some(_), Some(preview)) => Some(preview),
+            (Some(_), None) => service.options().await?.preview,
+        };
+
+        let mut offset = 0;
+        let mut encapsulated = Vec::with_capacity(sections.len() + 1);
+        for (name, head) in sections {
+            encapsulated.push(format!("{name}={offset}"));
+            offset += head.len();
+        }
+        let body_name = if body.is_some() {
+            body_name
+        } else {
+            "null-body"
+        };
+        encapsulated.push(format!("{body_name}={offset}"));
+
+        let mut message = format!(
+            "{method} {} ICAP/1.0\r\nHost: {}\r\nAllow: 204\r\n",
+            service.url, service.host
+        );
+        if let Some(preview) = preview {
+            message.push_str(&format!("Preview: {preview}\r\n"));
+        }
+        message.push_str(&format!(
+            "Encapsulated: {}\r\n\r\n",
+            encapsulated.join(", ")
+        ));
+        let mut message = message.into_bytes();
+        for (_, head) in sections {
+            message.extend_from_slice(head);
+        }
+
+        let mut stream = BufReader::new(TcpStream::connect(&service.addr).await?);
+        let Some(body) = body else {
+            stream.get_mut().write_all(&message).await?;
+            return read_response(&mut stream).await;
+        };
+        let Some(preview) = preview else {
+            push_chunk(&mut message, body);
+            message.extend_from_slice(b"0\r\n\r\n");
+            stream.get_mut().write_all(&message).await?;
+            return read_response(&mut stream).await;
+        };
+
+        // 미리보기 (바디가 미리보기 안에 모두 들어가면 ieof)
+        let (head, rest) = body.split_at(preview.min(body.len()));
+        push_chunk(&mut message, head);
+        if rest.is_empty() {
+            message.extend_from_slice(b"0; ieof\r\n\r\n");
+        } else {
+            message.extend_from_slice(b"0\r\n\r\n");
+        }
+        stream.get_mut().write_all(&message).await?;
+        let response = read_response(&mut stream).await?;
+        if response.status != 100 {
+            return Ok(response);
+        }
+
+        let mut message = Vec::with_capacity(rest.len() + 32);
+        push_chunk(&mut message, rest);
+        message.extend_from_slice(b"0\r\n\r\n");
+        stream.get_mut().write_all(&message).await?;
+        read_response(&mut stream).await
+    }
+}
+
+/// 차단 사유 (서비스가 알려준 위반 내용 우선)
+fn blocked_reason(mode: &str, response: &IcapResponse) -> String {
+    match response.violation() {
+        Some(violation) => format!("icap {mode}: {violation}"),
+        None => format!("icap {mode}: blocked by service"),
+    }
+}
+
+/// 서비스가 원래 응답을 오류 응답으로 바꿨는지 (원래 응답이 같은 상태면 변경으로 봄)
+fn is_block_page(original: StatusCode, modified: StatusCode) -> bool {
+    (modified.is_client_error() || modified.is_server_error()) && modified != original
+}
+
+/// 청크 인코딩으로 추가 (빈 데이터는 생략)
+fn push_chunk(message: &mut Vec<u8>, data: &[u8]) {
+    if data.is_empty() {
+        return;
+    }
+    message.extend_from_slice(format!("{:x}\r\n", data.len()).as_bytes());
+    message.extend_from_slice(data);
+    message.extend_from_slice(b"\r\n");
+}
+
+/// 캡슐화할 HTTP 요청 헤더
+fn request_head(method: &Method, uri: &Uri, headers: &HeaderMap) -> Vec<u8> {
+    let mut head = format!("{method} {uri} HTTP/1.1\r\n").into_bytes();
+    push_headers(&mut head, headers);
+    head
+}
+
+/// 캡슐화할 HTTP 응답 헤더
+fn response_head(status: StatusCode, headers: &HeaderMap) -> Vec<u8> {
+    let reason = status.canonical_reason().unwrap_or_default();
+    let mut head = format!("HTTP/1.1 {} {reason}\r\n", status.as_u16()).into_bytes();
+    push_headers(&mut head, headers);
+    head
+}
+
+fn push_headers(head: &mut Vec<u8>, headers: &HeaderMap) {
+    for (name, value) in headers {
+        head.extend_from_slice(name.as_str().as_bytes());
+        head.extend_from_slice(b": ");
+        head.extend_from_slice(value.as_bytes());
+        head.extend_from_slice(b"\r\n");
+    }
+    head.extend_from_slice(b"\r\n");
+}
+
+/// 서비스가 돌려준 HTTP 요청 헤더로 요청 생성 (버전은 원래 요청 유지)
+fn build_request(head: &[u8], original: &request::Parts) -> Result<request::Parts> {
+    let (start_line, headers) = parse_http_head(head)?;
+    let mut start = start_line.split_whitespace();
+    let (Some(method), Some(uri)) = (start.next(), start.next()) else {
+        return Err(ProxyError::Http(format!(
+            "잘못된 ICAP 요청 시작줄: {start_line}"
+        )));
+    };
+
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .version(original.version);
+    if let Some(map) = builder.headers_mut() {
+        *map = headers;
+    }
+    let (parts, ()) = builder
+        .body(())
+        .map_err(|e| ProxyError::Http(format!("ICAP 변경 요청 생성 실패: {e}")))?
+        .into_parts();
+    Ok(parts)
+}
+
+/// 서비스가 돌려준 HTTP 응답 헤더로 응답 생성
+fn build_response(head: &[u8]) -> Result<response::Parts> {
+    let (start_line, headers) = parse_http_head(head)?;
+    let status = start_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .and_then(|status| StatusCode::from_u16(status).ok())
+        .ok_or_else(|| ProxyError::Http(format!("잘못된 ICAP 응답 시작줄: {start_line}")))?;
+
+    let (mut parts, ()) = Response::new(()).into_parts();
+    parts.status = status;
+    parts.headers = headers;
+    Ok(parts)
+}
+
+/// HTTP 시작줄, 헤더 해석 (길이 헤더는 바디에 맞춰 다시 계산되도록 제거)
+fn parse_http_head(head: &[u8]) -> Result<(String, HeaderMap)> {
+    let head = String::from_utf8_lossy(head);
+    let mut lines = head.split("\r\n");
+    let start_line = lines.next().unwrap_or_default().to_string();
+
+    let mut headers = HeaderMap::new();
+    for line in lines.take_while(|line| !line.is_empty()) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.trim().as_bytes()),
+            HeaderValue::from_str(value.trim()),
+        ) else {
+            debug!("ICAP 캡슐화 헤더 무시: {line}");
+            continue;
+        };
+        headers.append(name, value);
+    }
+    headers.remove(CONTENT_LENGTH);
+    headers.remove(TRANSFER_ENCODING);
+    Ok((start_line, headers))
+}
+
+/// ICAP 응답 읽기 (상태줄, 헤더, 캡슐화된 HTTP 헤더와 청크 바디)
+async fn read_response(stream: &mut BufReader<TcpStream>) -> Result<IcapResponse> {
+    let status_line = read_line(stream).await?;
+    let mut status_parts = status_line.split_whitespace();
+    let status = match (status_parts.next(), status_parts.next()) {
+        (Some(version), Some(status)) if version.starts_with("ICAP/") => status.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| ProxyError::Http(format!("잘못된 ICAP 상태줄: {status_line}")))?;
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(stream).await?;
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut response = IcapResponse {
+        status,
+        headers,
+        sections: Vec::new(),
+        body: None,
+    };
+    if status != 200 {
+        return Ok(response);
+    }
+
+    // `Encapsulated: res-hdr=0, res-body=123` (마지막 항목이 바디)
+    let entries = response
+        .header("encapsulated")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|entry| {
+            let (name, offset) = entry.trim().split_once('=')?;
+            Some((name.to_string(), offset.trim().parse::<usize>().ok()?))
+        })
+        .collect::<Vec<_>>();
+    let Some(((body_name, body_offset), sections)) = entries.split_last() else {
+        return Ok(response);
+    };
+    if *body_offset > MAX_HEAD_BYTES {
+        return Err(ProxyError::Http(format!(
+            "ICAP 캡슐화 헤더가 너무 큼: {body_offset} 바이트"
+        )));
+    }
+
+    let mut head = vec![0; *body_offset];
+    stream.read_exact(&mut head).await?;
+    for (index, (name, offset)) in sections.iter().enumerate() {
+        let end = sections
+            .get(index + 1)
+            .map_or(*body_offset, |(_, next)| *next);
+        let section = head.get(*offset..end).ok_or_else(|| {
+            ProxyError::Http(format!("잘못된 ICAP Encapsulated 오프셋: {name}={offset}"))
+        })?;
+        response.sections.push((name.clone(), section.to_vec()));
+    }
+    if body_name.ends_with("-body") && body_name != "null-body" {
+        response.body = Some(read_chunked(stream).await?);
+    }
+    Ok(response)
+}
+
+/// 청크 바디 읽기 (트레일러는 버림, 전체 크기는 `MAX_BODY_BYTES`까지)
+async fn read_chunked(stream: &mut BufReader<TcpStream>) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let line = read_line(stream).await?;
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|e| ProxyError::Http(format!("잘못된 ICAP 청크 크기 {size}: {e}")))?;
+        if size == 0 {
+            while !read_line(stream).await?.is_empty() {}
+            return Ok(body);
+        }
+        let start = body.len();
+        let end = start
+            .checked_add(size)
+            .filter(|end| *end <= MAX_BODY_BYTES)
+            .ok_or_else(|| {
+                ProxyError::Http(format!(
+                    "ICAP 캡슐화 바디가 너무 큼: 청크 {size} 바이트 (최대 {MAX_BODY_BYTES})"
+                ))
+            })?;
+        body.resize(end, 0);
+        stream.read_exact(&mut body[start..]).await?;
+        read_line(stream).await?;
+    }
+}
+
+/// 한 줄 읽기 (줄바꿈 제외, 연결이 끊기면 오류)
+async fn read_line(stream: &mut BufReader<TcpStream>) -> Result<String> {
+    let mut line = String::new();
+    if stream.read_line(&mut line).await? == 0 {
+        return Err(ProxyError::Http("ICAP 연결이 중간에 끊김".to_string()));
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// 스텁이 받은 ICAP 요청
+    #[derive(Debug, Clone, Default)]
+    struct StubRequest {
+        method: String,
+        headers: Vec<String>,
+        /// 미리보기(첫 전송) 바디
+        preview: Vec<u8>,
+        /// 100 Continue 이후까지 합친 바디
+        body: Vec<u8>,
+    }
+
+    impl StubRequest {
+        fn has_header(&self, header: &str) -> bool {
+            self.headers.iter().any(|line| line == header)
+        }
+    }
+
+    /// 스텁 응답
+    enum Reply {
+        Send(Vec<u8>),
+        /// 100 Continue 후 나머지 바디를 받고 응답
+        ContinueThen(Vec<u8>),
+        /// 응답하지 않음
+        Stall,
+    }
+
+    type Handler = fn(&StubRequest) -> Reply;
+
+    /// 스텁 서비스 시작 (서비스 URL, 받은 요청 목록)
+    async fn start_stub(handler: Handler) -> (String, Arc<Mutex<Vec<StubRequest>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let log = log.clone();
+                tokio::spawn(async move {
+                    let _ = serve(BufReader::new(stream), handler, log).await;
+                });
+            }
+        });
+        (format!("icap://{addr}/service"), received)
+    }
+
+    async fn serve(
+        mut stream: BufReader<TcpStream>,
+        handler: Handler,
+        log: Arc<Mutex<Vec<StubRequest>>>,
+    ) -> Result<()> {
+        let request_line = read_line(&mut stream).await?;
+        let mut request = StubRequest {
+            method: request_line
+                .split(' ')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            ..StubRequest::default()
+        };
+        loop {
+            let line = read_line(&mut stream).await?;
+            if line.is_empty() {
+                break;
+            }
+            request.headers.push(line);
+        }
+
+        let encapsulated = request
+            .headers
+            .iter()
+            .find_map(|line| line.strip_prefix("Encapsulated: "))
+            .unwrap_or("null-body=0")
+            .to_string();
+        let (body_name, offset) = encapsulated
+            .rsplit(", ")
+            .next()
+            .unwrap()
+            .split_once('=')
+            .unwrap();
+        let mut head = vec![0; offset.parse().unwrap()];
+        stream.read_exact(&mut head).await?;
+        if body_name != "null-body" {
+            request.preview = read_chunked(&mut stream).await?;
+            request.body = request.preview.clone();
+        }
+
+        match handler(&request) {
+            Reply::Send(reply) => stream.get_mut().write_all(&reply).await?,
+            Reply::ContinueThen(reply) => {
+                stream
+                    .get_mut()
+                    .write_all(b"ICAP/1.0 100 Continue\r\n\r\n")
+                    .await?;
+                request.body.extend(read_chunked(&mut stream).await?);
+                stream.get_mut().write_all(&reply).await?;
+            }
+            Reply::Stall => tokio::time::sleep(Duration::from_secs(30)).await,
+        }
+        log.lock().unwrap().push(request);
+        Ok(())
+    }
+
+    fn client(url: &str, failure_policy: &str, preview_bytes: Option<usize>) -> IcapClient {
+        IcapClient::new(&IcapConfig {
+            reqmod_url: Some(format!("{url}/reqmod")),
+            respmod_url: Some(format!("{url}/respmod")),
+            timeout_ms: 300,
+            failure_policy: failure_policy.to_string(),
+            preview_bytes,
+        })
+        .unwrap()
+    }
+
+    fn request_parts(method: &str) -> request::Parts {
+        Request::builder()
+            .method(method)
+            .uri("http://example.com/upload")
+            .header("host", "example.com")
+            .body(())
+            .unwrap()
+            .into_parts()
+            .0
+    }
+
+    fn response_parts(status: u16) -> response::Parts {
+        Response::builder()
+            .status(status)
+            .header("content-type", "text/plain")
+            .body(())
+            .unwrap()
+            .into_parts()
+            .0
+    }
+
+    /// 캡슐화된 HTTP 응답을 담은 ICAP 200 응답
+    fn icap_response(extra_headers: &str, res_hdr: &str, body: &str) -> Vec<u8> {
+        format!(
+            "ICAP/1.0 200 OK\r\n{extra_headers}Encapsulated: res-hdr=0, res-body={}\r\n\r\n{res_hdr}{:x}\r\n{body}\r\n0\r\n\r\n",
+            res_hdr.len(),
+            body.len()
+        )
+        .into_bytes()
+    }
+
+    const NO_CONTENT: &[u8] = b"ICAP/1.0 204 No Content\r\nEncapsulated: null-body=0\r\n\r\n";
+    const BLOCK_PAGE: &str = "HTTP/1.1 403 Forbidden\r\nContent-Type: text/plain\r\n\r\n";
+
+    #[tokio::test]
+    async fn options_preview_then_continue() {
+        let (url, received) = start_stub(|request| match request.method.as_str() {
+            "OPTIONS" => Reply::Send(
+                b"ICAP/1.0 200 OK\r\nPreview: 4\r\nEncapsulated: null-body=0\r\n\r\n".to_vec(),
+            ),
+            _ => Reply::ContinueThen(NO_CONTENT.to_vec()),
+        })
+        .await;
+        let client = client(&url, "closed", None);
+
+        let outcome = client
+            .reqmod(&request_parts("POST"), &Bytes::from("abcdefghij"))
+            .await;
+        assert!(matches!(outcome, IcapOutcome::Unmodified));
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].method, "OPTIONS");
+        let reqmod = &received[1];
+        assert_eq!(reqmod.method, "REQMOD");
+        assert!(reqmod.has_header("Preview: 4"));
+        assert!(reqmod.has_header("Allow: 204"));
+        assert_eq!(reqmod.preview, b"abcd");
+        assert_eq!(reqmod.body, b"abcdefghij");
+    }
+
+    #[tokio::test]
+    async fn reqmod_no_content_is_unmodified() {
+        let (url, received) = start_stub(|_| Reply::Send(NO_CONTENT.to_vec())).await;
+        let client = client(&url, "closed", Some(0));
+
+        let outcome = client.reqmod(&request_parts("GET"), &Bytes::new()).await;
+        assert!(matches!(outcome, IcapOutcome::Unmodified));
+
+        // 바디가 없으면 OPTIONS 없이 null-body로 전송
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert!(
+            received[0]
+                .headers
+                .iter()
+                .any(|line| line.starts_with("Encapsulated: req-hdr=0, null-body="))
+        );
+    }
+
+    #[tokio::test]
+    async fn reqmod_response_is_blocked() {
+        let (url, _) =
+            start_stub(|_| Reply::Send(icap_response("", BLOCK_PAGE, "blocked by icap"))).await;
+        let client = client(&url, "closed", Some(0));
+
+        let IcapOutcome::Blocked(response, reason) =
+            client.reqmod(&request_parts("GET"), &Bytes::new()).await
+        else {
+            panic!("REQMOD 응답이 차단으로 처리되지 않음");
+        };
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(reason, "icap reqmod: blocked by service");
+    }
+
+    #[tokio::test]
+    async fn respmod_infection_is_blocked() {
+        let (url, _) = start_stub(|_| {
+            Reply::Send(icap_response(
+                "X-Infection-Found: Type=0; Resolution=2; Threat=EICAR-Test;\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\n",
+                "virus found",
+            ))
+        })
+        .await;
+        let client = client(&url, "closed", Some(1024));
+
+        let IcapOutcome::Blocked(response, reason) = client
+            .respmod(
+                &Method::GET,
+                &"http://example.com/eicar.com".parse().unwrap(),
+                &response_parts(200),
+                &Bytes::from("X5O!P%@AP[4\\PZX54(P^)7CC)7}$EICAR"),
+            )
+            .await
+        else {
+            panic!("감염 응답이 차단으로 처리되지 않음");
+        };
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            reason,
+            "icap respmod: Type=0; Resolution=2; Threat=EICAR-Test;"
+        );
+    }
+
+    #[tokio::test]
+    async fn respmod_block_page_is_blocked() {
+        let (url, _) =
+            start_stub(|_| Reply::Send(icap_response("", BLOCK_PAGE, "blocked page"))).await;
+        let client = client(&url, "closed", Some(1024));
+
+        let outcome = client
+            .respmod(
+                &Method::GET,
+                &"http://example.com/".parse().unwrap(),
+                &response_parts(200),
+                &Bytes::from("hello"),
+            )
+            .await;
+        assert!(matches!(outcome, IcapOutcome::Blocked(..)));
+
+        // 원래 응답과 같은 오류 상태면 변경으로 처리
+        let outcome = client
+            .respmod(
+                &Method::GET,
+                &"http://example.com/".parse().unwrap(),
+                &response_parts(403),
+                &Bytes::from("hello"),
+            )
+            .await;
+        assert!(matches!(outcome, IcapOutcome::Modified(..)));
+    }
+
+    #[tokio::test]
+    async fn oversized_chunk_is_rejected() {
+        let (url, _) = start_stub(|_| {
+            Reply::Send(
+                format!(
+                    "ICAP/1.0 200 OK\r\nEncapsulated: res-hdr=0, res-body={}\r\n\r\n{BLOCK_PAGE}ffffffffff\r\n",
+                    BLOCK_PAGE.len()
+                )
+                .into_bytes(),
+            )
+        })
+        .await;
+        let client = client(&url, "closed", Some(0));
+
+        let IcapOutcome::Blocked(response, reason) =
+            client.reqmod(&request_parts("GET"), &Bytes::new()).await
+        else {
+            panic!("잘못된 청크 크기가 실패로 처리되지 않음");
+        };
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(reason.contains("너무 큼"), "{reason}");
+    }
+
+    #[tokio::test]
+    async fn stalled_service_follows_failure_policy() {
+        let (url, _) = start_stub(|_| Reply::Stall).await;
+        let limit = Duration::from_secs(2);
+
+        let outcome = timeout(
+            limit,
+            client(&url, "open", Some(0)).reqmod(&request_parts("GET"), &Bytes::new()),
+        )
+        .await
+        .expect("fail-open이 제한 시간 안에 끝나지 않음");
+        assert!(matches!(outcome, IcapOutcome::Unmodified));
+
+        let outcome = timeout(
+            limit,
+            client(&url, "closed", Some(0)).reqmod(&request_parts("GET"), &Bytes::new()),
+        )
+        .await
+        .expect("fail-closed가 제한 시간 안에 끝나지 않음");
+        let IcapOutcome::Blocked(response, _) = outcome else {
+            panic!("fail-closed가 차단하지 않음");
+        };
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
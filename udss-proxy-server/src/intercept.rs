@@ -0,0 +1,141 @@
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use hyper::service::service_fn;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder as AutoConnBuilder;
+use log::{debug, error};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::time::timeout;
+use tokio_rustls::TlsAcceptor;
+
+use udss_proxy_acl::decision::Action;
+use udss_proxy_tls::{Interceptor, TlsFingerprint, is_certificate_rejection};
+
+use crate::proxy_server::{HandlerContext, intercept_handler};
+
+/// 미리 읽은 데이터를 먼저 돌려주는 스트림
+pub(crate) struct Rewind<T> {
+    prefix: Vec<u8>,
+    pos: usize,
+    inner: T,
+}
+
+impl<T> Rewind<T> {
+    pub(crate) fn new(prefix: Vec<u8>, inner: T) -> Self {
+        Self {
+            prefix,
+            pos: 0,
+            inner,
+        }
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for Rewind<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if self.pos < self.prefix.len() {
+            let remaining = &self.prefix[self.pos..];
+            let len = remaining.len().min(buf.remaining());
+            buf.put_slice(&remaining[..len]);
+            self.pos += len;
+            if self.pos == self.prefix.len() {
+                self.prefix = Vec::new();
+                self.pos = 0;
+            }
+            return Poll::Ready(Ok(()));
+        }
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for Rewind<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// 가로채기 대상 터널 정보
+pub(crate) struct InterceptTarget {
+    /// CONNECT 대상 (`host:port`)
+    pub(crate) authority: String,
+    /// 인증서 발급 호스트 (SNI, 없으면 CONNECT 호스트)
+    pub(crate) server_name: String,
+    /// 클라이언트 TLS 핑거프린트
+    pub(crate) fingerprint: TlsFingerprint,
+    /// 클라이언트 주소
+    pub(crate) client_addr: SocketAddr,
+    /// 규칙에 일치하지 않는 요청의 기본 동작
+    pub(crate) default_action: Action,
+    /// 클라이언트 인증서로 확인한 사용자
+    pub(crate) user: Option<Arc<str>>,
+}
+
+/// CONNECT 터널 TLS 가로채기 후 HTTP 처리
+pub(crate) async fn run_intercept<S>(
+    client_io: S,
+    buffer: Vec<u8>,
+    target: InterceptTarget,
+    interceptor: Arc<Interceptor>,
+    context: Arc<HandlerContext>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let server_name = target.server_name.clone();
+    let server_config = match interceptor.server_config(&server_name).await {
+        Ok(config) => config,
+        Err(e) => {
+            error!("가로채기 인증서 발급 실패 {server_name}: {e}");
+            return;
+        }
+    };
+
+    // 클라이언트 TLS 핸드셰이크 (인증서 고정 클라이언트는 인증서 거부 경고로 실패)
+    let acceptor = TlsAcceptor::from(server_config);
+    let stream = Rewind::new(buffer, client_io);
+    let tls_stream = match timeout(context.connect_timeout, acceptor.accept(stream)).await {
+        Ok(Ok(tls_stream)) => tls_stream,
+        Ok(Err(e)) => {
+            debug!("가로채기 TLS 핸드셰이크 실패 {server_name}: {e}");
+            if is_certificate_rejection(&e) {
+                interceptor.pinning.record_failure(&server_name);
+            }
+            return;
+        }
+        Err(_) => {
+            debug!("가로채기 TLS 핸드셰이크 타임아웃: {server_name}");
+            return;
+        }
+    };
+    interceptor.pinning.record_success(&server_name);
+
+    let target = Arc::new(target);
+    let io = TokioIo::new(tls_stream);
+    if let Err(err) = AutoConnBuilder::new(TokioExecutor::default())
+        .serve_connection(
+            io,
+            service_fn(move |req| intercept_handler(req, target.clone(), context.clone())),
+        )
+        .await
+    {
+        debug!("가로채기 커넥션 종료 {server_name}: {err}");
+    }
+}
@@ -1,3 +1,6 @@
+mod icap;
+mod intercept;
 pub mod proxy_server;
+mod tunnel;
 
 pub use proxy_server::ProxyServer;
@@ -1,39 +1,75 @@
 use http_body_util::{BodyExt, Full};
 use hyper::body::{Bytes, Incoming};
 use hyper::service::service_fn;
-use hyper::{Method, Request, Response, StatusCode};
+use hyper::{Method, Request, Response, StatusCode, Version};
 use hyper_util::client::legacy::Client as HyperClient;
 use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::rt::{TokioExecutor, TokioIo};
 use hyper_util::server::conn::auto::Builder as AutoConnBuilder;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpListener;
 use tokio::time::Duration;
+use tokio_rustls::TlsAcceptor;
 
+use udss_proxy_acl::access_policy::{AccessPolicy, parse_action};
+use udss_proxy_acl::block_page;
+use udss_proxy_acl::content_restriction::ContentRestrictions;
+use udss_proxy_acl::decision::{Action, Decision, RuleType, Verdict};
 use udss_proxy_acl::domain_blocker::DomainBlocker;
+use udss_proxy_acl::file_type::Download;
+use udss_proxy_acl::header_rewrite::{HeaderContext, HeaderDirection, HeaderEdit, HeaderOperation};
+use udss_proxy_acl::warn::{CONTINUE_PATH, ContinueRequest, WarnAcknowledgments};
 use udss_proxy_config::setting::Settings;
 use udss_proxy_error::{ProxyError, Result};
+use udss_proxy_logging::{RequestLog, RequestLogger, UpstreamTlsLogger, next_session_id};
+use udss_proxy_metrics::acl::AclMetrics;
+use udss_proxy_tls::{
+    CaBundle, Interceptor, UpstreamConnector, UpstreamInfo, UpstreamTls, build_listener_config,
+    client_identity,
+};
+
+use crate::icap::{IcapClient, IcapOutcome};
+use crate::intercept::InterceptTarget;
+use crate::tunnel::run_tunnel;
 
 /// 프록시 서버 구조체
 pub struct ProxyServer {
     /// 서버 설정 정보
     setting: Settings,
     /// HTTP 클라이언트 연결 풀
-    client_pool: Arc<HyperClient<HttpConnector, Full<Bytes>>>,
+    client_pool: Arc<UpstreamClient>,
     /// 도메인 차단기
     domain_blocker: Arc<DomainBlocker>,
+    /// 요청 로그 기록기
+    request_logger: RequestLogger,
+    /// CONNECT 터널 TLS 가로채기 (비활성화시 None)
+    interceptor: Option<Arc<Interceptor>>,
+    /// 접근 제어 지표
+    acl_metrics: Arc<AclMetrics>,
 }
 
 impl ProxyServer {
     /// 새로운 프록시 서버 인스턴스를 생성
-    pub fn new(setting: Settings, domain_blocker: Arc<DomainBlocker>) -> Self {
+    pub fn new(
+        setting: Settings,
+        domain_blocker: Arc<DomainBlocker>,
+        request_logger: RequestLogger,
+        upstream_tls: Arc<UpstreamTls>,
+        upstream_tls_logger: UpstreamTlsLogger,
+        interceptor: Option<Arc<Interceptor>>,
+    ) -> Result<Self> {
         // HTTP 커넥터 설정
         let mut connector = HttpConnector::new();
         connector.set_keepalive(Some(Duration::from_secs(30))); // 연결 유지 시간
         connector.set_nodelay(true); // TCP_NODELAY 활성화 (지연 최소화)
         connector.set_reuse_address(true); // 주소 재사용 허용
 
+        // 업스트림 TLS 정책 적용 커넥터
+        let connector = UpstreamConnector::new(connector, upstream_tls, upstream_tls_logger);
+
         // HTTP 클라이언트 생성 (연결 풀링 설정)
         let client = Arc::new(
             HyperClient::builder(TokioExecutor::default())
@@ -42,11 +78,14 @@ impl ProxyServer {
                 .build(connector),
         );
 
-        Self {
+        Ok(Self {
             setting,
             client_pool: client,
             domain_blocker,
-        }
+            request_logger,
+            interceptor,
+            acl_metrics: Arc::new(AclMetrics::new()),
+        })
     }
 
     /// 서버실행
@@ -59,81 +98,633 @@ impl ProxyServer {
         let listener = TcpListener::bind(&addr).await?;
         info!("프록시 서버 시작: {addr}");
 
-        let client = self.client_pool.clone();
-        let domain_blocker = self.domain_blocker.clone();
+        // 배포용 CA 번들 로드
+        let ca_bundle = match CaBundle::load(&self.setting.proxy) {
+            Ok(bundle) => Some(bundle),
+            Err(e) => {
+                warn!("배포용 CA 인증서 로드 실패, CA 배포 비활성화: {e}");
+                None
+            }
+        };
+
+        let context = Arc::new(HandlerContext {
+            client: self.client_pool.clone(),
+            blocker: self.domain_blocker.clone(),
+            ca_bundle,
+            request_logger: self.request_logger.clone(),
+            interceptor: self.interceptor.clone(),
+            connect_timeout: Duration::from_millis(self.setting.proxy.timeout_ms as u64),
+            access_policy: AccessPolicy::new(&self.setting.proxy.access_policy)?,
+            acl_metrics: self.acl_metrics.clone(),
+            warn_acks: WarnAcknowledgments::new(Duration::from_secs(
+                self.setting.proxy.warn_page.ack_ttl_seconds,
+            )),
+            content_restrictions: ContentRestrictions::new(
+                &self.setting.proxy.content_restrictions,
+            )?,
+            icap: self
+                .setting
+                .proxy
+                .icap
+                .as_ref()
+                .map(IcapClient::new)
+                .transpose()?,
+        });
+
+        // HTTPS 프록시 리스너
+        if let Some(tls_config) = &self.setting.proxy.tls_listener {
+            let acceptor = TlsAcceptor::from(build_listener_config(tls_config)?);
+            let default_action = tls_config
+                .default_action
+                .as_deref()
+                .map(parse_action)
+                .transpose()?;
+            let tls_addr = format!("{}:{}", self.setting.proxy.bind_host, tls_config.bind_port);
+            let tls_listener = TcpListener::bind(&tls_addr).await?;
+            info!("HTTPS 프록시 리스너 시작: {tls_addr}");
+
+            let handshake_timeout = Duration::from_millis(self.setting.proxy.timeout_ms as u64);
+            tokio::spawn(run_tls_listener(
+                tls_listener,
+                acceptor,
+                handshake_timeout,
+                default_action,
+                context.clone(),
+            ));
+        }
 
         loop {
             let (stream, client_addr) = listener.accept().await?;
-            let client_clone = client.clone();
-            let blocker_clone = domain_blocker.clone();
+            if !is_client_allowed(&context, client_addr) {
+                continue;
+            }
+            let context_clone = context.clone();
 
             tokio::spawn(async move {
-                let io = TokioIo::new(stream);
-                if let Err(err) = AutoConnBuilder::new(TokioExecutor::default())
-                    .serve_connection(
-                        io,
-                        service_fn(move |req| {
-                            proxy_handler(req, client_clone.clone(), blocker_clone.clone())
-                        }),
-                    )
-                    .await
-                {
-                    error!("커넥션 에러: {err}");
-                } else {
-                    debug!("커넥션 종료: {client_addr}");
-                }
+                serve_connection(stream, client_addr, None, None, context_clone).await;
             });
         }
     }
 }
 
+/// 업스트림 HTTP 클라이언트
+pub(crate) type UpstreamClient = HyperClient<UpstreamConnector, Full<Bytes>>;
+
+/// 요청 핸들러 공유 상태
+pub(crate) struct HandlerContext {
+    /// HTTP 클라이언트 연결 풀
+    pub(crate) client: Arc<UpstreamClient>,
+    /// 도메인 차단기
+    pub(crate) blocker: Arc<DomainBlocker>,
+    /// 배포용 CA 번들
+    pub(crate) ca_bundle: Option<CaBundle>,
+    /// 요청 로그 기록기
+    pub(crate) request_logger: RequestLogger,
+    /// CONNECT 터널 TLS 가로채기
+    pub(crate) interceptor: Option<Arc<Interceptor>>,
+    /// 업스트림 연결 타임아웃
+    pub(crate) connect_timeout: Duration,
+    /// 규칙에 일치하지 않는 요청의 기본 동작
+    pub(crate) access_policy: AccessPolicy,
+    /// 접근 제어 지표
+    pub(crate) acl_metrics: Arc<AclMetrics>,
+    /// 경고 규칙 계속 진행 기록
+    pub(crate) warn_acks: WarnAcknowledgments,
+    /// 세이프서치, 테넌트 제한 강제
+    pub(crate) content_restrictions: ContentRestrictions,
+    /// ICAP 콘텐츠 검사 (설정이 없으면 None)
+    pub(crate) icap: Option<IcapClient>,
+}
+
+/// HTTPS 프록시 리스너 accept 루프
+async fn run_tls_listener(
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    handshake_timeout: Duration,
+    default_action: Option<Action>,
+    context: Arc<HandlerContext>,
+) {
+    loop {
+        let (stream, client_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                error!("HTTPS 리스너 accept 실패: {e}");
+                continue;
+            }
+        };
+        if !is_client_allowed(&context, client_addr) {
+            continue;
+        }
+        let acceptor = acceptor.clone();
+        let context_clone = context.clone();
+
+        tokio::spawn(async move {
+            // 클라이언트 TLS 핸드셰이크
+            let tls_stream =
+                match tokio::time::timeout(handshake_timeout, acceptor.accept(stream)).await {
+                    Ok(Ok(tls_stream)) => tls_stream,
+                    Ok(Err(e)) => {
+                        debug!("클라이언트 TLS 핸드셰이크 실패 ({client_addr}): {e}");
+                        return;
+                    }
+                    Err(_) => {
+                        debug!("클라이언트 TLS 핸드셰이크 타임아웃: {client_addr}");
+                        return;
+                    }
+                };
+
+            // 클라이언트 인증서 사용자 (헤더 변경 템플릿 `{user}`)
+            let user = client_identity(tls_stream.get_ref().1.peer_certificates()).map(Arc::from);
+            if let Some(user) = &user {
+                debug!("클라이언트 인증서 사용자: {user} ({client_addr})");
+            }
+
+            serve_connection(tls_stream, client_addr, default_action, user, context_clone).await;
+        });
+    }
+}
+
+/// 클라이언트 네트워크 규칙 확인 (차단이면 커넥션을 바로 닫음)
+fn is_client_allowed(context: &HandlerContext, client_addr: SocketAddr) -> bool {
+    let Some(decision) = context.blocker.check_client(client_addr.ip()) else {
+        return true;
+    };
+    context.blocker.record_hit(&decision);
+    if decision.is_blocked() {
+        info!("차단된 클라이언트 커넥션: {client_addr} ({decision})");
+        return false;
+    }
+    true
+}
+
+/// 클라이언트 커넥션 처리 (HTTP/1.1, h2 자동 감지)
+///
+/// `listener_action`: 리스너별 기본 동작 (없으면 전역 설정)
+/// `user`: 클라이언트 인증서로 확인한 사용자 (없으면 None)
+async fn serve_connection<S>(
+    stream: S,
+    client_addr: SocketAddr,
+    listener_action: Option<Action>,
+    user: Option<Arc<str>>,
+    context: Arc<HandlerContext>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let default_action = context
+        .access_policy
+        .default_action(listener_action, client_addr.ip());
+    let io = TokioIo::new(stream);
+    if let Err(err) = AutoConnBuilder::new(TokioExecutor::default())
+        .serve_connection_with_upgrades(
+            io,
+            service_fn(move |req| {
+                proxy_handler(
+                    req,
+                    client_addr,
+                    default_action,
+                    user.clone(),
+                    context.clone(),
+                )
+            }),
+        )
+        .await
+    {
+        error!("커넥션 에러: {err}");
+    } else {
+        debug!("커넥션 종료: {client_addr}");
+    }
+}
+
 /// 프록시 요청 핸들러
 async fn proxy_handler(
     req: Request<Incoming>,
-    client: Arc<HyperClient<HttpConnector, Full<Bytes>>>,
-    blocker: Arc<DomainBlocker>,
+    client_addr: SocketAddr,
+    default_action: Action,
+    user: Option<Arc<str>>,
+    context: Arc<HandlerContext>,
 ) -> Result<Response<Full<Bytes>>> {
     debug!("incoming: {req:?}");
 
-    // 직접 프록시 서버로 보내는 요청에 대한 기본 응답 (모든 경로 차단)
-    if req.uri().authority().is_none() { // path가 무엇이든 상관없이 authority가 없는 모든 요청 차단
+    // 직접 프록시 서버로 보내는 요청에 대한 기본 응답 (CA 배포, 지표 경로 외 모두 차단)
+    if req.uri().authority().is_none() {
         debug!("직접 요청 감지: URI={}", req.uri());
+        if let Some(bundle) = &context.ca_bundle
+            && req.method() == Method::GET
+            && let Some(response) = ca_distribution_response(bundle, req.uri().path())
+        {
+            return Ok(response);
+        }
+        // 지표는 로컬 클라이언트에만 제공
+        if req.method() == Method::GET
+            && req.uri().path() == "/metrics"
+            && client_addr.ip().to_canonical().is_loopback()
+        {
+            return Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header("Content-Type", "text/plain; version=0.0.4")
+                .body(Full::new(Bytes::from(
+                    context.acl_metrics.render_prometheus(),
+                )))
+                .unwrap());
+        }
         return Ok(Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .header("Content-Type", "text/plain")
-            .body(Full::new(Bytes::from("This is a proxy server. Direct requests are not allowed.")))
+            .body(Full::new(Bytes::from(
+                "This is a proxy server. Direct requests are not allowed.",
+            )))
             .unwrap());
     }
 
+    let mut log_entry = request_log_entry(&req, client_addr);
+
     // 요청 URI에서 호스트 정보 추출 및 차단 여부 확인
-    if let Some(host_str) = req.uri().host() {
-        if !host_str.is_empty() && blocker.is_blocked(host_str) {
-            info!("차단된 도메인 요청: {} (Host: {})", req.uri(), host_str);
-            return Ok(create_error_response(
-                StatusCode::FORBIDDEN,
-                &format!("Access to the domain '{host_str}' is blocked by policy."),
-            ));
-        }
-    } else {
-        debug!("요청 URI에 host 정보 없음: {}", req.uri());
+    if let Some(response) = blocked_request_response(
+        &req,
+        client_addr.ip(),
+        &mut log_entry,
+        default_action,
+        &context,
+    )
+    .await
+    {
+        return Ok(response);
     }
 
     // CONNECT 메서드 처리 (HTTPS 터널링)
     if Method::CONNECT == req.method() {
+        let Some(authority) = req.uri().authority().map(ToString::to_string) else {
+            return Ok(create_error_response(
+                StatusCode::BAD_REQUEST,
+                "CONNECT target required",
+            ));
+        };
+
+        tokio::spawn(async move {
+            match hyper::upgrade::on(req).await {
+                Ok(upgraded) => {
+                    run_tunnel(
+                        upgraded,
+                        authority,
+                        client_addr,
+                        default_action,
+                        user,
+                        log_entry,
+                        context,
+                    )
+                    .await;
+                }
+                Err(e) => error!("CONNECT 업그레이드 실패: {e}"),
+            }
+        });
+
         Ok(Response::builder()
             .status(StatusCode::OK)
             .body(Full::new(Bytes::new()))
             .unwrap())
     } else {
         // 일반 HTTP 요청 처리
-        handle_http_request(req, client).await
+        handle_http_request(req, client_addr.ip(), user.as_deref(), log_entry, &context).await
+    }
+}
+
+/// 가로채기한 HTTPS 요청 핸들러
+pub(crate) async fn intercept_handler(
+    req: Request<Incoming>,
+    target: Arc<InterceptTarget>,
+    context: Arc<HandlerContext>,
+) -> Result<Response<Full<Bytes>>> {
+    debug!("intercepted: {req:?}");
+
+    // HTTP/1.1 상대 URI를 https 절대 URI로 변환
+    let (mut parts, body) = req.into_parts();
+    if parts.uri.scheme().is_none() {
+        // Host 헤더가 없으면 CONNECT 대상 사용
+        if !parts.headers.contains_key(hyper::header::HOST)
+            && let Ok(value) = hyper::header::HeaderValue::from_str(&target.authority)
+        {
+            parts.headers.insert(hyper::header::HOST, value);
+        }
+        convert_relative_to_absolute_uri(&mut parts, true)?;
+    }
+    let req = Request::from_parts(parts, body);
+
+    let mut log_entry = request_log_entry(&req, target.client_addr);
+    log_entry.is_tls = true;
+    log_entry.ja3_hash = Some(target.fingerprint.ja3_hash.clone());
+    log_entry.ja4 = Some(target.fingerprint.ja4.clone());
+
+    if let Some(response) = blocked_request_response(
+        &req,
+        target.client_addr.ip(),
+        &mut log_entry,
+        target.default_action,
+        &context,
+    )
+    .await
+    {
+        return Ok(response);
+    }
+
+    handle_http_request(
+        req,
+        target.client_addr.ip(),
+        target.user.as_deref(),
+        log_entry,
+        &context,
+    )
+    .await
+}
+
+/// 접근 제어 판정을 로그에 남기고 차단이면 차단 응답, 경고면 경고 페이지 응답
+///
+/// 목적지 IP 규칙은 IP 리터럴 호스트와 DNS 조회 결과 주소에 적용하고,
+/// 일치하는 규칙이 없으면 `default_action`을 따른다.
+/// 경고 규칙의 CONNECT는 가로채기가 켜져 있으면 통과시켜 터널 안 요청에서 경고하고,
+/// 아니면 경고 페이지를 표시할 수 없으므로 차단한다.
+async fn blocked_request_response(
+    req: &Request<Incoming>,
+    client_ip: IpAddr,
+    log_entry: &mut RequestLog,
+    default_action: Action,
+    context: &HandlerContext,
+) -> Option<Response<Full<Bytes>>> {
+    let Some(host_str) = req.uri().host() else {
+        debug!("요청 URI에 host 정보 없음: {}", req.uri());
+        return None;
+    };
+    if host_str.is_empty() {
+        return None;
+    }
+
+    // 경고 페이지 계속 진행 (HTTP, 가로챈 HTTPS 요청)
+    if Method::CONNECT != req.method() && req.uri().path() == CONTINUE_PATH {
+        return Some(warn_continue_response(
+            req, host_str, client_ip, log_entry, context,
+        ));
+    }
+
+    let verdict = if Method::CONNECT == req.method() {
+        context.blocker.check_host(host_str)
+    } else {
+        let path_and_query = req
+            .uri()
+            .path_and_query()
+            .map_or("/", hyper::http::uri::PathAndQuery::as_str);
+        context
+            .blocker
+            .check_request(req.method().as_str(), host_str, path_and_query)
+    };
+    let verdict = context.blocker.check_resolved(host_str, verdict).await;
+    let Some(decision) = verdict
+        .decision
+        .clone()
+        .or_else(|| AccessPolicy::fallback(default_action))
+    else {
+        record_monitor(log_entry, &verdict, context);
+        return None;
+    };
+
+    record_decision(log_entry, &decision, context);
+    let target = match decision.rule_type {
+        RuleType::Url => format!("URL '{}'", req.uri()),
+        RuleType::Ip => format!("address of '{host_str}'"),
+        _ => format!("domain '{host_str}'"),
+    };
+    let warn_pending = is_warn_pending(&decision, client_ip, context);
+    if warn_pending && Method::CONNECT != req.method() {
+        info!("경고 페이지: {} {} ({decision})", req.method(), req.uri());
+        let message =
+            format!("Access to the {target} is restricted by policy. Continue only if needed.");
+        return Some(warn_response(
+            &req.uri().to_string(),
+            &message,
+            &decision,
+            client_ip,
+            log_entry,
+            context,
+        ));
+    }
+    let blocked = decision.is_blocked() || (warn_pending && context.interceptor.is_none());
+    if !blocked {
+        record_monitor(log_entry, &verdict, context);
+        return None;
+    }
+
+    info!("차단된 요청: {} {} ({decision})", req.method(), req.uri());
+    let message = format!("Access to the {target} is blocked by policy");
+    Some(block_response(
+        &req.uri().to_string(),
+        &message,
+        &decision,
+        log_entry,
+        context,
+    ))
+}
+
+/// 판정 규칙을 로그 항목과 규칙 적중 기록에 반영
+fn record_decision(log_entry: &mut RequestLog, decision: &Decision, context: &HandlerContext) {
+    context.blocker.record_hit(decision);
+    log_entry.rule_id = decision.rule_id;
+    log_entry.rule_type = Some(decision.rule_type.to_string());
+    log_entry.rule_action = Some(decision.action.to_string());
+    log_entry.rule_reason = Some(decision.reason.clone());
+}
+
+/// 허용된 요청의 모니터 규칙 차단 예정 기록
+fn record_monitor(log_entry: &mut RequestLog, verdict: &Verdict, context: &HandlerContext) {
+    let Some(monitor) = verdict.monitor() else {
+        return;
+    };
+    debug!(
+        "모니터 규칙 차단 예정: {}{} ({monitor})",
+        log_entry.host, log_entry.path
+    );
+    context.blocker.record_hit(monitor);
+    let rule_type = monitor.rule_type.to_string();
+    if let Some(rule_id) = monitor.rule_id {
+        context.acl_metrics.record_would_block(&rule_type, rule_id);
     }
+    log_entry.monitor_rule_id = monitor.rule_id;
+    log_entry.monitor_rule_type = Some(rule_type);
+    log_entry.monitor_reason = Some(monitor.reason.clone());
+}
+
+/// 차단 로그를 남기고 차단 안내 페이지 응답
+fn block_response(
+    url: &str,
+    message: &str,
+    decision: &Decision,
+    log_entry: &mut RequestLog,
+    context: &HandlerContext,
+) -> Response<Full<Bytes>> {
+    log_entry.is_rejected = true;
+    context
+        .acl_metrics
+        .record_blocked(&decision.rule_type.to_string());
+    context.request_logger.log(log_entry.clone());
+    policy_page_response(block_page::render(url, message, decision))
+}
+
+/// 계속 진행 기록이 없는 경고 규칙 판정인지 여부
+fn is_warn_pending(decision: &Decision, client_ip: IpAddr, context: &HandlerContext) -> bool {
+    decision.action == Action::Warn
+        && decision
+            .rule_id
+            .is_some_and(|rule_id| !context.warn_acks.is_acknowledged(client_ip, rule_id))
+}
+
+/// 경고 로그를 남기고 계속 진행 버튼이 있는 경고 페이지 응답
+fn warn_response(
+    url: &str,
+    message: &str,
+    decision: &Decision,
+    client_ip: IpAddr,
+    log_entry: &mut RequestLog,
+    context: &HandlerContext,
+) -> Response<Full<Bytes>> {
+    let rule_id = decision.rule_id.unwrap_or_default();
+    let token = context.warn_acks.token(client_ip, rule_id);
+    log_entry.is_rejected = true;
+    context.request_logger.log(log_entry.clone());
+    policy_page_response(block_page::render_warning(
+        url, message, decision, rule_id, &token,
+    ))
 }
 
-/// HTTP 요청 업스트림 포워딩
+/// 경고 페이지 계속 진행 (토큰 확인 후 원래 URL로 이동)
+fn warn_continue_response(
+    req: &Request<Incoming>,
+    host: &str,
+    client_ip: IpAddr,
+    log_entry: &mut RequestLog,
+    context: &HandlerContext,
+) -> Response<Full<Bytes>> {
+    let Some(request) = req.uri().query().and_then(ContinueRequest::parse) else {
+        return create_error_response(StatusCode::BAD_REQUEST, "Invalid continue request");
+    };
+
+    // 다른 호스트로 이동 방지
+    let location = match request.url.parse::<hyper::Uri>() {
+        Ok(url)
+            if url
+                .host()
+                .is_some_and(|url_host| url_host.eq_ignore_ascii_case(host)) =>
+        {
+            request.url
+        }
+        _ => "/".to_string(),
+    };
+
+    // 토큰이 만료됐으면 원래 URL에서 경고 페이지를 다시 표시
+    if context
+        .warn_acks
+        .acknowledge(client_ip, request.rule_id, &request.token)
+    {
+        info!(
+            "경고 규칙 계속 진행: {client_ip} rule {} ({location})",
+            request.rule_id
+        );
+        log_entry.rule_id = Some(request.rule_id);
+        log_entry.rule_action = Some(Action::Warn.to_string());
+        log_entry.rule_reason = Some("acknowledged by user".to_string());
+        context.request_logger.log(log_entry.clone());
+    } else {
+        debug!(
+            "경고 규칙 계속 진행 토큰 확인 실패: {client_ip} rule {}",
+            request.rule_id
+        );
+    }
+
+    Response::builder()
+        .status(StatusCode::SEE_OTHER)
+        .header(hyper::header::LOCATION, location)
+        .header("Cache-Control", "no-store")
+        .body(Full::new(Bytes::new()))
+        .unwrap()
+}
+
+/// 차단, 경고 안내 페이지 응답
+fn policy_page_response(html: String) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(StatusCode::FORBIDDEN)
+        .header("Content-Type", "text/html; charset=utf-8")
+        .header("Cache-Control", "no-store")
+        .body(Full::new(Bytes::from(html)))
+        .unwrap()
+}
+
+/// 다운로드 파일 형식 규칙 확인 (차단이면 차단 응답)
+fn blocked_download_response(
+    url: &hyper::Uri,
+    headers: &hyper::HeaderMap,
+    body: &[u8],
+    log_entry: &mut RequestLog,
+    context: &HandlerContext,
+) -> Option<Response<Full<Bytes>>> {
+    if !context.blocker.has_file_type_rules() {
+        return None;
+    }
+
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    let download = Download::new(
+        &log_entry.path,
+        header(hyper::header::CONTENT_TYPE),
+        header(hyper::header::CONTENT_DISPOSITION),
+        body,
+    );
+    let verdict = context.blocker.check_download(&log_entry.host, &download);
+    let Some(decision) = verdict.decision.clone().filter(Decision::is_blocked) else {
+        record_monitor(log_entry, &verdict, context);
+        return None;
+    };
+
+    info!("차단된 다운로드: {url} ({decision})");
+    record_decision(log_entry, &decision, context);
+    let message = format!("Download of '{url}' is blocked by policy");
+    Some(block_response(
+        &url.to_string(),
+        &message,
+        &decision,
+        log_entry,
+        context,
+    ))
+}
+
+/// 요청 로그 기본 항목
+fn request_log_entry(req: &Request<Incoming>, client_addr: SocketAddr) -> RequestLog {
+    let path = if Method::CONNECT == req.method() {
+        req.uri().authority().map_or("", |a| a.as_str())
+    } else {
+        req.uri()
+            .path_and_query()
+            .map_or("", hyper::http::uri::PathAndQuery::as_str)
+    };
+    let header = req
+        .headers()
+        .iter()
+        .map(|(name, value)| format!("{name}: {}", String::from_utf8_lossy(value.as_bytes())))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    RequestLog {
+        host: req.uri().host().unwrap_or_default().to_string(),
+        method: req.method().to_string(),
+        path: path.to_string(),
+        header,
+        session_id: next_session_id(),
+        client_ip: client_addr.ip().to_string(),
+        ..RequestLog::default()
+    }
+}
+
+/// HTTP 요청 업스트림 포워딩 (헤더 변경 규칙, ICAP 검사 적용)
 async fn handle_http_request(
     req: Request<Incoming>,
-    client: Arc<HyperClient<HttpConnector, Full<Bytes>>>,
+    client_ip: IpAddr,
+    user: Option<&str>,
+    mut log_entry: RequestLog,
+    context: &HandlerContext,
 ) -> Result<Response<Full<Bytes>>> {
     let (mut parts, body) = req.into_parts();
 
@@ -142,8 +733,52 @@ async fn handle_http_request(
         convert_relative_to_absolute_uri(&mut parts, false)?;
     }
 
+    // 클라이언트 인증서가 없는 커넥션에서는 `{user}` 템플릿 규칙이 적용되지 않음
+    let header_context = HeaderContext {
+        client_ip: client_ip.to_canonical(),
+        user,
+    };
+    let host = parts.uri.host().unwrap_or_default().to_string();
+
+    // 세이프서치 검색 파라미터 강제
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map_or("/", hyper::http::uri::PathAndQuery::as_str);
+    if let Some(rewritten) = context
+        .content_restrictions
+        .rewrite_query(&host, path_and_query)
+    {
+        debug!("세이프서치 쿼리 적용: {host}{rewritten}");
+        let mut uri_parts = parts.uri.clone().into_parts();
+        uri_parts.path_and_query = rewritten.parse().ok();
+        match hyper::Uri::from_parts(uri_parts) {
+            Ok(uri) => parts.uri = uri,
+            Err(e) => warn!("세이프서치 쿼리 적용 실패 {host}: {e}"),
+        }
+    }
+
+    let edits = context
+        .blocker
+        .header_edits(HeaderDirection::Request, &host, &header_context);
+    apply_header_edits(&mut parts.headers, edits);
+
+    // 공급자 제한 헤더 (헤더 변경 규칙보다 우선)
+    for (name, value) in context.content_restrictions.request_headers(&host) {
+        match hyper::header::HeaderValue::from_str(&value) {
+            Ok(value) => {
+                parts
+                    .headers
+                    .insert(hyper::header::HeaderName::from_static(name), value);
+            }
+            Err(e) => warn!("제한 헤더 값 오류 {name}: {e}"),
+        }
+    }
+    // 업스트림은 HTTP/1.1 커넥션 풀 사용 (h2 클라이언트 요청 포함)
+    parts.version = Version::HTTP_11;
+
     // 요청 바디를 Full<Bytes>로 변환
-    let body_bytes = match body.collect().await {
+    let mut body_bytes = match body.collect().await {
         Ok(collected) => collected.to_bytes(),
         Err(e) => {
             error!("요청 바디 읽기 실패: {e}");
@@ -154,19 +789,49 @@ async fn handle_http_request(
         }
     };
 
+    // ICAP 요청 검사 (REQMOD)
+    if let Some(icap) = &context.icap {
+        match icap.reqmod(&parts, &body_bytes).await {
+            IcapOutcome::Unmodified => {}
+            IcapOutcome::Modified(modified, body) => {
+                debug!("ICAP 요청 변경: {} -> {}", parts.uri, modified.uri);
+                parts = modified;
+                body_bytes = body;
+            }
+            IcapOutcome::Blocked(response, reason) => {
+                return Ok(icap_blocked_response(
+                    &parts.uri, response, reason, log_entry, context,
+                ));
+            }
+        }
+    }
+
+    let method = parts.method.clone();
     let outgoing_req = Request::from_parts(parts, Full::new(body_bytes));
-    debug!("서버로 요청 포워딩: {}", outgoing_req.uri());
+    let uri = outgoing_req.uri().clone();
+    debug!("서버로 요청 포워딩: {uri}");
 
     // 업스트림으로 요청 전송
-    match client.request(outgoing_req).await {
+    let result = context.client.request(outgoing_req).await;
+    if let Ok(response) = &result
+        && let Some(addr) = response
+            .extensions()
+            .get::<UpstreamInfo>()
+            .and_then(|info| info.remote_addr)
+    {
+        log_entry.target_ip = addr.ip().to_string();
+    }
+
+    match result {
         Ok(response) => {
             debug!("응답코드: {}", response.status());
 
-            let (parts, body) = response.into_parts();
-            let body_bytes = match body.collect().await {
+            let (mut parts, body) = response.into_parts();
+            let mut body_bytes = match body.collect().await {
                 Ok(collected) => collected.to_bytes(),
                 Err(e) => {
                     error!("응답 바디 읽기 실패: {e}");
+                    context.request_logger.log(log_entry);
                     return Ok(create_error_response(
                         StatusCode::BAD_GATEWAY,
                         "Failed to read response body",
@@ -174,10 +839,47 @@ async fn handle_http_request(
                 }
             };
 
+            // 다운로드 파일 형식 확인 (확장자, Content-Type, 매직 바이트)
+            if let Some(response) = blocked_download_response(
+                &uri,
+                &parts.headers,
+                &body_bytes,
+                &mut log_entry,
+                context,
+            ) {
+                return Ok(response);
+            }
+
+            // ICAP 응답 검사 (RESPMOD)
+            if let Some(icap) = &context.icap {
+                match icap.respmod(&method, &uri, &parts, &body_bytes).await {
+                    IcapOutcome::Unmodified => {}
+                    IcapOutcome::Modified(modified, body) => {
+                        debug!("ICAP 응답 변경: {uri}");
+                        parts = modified;
+                        body_bytes = body;
+                    }
+                    IcapOutcome::Blocked(response, reason) => {
+                        return Ok(icap_blocked_response(
+                            &uri, response, reason, log_entry, context,
+                        ));
+                    }
+                }
+            }
+            context.request_logger.log(log_entry);
+
+            // 응답 헤더 변경
+            let edits =
+                context
+                    .blocker
+                    .header_edits(HeaderDirection::Response, &host, &header_context);
+            apply_header_edits(&mut parts.headers, edits);
+
             Ok(Response::from_parts(parts, Full::new(body_bytes)))
         }
         Err(e) => {
             error!("요청 포워딩 실패: {e}");
+            context.request_logger.log(log_entry);
             Ok(create_error_response(
                 StatusCode::BAD_GATEWAY,
                 "Failed to connect to upstream",
@@ -186,6 +888,61 @@ async fn handle_http_request(
     }
 }
 
+/// ICAP 서비스 차단 응답 기록
+fn icap_blocked_response(
+    url: &hyper::Uri,
+    response: Response<Full<Bytes>>,
+    reason: String,
+    mut log_entry: RequestLog,
+    context: &HandlerContext,
+) -> Response<Full<Bytes>> {
+    info!("ICAP 차단: {url} ({reason})");
+    log_entry.is_rejected = true;
+    log_entry.rule_type = Some("icap".to_string());
+    log_entry.rule_action = Some("block".to_string());
+    log_entry.rule_reason = Some(reason);
+    context.request_logger.log(log_entry);
+    response
+}
+
+/// 헤더 변경 적용 (값이 헤더에 쓸 수 없는 문자를 포함하면 건너뜀)
+fn apply_header_edits(headers: &mut hyper::HeaderMap, edits: Vec<HeaderEdit>) {
+    for edit in edits {
+        let Ok(name) = hyper::header::HeaderName::from_bytes(edit.name.as_bytes()) else {
+            warn!("잘못된 헤더 이름 '{}': 규칙 {}", edit.name, edit.rule_id);
+            continue;
+        };
+        let value = match edit
+            .value
+            .as_deref()
+            .map(hyper::header::HeaderValue::from_str)
+        {
+            Some(Ok(value)) => Some(value),
+            Some(Err(e)) => {
+                warn!("잘못된 헤더 값 '{}': 규칙 {} ({e})", name, edit.rule_id);
+                continue;
+            }
+            None => None,
+        };
+        debug!(
+            "헤더 변경: {} {name} (규칙 {})",
+            edit.operation, edit.rule_id
+        );
+        match (edit.operation, value) {
+            (HeaderOperation::Set, Some(value)) => {
+                headers.insert(name, value);
+            }
+            (HeaderOperation::Add, Some(value)) => {
+                headers.append(name, value);
+            }
+            (HeaderOperation::Remove, _) => {
+                headers.remove(name);
+            }
+            _ => {}
+        }
+    }
+}
+
 /// 상대 URI 절대 URI로 변환
 fn convert_relative_to_absolute_uri(
     parts: &mut hyper::http::request::Parts,
@@ -227,6 +984,51 @@ fn convert_relative_to_absolute_uri(
     }
 }
 
+/// CA 배포 경로 응답
+fn ca_distribution_response(bundle: &CaBundle, path: &str) -> Option<Response<Full<Bytes>>> {
+    let (content_type, filename, body) = match path {
+        "/" | "/index.html" => (
+            "text/html; charset=utf-8",
+            None,
+            Bytes::from(bundle.landing_page()),
+        ),
+        "/ca.crt" => (
+            "application/x-x509-ca-cert",
+            Some(CaBundle::download_name("crt")),
+            Bytes::copy_from_slice(bundle.der()),
+        ),
+        "/ca.pem" => (
+            "application/x-pem-file",
+            Some(CaBundle::download_name("pem")),
+            Bytes::from(bundle.pem()),
+        ),
+        "/ca.mobileconfig" => (
+            "application/x-apple-aspen-config",
+            Some(CaBundle::download_name("mobileconfig")),
+            Bytes::from(bundle.mobileconfig()),
+        ),
+        "/policies.json" => (
+            "application/json",
+            None,
+            Bytes::from(bundle.firefox_policies()),
+        ),
+        _ => return None,
+    };
+
+    debug!("CA 배포 요청: {path}");
+    let mut builder = Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", content_type);
+    if let Some(filename) = filename {
+        builder = builder.header(
+            "Content-Disposition",
+            format!("attachment; filename=\"{filename}\""),
+        );
+    }
+
+    Some(builder.body(Full::new(body)).unwrap())
+}
+
 /// 에러응답
 fn create_error_response(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
     Response::builder()
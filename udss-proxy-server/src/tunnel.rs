@@ -0,0 +1,169 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use hyper::upgrade::Upgraded;
+use hyper_util::rt::TokioIo;
+use log::{debug, error, info};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, copy_bidirectional};
+use tokio::net::TcpStream;
+use tokio::time::{Duration, timeout};
+
+use udss_proxy_acl::decision::Action;
+use udss_proxy_logging::RequestLog;
+use udss_proxy_tls::{ClientHello, ClientHelloParse, parse_client_hello};
+
+use crate::intercept::{InterceptTarget, run_intercept};
+use crate::proxy_server::HandlerContext;
+
+/// `ClientHello` 최대 수신 크기
+const MAX_CLIENT_HELLO_SIZE: usize = 64 * 1024;
+/// `ClientHello` 대기 시간 (서버가 먼저 말하는 프로토콜 대비)
+const CLIENT_HELLO_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// CONNECT 터널 처리
+pub(crate) async fn run_tunnel(
+    upgraded: Upgraded,
+    authority: String,
+    client_addr: SocketAddr,
+    default_action: Action,
+    user: Option<Arc<str>>,
+    mut log_entry: RequestLog,
+    context: Arc<HandlerContext>,
+) {
+    let mut client_io = TokioIo::new(upgraded);
+
+    // 클라이언트 첫 데이터에서 TLS 핑거프린트 추출
+    let mut buffer = Vec::with_capacity(4096);
+    if let Some(hello) = read_client_hello(&mut client_io, &mut buffer).await {
+        let fingerprint = hello.fingerprint();
+        debug!(
+            "TLS 핑거프린트 {authority} (SNI: {:?}): JA3={} JA4={}",
+            hello.server_name, fingerprint.ja3_hash, fingerprint.ja4
+        );
+
+        log_entry.is_tls = true;
+        let blocked = context
+            .blocker
+            .is_fingerprint_blocked(&fingerprint.ja3_hash, &fingerprint.ja4);
+        log_entry.ja3_hash = Some(fingerprint.ja3_hash.clone());
+        log_entry.ja4 = Some(fingerprint.ja4.clone());
+
+        if blocked {
+            info!("차단된 TLS 핑거프린트 터널: {authority}");
+            log_entry.is_rejected = true;
+            context.request_logger.log(log_entry);
+            return;
+        }
+
+        // TLS 가로채기 (인증서 고정 우회 호스트 제외)
+        if let Some(interceptor) = &context.interceptor {
+            let server_name = hello.server_name.clone().unwrap_or_else(|| {
+                log_entry
+                    .host
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .to_string()
+            });
+            if interceptor.pinning.is_bypassed(&server_name) {
+                debug!("인증서 고정 우회 호스트, 가로채기 없이 터널링: {server_name}");
+            } else {
+                context.request_logger.log(log_entry);
+                let target = InterceptTarget {
+                    authority,
+                    server_name,
+                    fingerprint,
+                    client_addr,
+                    default_action,
+                    user,
+                };
+                run_intercept(
+                    client_io,
+                    buffer,
+                    target,
+                    interceptor.clone(),
+                    context.clone(),
+                )
+                .await;
+                return;
+            }
+        }
+    }
+
+    // 경고 페이지는 가로챈 요청에만 표시할 수 있어 계속 진행 기록이 없으면 차단
+    if log_entry.rule_action == Some(Action::Warn.to_string())
+        && log_entry
+            .rule_id
+            .is_some_and(|rule_id| !context.warn_acks.is_acknowledged(client_addr.ip(), rule_id))
+    {
+        info!("경고 규칙 터널 차단 (가로채기 없음): {authority}");
+        log_entry.is_rejected = true;
+        context.request_logger.log(log_entry);
+        return;
+    }
+
+    // 업스트림 연결 (세이프서치 대상이면 제한 전용 엔드포인트로 연결, SNI는 그대로)
+    let target = match context
+        .content_restrictions
+        .restricted_endpoint(&log_entry.host)
+    {
+        Some(endpoint) => {
+            let port = authority.rsplit_once(':').map_or("443", |(_, port)| port);
+            debug!("제한 엔드포인트로 터널 연결: {authority} -> {endpoint}:{port}");
+            format!("{endpoint}:{port}")
+        }
+        None => authority.clone(),
+    };
+    let mut upstream = match timeout(context.connect_timeout, TcpStream::connect(&target)).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(e)) => {
+            error!("터널 업스트림 연결 실패 {authority}: {e}");
+            context.request_logger.log(log_entry);
+            return;
+        }
+        Err(_) => {
+            error!("터널 업스트림 연결 타임아웃: {authority}");
+            context.request_logger.log(log_entry);
+            return;
+        }
+    };
+    if let Ok(peer) = upstream.peer_addr() {
+        log_entry.target_ip = peer.ip().to_string();
+    }
+    context.request_logger.log(log_entry);
+
+    // 미리 읽은 데이터 전달 후 양방향 중계
+    if !buffer.is_empty()
+        && let Err(e) = upstream.write_all(&buffer).await
+    {
+        error!("터널 초기 데이터 전송 실패 {authority}: {e}");
+        return;
+    }
+
+    match copy_bidirectional(&mut client_io, &mut upstream).await {
+        Ok((from_client, from_server)) => {
+            debug!("터널 종료 {authority}: 송신 {from_client} bytes, 수신 {from_server} bytes");
+        }
+        Err(e) => debug!("터널 중계 종료 {authority}: {e}"),
+    }
+}
+
+/// 클라이언트 `ClientHello` 수신 (읽은 데이터는 `buffer`에 보존)
+async fn read_client_hello<R>(reader: &mut R, buffer: &mut Vec<u8>) -> Option<ClientHello>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut chunk = [0u8; 4096];
+    loop {
+        match parse_client_hello(buffer) {
+            ClientHelloParse::Complete(hello) => return Some(hello),
+            ClientHelloParse::NotTls => return None,
+            ClientHelloParse::Incomplete if buffer.len() >= MAX_CLIENT_HELLO_SIZE => return None,
+            ClientHelloParse::Incomplete => {}
+        }
+
+        match timeout(CLIENT_HELLO_TIMEOUT, reader.read(&mut chunk)).await {
+            Ok(Ok(0)) | Ok(Err(_)) | Err(_) => return None,
+            Ok(Ok(n)) => buffer.extend_from_slice(&chunk[..n]),
+        }
+    }
+}
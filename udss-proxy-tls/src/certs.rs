@@ -3,19 +3,40 @@ use std::path::Path;
 
 use log::{debug, info, warn};
 use rcgen::{
-    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType, IsCa, KeyPair,
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType, GeneralSubtree,
+    IsCa, KeyPair, NameConstraints, PKCS_ECDSA_P256_SHA256, PKCS_ECDSA_P384_SHA384, PKCS_ED25519,
+    PKCS_RSA_SHA256, RsaKeySize,
 };
+use time::{Duration, OffsetDateTime};
 use tokio::sync::Mutex;
 use udss_proxy_config::Config;
 use udss_proxy_error::{Result, config_err};
 
 // 루트 CA 인증서 및 키 저장 (전역 변수)
-pub static ROOT_CA: std::sync::LazyLock<Mutex<Option<Certificate>>> = std::sync::LazyLock::new(|| Mutex::new(None));
+pub static ROOT_CA: std::sync::LazyLock<Mutex<Option<RootCa>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+/// 루트 CA 인증서와 서명 키
+pub struct RootCa {
+    pub cert: Certificate,
+    pub key_pair: KeyPair,
+}
 
 // 인증서 파일 경로 상수
 const CA_CERT_PEM_FILE: &str = "ca_cert.pem";
 const CA_KEY_PEM_FILE: &str = "ca_key.pem";
-const CA_CERT_CRT_FILE: &str = "ca_cert.crt";
+pub(crate) const CA_CERT_CRT_FILE: &str = "ca_cert.crt";
+
+/// 지원 키 알고리즘
+const KEY_ALGORITHMS: [&str; 5] = [
+    "ecdsa-p256",
+    "ecdsa-p384",
+    "ed25519",
+    "rsa-2048",
+    "rsa-4096",
+];
+/// 발급 인증서 시작 시각 (클라이언트 시계 오차 허용, 일)
+const BACKDATE_DAYS: i64 = 1;
 
 /// 인증서 디렉토리 확인 및 생성
 pub fn ensure_ssl_directories(config: &Config) -> Result<()> {
@@ -63,10 +84,9 @@ pub fn load_trusted_certificates(config: &mut Config) -> Result<()> {
             && path
                 .extension()
                 .is_some_and(|ext| ext == "pem" || ext == "crt")
+            && let Some(path_str) = path.to_str()
         {
-            if let Some(path_str) = path.to_str() {
-                println!("Valid certificate file: {path_str}");
-            }
+            println!("Valid certificate file: {path_str}");
         }
     }
 
@@ -90,19 +110,19 @@ pub async fn init_root_ca(config: &Config) -> Result<()> {
 
     if crt_exists && key_exists && pem_exists {
         info!("기존 CA 인증서 로드");
-        let _cert_pem = fs::read_to_string(&ca_cert_pem_path)?;
+        let cert_crt = fs::read_to_string(&ca_cert_crt_path)?;
         let key_pem = fs::read_to_string(&ca_key_pem_path)?;
 
         // PEM에서 인증서와 키 로드
         let key_pair = KeyPair::from_pem(&key_pem)?;
 
-        // 인증서 파라미터 생성
-        let params = CertificateParams::new(vec!["UDSS Proxy Root CA".to_string()])?;
+        // 기존 인증서 파라미터 복원 (발급자 DN 유지)
+        let params = CertificateParams::from_ca_cert_pem(&cert_crt)?;
 
         // 인증서 생성
         let cert = params.self_signed(&key_pair)?;
 
-        *ca_guard = Some(cert);
+        *ca_guard = Some(RootCa { cert, key_pair });
     } else if crt_exists && key_exists {
         // .crt와 .key 파일이 모두 존재하는 경우 .pem 파일 생성
         info!(".crt와 .key 파일에서 .pem 파일 생성");
@@ -116,13 +136,13 @@ pub async fn init_root_ca(config: &Config) -> Result<()> {
         // 키페어와 인증서 로드
         let key_pair = KeyPair::from_pem(&key_pem)?;
 
-        // 인증서 파라미터 생성
-        let params = CertificateParams::new(vec!["UDSS Proxy Root CA".to_string()])?;
+        // 기존 인증서 파라미터 복원 (발급자 DN 유지)
+        let params = CertificateParams::from_ca_cert_pem(&cert_crt)?;
 
         // 인증서 생성
         let cert = params.self_signed(&key_pair)?;
 
-        *ca_guard = Some(cert);
+        *ca_guard = Some(RootCa { cert, key_pair });
     } else if pem_exists {
         // .pem 파일만 존재하는 경우 .crt와 .key 파일로 분리
         info!(".pem 파일에서 .crt와 .key 파일 생성");
@@ -158,50 +178,144 @@ pub async fn init_root_ca(config: &Config) -> Result<()> {
         // 키페어와 인증서 로드
         let key_pair = KeyPair::from_pem(&key_part)?;
 
-        // 인증서 파라미터 생성
-        let params = CertificateParams::new(vec!["UDSS Proxy Root CA".to_string()])?;
+        // 기존 인증서 파라미터 복원 (발급자 DN 유지)
+        let params = CertificateParams::from_ca_cert_pem(&cert_part)?;
 
         // 인증서 생성
         let cert = params.self_signed(&key_pair)?;
 
-        *ca_guard = Some(cert);
+        *ca_guard = Some(RootCa { cert, key_pair });
     } else {
         info!("새 CA 인증서 생성");
+        let root_ca = create_root_ca_files(config)?;
 
-        // 인증서 파라미터 설정
-        let mut params = CertificateParams::default();
-        let mut distinguished_name = DistinguishedName::new();
-        distinguished_name.push(DnType::CommonName, "UDSS Proxy Root CA");
-        distinguished_name.push(DnType::OrganizationName, "CoremaxTech");
-        distinguished_name.push(DnType::CountryName, "KR");
-        params.distinguished_name = distinguished_name;
-
-        // 인증서 속성 설정 - IsCa enum 사용
-        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
-        params.key_usages = vec![
-            rcgen::KeyUsagePurpose::KeyCertSign,
-            rcgen::KeyUsagePurpose::CrlSign,
-            rcgen::KeyUsagePurpose::DigitalSignature,
-        ];
-
-        // 키페어 생성 및 인증서 생성
-        let key_pair = KeyPair::generate()?;
-        let cert = params.self_signed(&key_pair)?;
+        *ca_guard = Some(root_ca);
+        drop(ca_guard);
+    }
 
-        // PEM 형식으로 저장
-        let cert_pem = cert.pem();
-        let key_pem = key_pair.serialize_pem();
+    Ok(())
+}
 
-        // 파일로 저장
-        fs::write(&ca_cert_pem_path, &cert_pem)?;
-        fs::write(&ca_key_pem_path, &key_pem)?;
+/// 설정(`ca`)에 따라 새 루트 CA를 생성하고 파일로 저장 (기존 파일 덮어씀)
+pub fn create_root_ca_files(config: &Config) -> Result<RootCa> {
+    let ca_config = &config.ca;
+
+    // 인증서 파라미터 설정
+    let mut params = CertificateParams::default();
+    params.distinguished_name = distinguished_name(
+        Some(&ca_config.common_name),
+        ca_config.organization.as_deref(),
+        ca_config.organizational_unit.as_deref(),
+        ca_config.country.as_deref(),
+    );
+    set_validity(&mut params, ca_config.validity_days);
+
+    // 인증서 속성 설정 - IsCa enum 사용
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.key_usages = vec![
+        rcgen::KeyUsagePurpose::KeyCertSign,
+        rcgen::KeyUsagePurpose::CrlSign,
+        rcgen::KeyUsagePurpose::DigitalSignature,
+    ];
+
+    // 이름 제약
+    if !ca_config.permitted_dns_names.is_empty() || !ca_config.excluded_dns_names.is_empty() {
+        let subtrees = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| GeneralSubtree::DnsName(name.clone()))
+                .collect()
+        };
+        params.name_constraints = Some(NameConstraints {
+            permitted_subtrees: subtrees(&ca_config.permitted_dns_names),
+            excluded_subtrees: subtrees(&ca_config.excluded_dns_names),
+        });
+    }
 
-        // Windows 인증서 스토어용 .crt 파일 생성
-        fs::write(&ca_cert_crt_path, &cert_pem)?;
+    // 키페어 생성 및 인증서 생성
+    let key_pair = generate_key_pair(&ca_config.key_algorithm)?;
+    let cert = params.self_signed(&key_pair)?;
+    info!(
+        "루트 CA 생성: {} ({}, {}일)",
+        ca_config.common_name, ca_config.key_algorithm, ca_config.validity_days
+    );
 
-        *ca_guard = Some(cert);
-        drop(ca_guard);
+    // PEM 형식으로 저장
+    let ssl_dir = &config.ssl_dir;
+    let cert_pem = cert.pem();
+    let key_pem = key_pair.serialize_pem();
+
+    // 파일로 저장
+    fs::write(format!("{ssl_dir}/{CA_CERT_PEM_FILE}"), &cert_pem)?;
+    fs::write(format!("{ssl_dir}/{CA_KEY_PEM_FILE}"), &key_pem)?;
+
+    // Windows 인증서 스토어용 .crt 파일 생성
+    fs::write(format!("{ssl_dir}/{CA_CERT_CRT_FILE}"), &cert_pem)?;
+
+    Ok(RootCa { cert, key_pair })
+}
+
+/// 루트 CA 파일 존재 여부
+pub fn root_ca_exists(config: &Config) -> bool {
+    [CA_CERT_PEM_FILE, CA_KEY_PEM_FILE, CA_CERT_CRT_FILE]
+        .iter()
+        .any(|file| Path::new(&config.ssl_dir).join(file).exists())
+}
+
+/// 키 알고리즘 설정값 확인
+pub fn validate_key_algorithm(algorithm: &str) -> Result<()> {
+    if KEY_ALGORITHMS.contains(&algorithm) {
+        Ok(())
+    } else {
+        Err(config_err(format!(
+            "지원하지 않는 키 알고리즘: {algorithm} ({} 지원)",
+            KEY_ALGORITHMS.join(", ")
+        )))
     }
+}
 
-    Ok(())
+/// 키 알고리즘 설정값으로 키페어 생성
+pub fn generate_key_pair(algorithm: &str) -> Result<KeyPair> {
+    validate_key_algorithm(algorithm)?;
+    match algorithm {
+        "ecdsa-p384" => Ok(KeyPair::generate_for(&PKCS_ECDSA_P384_SHA384)?),
+        "ed25519" => Ok(KeyPair::generate_for(&PKCS_ED25519)?),
+        "rsa-2048" => Ok(KeyPair::generate_rsa_for(
+            &PKCS_RSA_SHA256,
+            RsaKeySize::_2048,
+        )?),
+        "rsa-4096" => Ok(KeyPair::generate_rsa_for(
+            &PKCS_RSA_SHA256,
+            RsaKeySize::_4096,
+        )?),
+        _ => Ok(KeyPair::generate_for(&PKCS_ECDSA_P256_SHA256)?),
+    }
+}
+
+/// 인증서 DN 생성 (값이 없는 항목 제외)
+pub(crate) fn distinguished_name(
+    common_name: Option<&str>,
+    organization: Option<&str>,
+    organizational_unit: Option<&str>,
+    country: Option<&str>,
+) -> DistinguishedName {
+    let mut distinguished_name = DistinguishedName::new();
+    for (dn_type, value) in [
+        (DnType::CommonName, common_name),
+        (DnType::OrganizationName, organization),
+        (DnType::OrganizationalUnitName, organizational_unit),
+        (DnType::CountryName, country),
+    ] {
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+            distinguished_name.push(dn_type, value);
+        }
+    }
+    distinguished_name
+}
+
+/// 인증서 유효 기간 설정
+pub(crate) fn set_validity(params: &mut CertificateParams, validity_days: u32) {
+    let now = OffsetDateTime::now_utc();
+    params.not_before = now - Duration::days(BACKDATE_DAYS);
+    params.not_after = now + Duration::days(i64::from(validity_days));
 }
@@ -0,0 +1,196 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use log::{debug, info};
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::pem::PemObject;
+use sha2::{Digest, Sha256};
+
+use udss_proxy_config::Config;
+use udss_proxy_error::{Result, config_err};
+
+use crate::certs::CA_CERT_CRT_FILE;
+
+/// 배포용 CA 인증서 파일명
+const CA_DOWNLOAD_NAME: &str = "udss-proxy-ca";
+/// 프로파일 표시 이름
+const CA_DISPLAY_NAME: &str = "UDSS Proxy Root CA";
+
+/// 클라이언트 배포용 루트 CA 번들
+#[derive(Debug, Clone)]
+pub struct CaBundle {
+    /// DER 인코딩 인증서
+    der: Vec<u8>,
+    /// SHA-256 지문 (콜론 구분 16진수)
+    fingerprint: String,
+}
+
+impl CaBundle {
+    /// `ssl_dir`의 CA 인증서로 번들 생성
+    pub fn load(config: &Config) -> Result<Self> {
+        let path = Path::new(&config.ssl_dir).join(CA_CERT_CRT_FILE);
+        debug!("배포용 CA 인증서 로드: {}", path.display());
+
+        let cert = CertificateDer::pem_file_iter(&path)?
+            .next()
+            .ok_or_else(|| {
+                config_err(format!(
+                    "CA 인증서 파일에 인증서가 없습니다: {}",
+                    path.display()
+                ))
+            })??;
+
+        let bundle = Self::from_der(cert.as_ref().to_vec());
+        info!("배포용 CA 인증서 SHA-256: {}", bundle.fingerprint);
+        Ok(bundle)
+    }
+
+    /// DER 인증서로 번들 생성
+    pub fn from_der(der: Vec<u8>) -> Self {
+        let digest = Sha256::digest(&der);
+        let fingerprint = digest
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect::<Vec<_>>()
+            .join(":");
+
+        Self { der, fingerprint }
+    }
+
+    /// SHA-256 지문
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// DER 형식 (`/ca.crt`)
+    pub fn der(&self) -> &[u8] {
+        &self.der
+    }
+
+    /// PEM 형식 (`/ca.pem`)
+    pub fn pem(&self) -> String {
+        let encoded = BASE64.encode(&self.der);
+        let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+        for line in encoded.as_bytes().chunks(64) {
+            pem.push_str(&String::from_utf8_lossy(line));
+            pem.push('\n');
+        }
+        pem.push_str("-----END CERTIFICATE-----\n");
+        pem
+    }
+
+    /// Apple 구성 프로파일 (`/ca.mobileconfig`)
+    pub fn mobileconfig(&self) -> String {
+        let profile_uuid = self.uuid(0);
+        let payload_uuid = self.uuid(16);
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>PayloadContent</key>
+    <array>
+        <dict>
+            <key>PayloadCertificateFileName</key>
+            <string>{CA_DOWNLOAD_NAME}.crt</string>
+            <key>PayloadContent</key>
+            <data>{data}</data>
+            <key>PayloadDescription</key>
+            <string>Adds the {CA_DISPLAY_NAME} certificate</string>
+            <key>PayloadDisplayName</key>
+            <string>{CA_DISPLAY_NAME}</string>
+            <key>PayloadIdentifier</key>
+            <string>com.udss.proxy.ca.{payload_uuid}</string>
+            <key>PayloadType</key>
+            <string>com.apple.security.root</string>
+            <key>PayloadUUID</key>
+            <string>{payload_uuid}</string>
+            <key>PayloadVersion</key>
+            <integer>1</integer>
+        </dict>
+    </array>
+    <key>PayloadDescription</key>
+    <string>SHA-256 {fingerprint}</string>
+    <key>PayloadDisplayName</key>
+    <string>{CA_DISPLAY_NAME}</string>
+    <key>PayloadIdentifier</key>
+    <string>com.udss.proxy.profile.{profile_uuid}</string>
+    <key>PayloadRemovalDisallowed</key>
+    <false/>
+    <key>PayloadType</key>
+    <string>Configuration</string>
+    <key>PayloadUUID</key>
+    <string>{profile_uuid}</string>
+    <key>PayloadVersion</key>
+    <integer>1</integer>
+</dict>
+</plist>
+"#,
+            data = BASE64.encode(&self.der),
+            fingerprint = self.fingerprint,
+        )
+    }
+
+    /// Firefox 엔터프라이즈 정책 (`/policies.json`)
+    pub fn firefox_policies(&self) -> String {
+        format!(
+            r#"{{
+  "policies": {{
+    "Certificates": {{
+      "ImportEnterpriseRoots": true,
+      "Install": ["{CA_DOWNLOAD_NAME}.crt"]
+    }}
+  }}
+}}
+"#
+        )
+    }
+
+    /// 설치 안내 페이지 (`/`)
+    pub fn landing_page(&self) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="ko">
+<head>
+<meta charset="utf-8">
+<title>{CA_DISPLAY_NAME}</title>
+</head>
+<body>
+<h1>{CA_DISPLAY_NAME}</h1>
+<p>설치 전 아래 SHA-256 지문이 관리자가 안내한 값과 같은지 확인하세요.</p>
+<pre>{fingerprint}</pre>
+<ul>
+<li><a href="/ca.crt">ca.crt</a> - Windows, Android, ChromeOS (DER)</li>
+<li><a href="/ca.pem">ca.pem</a> - Linux, 기타 (PEM)</li>
+<li><a href="/ca.mobileconfig">ca.mobileconfig</a> - macOS, iOS 프로파일</li>
+<li><a href="/policies.json">policies.json</a> - Firefox 정책 (인증서를 Firefox 설치 디렉토리의 distribution 폴더에 {CA_DOWNLOAD_NAME}.crt 로 저장)</li>
+</ul>
+</body>
+</html>
+"#,
+            fingerprint = self.fingerprint,
+        )
+    }
+
+    /// 다운로드 파일명
+    pub fn download_name(extension: &str) -> String {
+        format!("{CA_DOWNLOAD_NAME}.{extension}")
+    }
+
+    /// 지문 기반 고정 UUID (프로파일 재설치시 동일 식별자 유지)
+    fn uuid(&self, offset: usize) -> String {
+        let digest = Sha256::digest(&self.der);
+        let bytes = &digest[offset..offset + 16];
+        let mut hex = String::with_capacity(36);
+        for (i, b) in bytes.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                hex.push('-');
+            }
+            let _ = write!(hex, "{b:02X}");
+        }
+        hex
+    }
+}
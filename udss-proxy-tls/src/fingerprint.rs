@@ -0,0 +1,551 @@
+use std::fmt::Write;
+
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+// TLS 레코드/핸드셰이크 타입
+const RECORD_HANDSHAKE: u8 = 0x16;
+const HANDSHAKE_CLIENT_HELLO: u8 = 0x01;
+const RECORD_HEADER_LEN: usize = 5;
+const HANDSHAKE_HEADER_LEN: usize = 4;
+
+// 확장 타입
+const EXT_SERVER_NAME: u16 = 0x0000;
+const EXT_SUPPORTED_GROUPS: u16 = 0x000a;
+const EXT_EC_POINT_FORMATS: u16 = 0x000b;
+const EXT_SIGNATURE_ALGORITHMS: u16 = 0x000d;
+const EXT_ALPN: u16 = 0x0010;
+const EXT_SUPPORTED_VERSIONS: u16 = 0x002b;
+
+/// `ClientHello` 파싱 결과
+#[derive(Debug)]
+pub enum ClientHelloParse {
+    /// 파싱 완료
+    Complete(ClientHello),
+    /// 데이터 부족 (추가 수신 필요)
+    Incomplete,
+    /// TLS `ClientHello`가 아님
+    NotTls,
+}
+
+/// 핑거프린트 계산에 필요한 `ClientHello` 필드
+#[derive(Debug, Clone, Default)]
+pub struct ClientHello {
+    pub legacy_version: u16,
+    pub cipher_suites: Vec<u16>,
+    pub extensions: Vec<u16>,
+    pub supported_groups: Vec<u16>,
+    pub ec_point_formats: Vec<u8>,
+    pub signature_algorithms: Vec<u16>,
+    pub supported_versions: Vec<u16>,
+    pub alpn_protocols: Vec<Vec<u8>>,
+    pub server_name: Option<String>,
+}
+
+/// JA3/JA4 TLS 클라이언트 핑거프린트
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsFingerprint {
+    /// JA3 원문 문자열
+    pub ja3: String,
+    /// JA3 MD5 해시
+    pub ja3_hash: String,
+    /// JA4 문자열
+    pub ja4: String,
+}
+
+/// 바이트 읽기 도우미
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.remaining() < len {
+            return None;
+        }
+        let slice = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Some(slice)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u24(&mut self) -> Option<usize> {
+        self.take(3)
+            .map(|b| (usize::from(b[0]) << 16) | (usize::from(b[1]) << 8) | usize::from(b[2]))
+    }
+
+    fn u16_list(&mut self, len: usize) -> Option<Vec<u16>> {
+        let data = self.take(len)?;
+        Some(
+            data.chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect(),
+        )
+    }
+}
+
+/// TLS 레코드에서 `ClientHello` 파싱
+pub fn parse_client_hello(buf: &[u8]) -> ClientHelloParse {
+    if buf.is_empty() {
+        return ClientHelloParse::Incomplete;
+    }
+    if buf[0] != RECORD_HANDSHAKE {
+        return ClientHelloParse::NotTls;
+    }
+
+    // 핸드셰이크 레코드 조각 결합
+    let mut handshake = Vec::new();
+    let mut pos = 0;
+    loop {
+        if buf.len() < pos + RECORD_HEADER_LEN {
+            return ClientHelloParse::Incomplete;
+        }
+        if buf[pos] != RECORD_HANDSHAKE || buf[pos + 1] != 0x03 {
+            return ClientHelloParse::NotTls;
+        }
+        let record_len = usize::from(u16::from_be_bytes([buf[pos + 3], buf[pos + 4]]));
+        let start = pos + RECORD_HEADER_LEN;
+        if buf.len() < start + record_len {
+            return ClientHelloParse::Incomplete;
+        }
+        handshake.extend_from_slice(&buf[start..start + record_len]);
+        pos = start + record_len;
+
+        if handshake.len() >= HANDSHAKE_HEADER_LEN {
+            if handshake[0] != HANDSHAKE_CLIENT_HELLO {
+                return ClientHelloParse::NotTls;
+            }
+            let body_len = (usize::from(handshake[1]) << 16)
+                | (usize::from(handshake[2]) << 8)
+                | usize::from(handshake[3]);
+            if handshake.len() >= HANDSHAKE_HEADER_LEN + body_len {
+                break;
+            }
+        }
+    }
+
+    let mut reader = Reader::new(&handshake);
+    match read_client_hello(&mut reader) {
+        Some(hello) => ClientHelloParse::Complete(hello),
+        None => ClientHelloParse::NotTls,
+    }
+}
+
+/// 핸드셰이크 메시지에서 `ClientHello` 필드 추출
+fn read_client_hello(reader: &mut Reader<'_>) -> Option<ClientHello> {
+    let _msg_type = reader.u8()?;
+    let body_len = reader.u24()?;
+    let mut body = Reader::new(reader.take(body_len)?);
+
+    let mut hello = ClientHello {
+        legacy_version: body.u16()?,
+        ..ClientHello::default()
+    };
+    body.take(32)?; // random
+    let session_id_len = usize::from(body.u8()?);
+    body.take(session_id_len)?;
+    let cipher_len = usize::from(body.u16()?);
+    hello.cipher_suites = body.u16_list(cipher_len)?;
+    let compression_len = usize::from(body.u8()?);
+    body.take(compression_len)?;
+
+    // 확장 없는 ClientHello
+    if body.remaining() < 2 {
+        return Some(hello);
+    }
+
+    let ext_total = usize::from(body.u16()?);
+    let mut exts = Reader::new(body.take(ext_total)?);
+    while exts.remaining() >= 4 {
+        let ext_type = exts.u16()?;
+        let ext_len = usize::from(exts.u16()?);
+        let mut data = Reader::new(exts.take(ext_len)?);
+        hello.extensions.push(ext_type);
+
+        match ext_type {
+            EXT_SERVER_NAME => {
+                let _list_len = data.u16()?;
+                while data.remaining() >= 3 {
+                    let name_type = data.u8()?;
+                    let name_len = usize::from(data.u16()?);
+                    let name = data.take(name_len)?;
+                    if name_type == 0 {
+                        hello.server_name = Some(String::from_utf8_lossy(name).into_owned());
+                        break;
+                    }
+                }
+            }
+            EXT_SUPPORTED_GROUPS => {
+                let len = usize::from(data.u16()?);
+                hello.supported_groups = data.u16_list(len)?;
+            }
+            EXT_EC_POINT_FORMATS => {
+                let len = usize::from(data.u8()?);
+                hello.ec_point_formats = data.take(len)?.to_vec();
+            }
+            EXT_SIGNATURE_ALGORITHMS => {
+                let len = usize::from(data.u16()?);
+                hello.signature_algorithms = data.u16_list(len)?;
+            }
+            EXT_ALPN => {
+                let len = usize::from(data.u16()?);
+                let mut list = Reader::new(data.take(len)?);
+                while list.remaining() > 0 {
+                    let proto_len = usize::from(list.u8()?);
+                    hello.alpn_protocols.push(list.take(proto_len)?.to_vec());
+                }
+            }
+            EXT_SUPPORTED_VERSIONS => {
+                let len = usize::from(data.u8()?);
+                hello.supported_versions = data.u16_list(len)?;
+            }
+            _ => {}
+        }
+    }
+
+    Some(hello)
+}
+
+/// GREASE 값 여부 (RFC 8701)
+fn is_grease(value: u16) -> bool {
+    value & 0x0f0f == 0x0a0a && value >> 8 == value & 0xff
+}
+
+/// 16진수 문자열 변환
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        let _ = write!(hex, "{b:02x}");
+    }
+    hex
+}
+
+/// JA4 해시 구간 (SHA-256 앞 12자리)
+fn ja4_hash(input: &str) -> String {
+    if input.is_empty() {
+        return "000000000000".to_string();
+    }
+    to_hex(&Sha256::digest(input.as_bytes()))[..12].to_string()
+}
+
+fn join_decimal<T: ToString>(values: impl Iterator<Item = T>) -> String {
+    values.map(|v| v.to_string()).collect::<Vec<_>>().join("-")
+}
+
+fn join_hex(values: &[u16]) -> String {
+    values
+        .iter()
+        .map(|v| format!("{v:04x}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl ClientHello {
+    /// JA3/JA4 핑거프린트 계산
+    pub fn fingerprint(&self) -> TlsFingerprint {
+        let ja3 = self.ja3();
+        let ja3_hash = to_hex(&Md5::digest(ja3.as_bytes()));
+        TlsFingerprint {
+            ja3,
+            ja3_hash,
+            ja4: self.ja4(),
+        }
+    }
+
+    /// JA3 원문 (`version,ciphers,extensions,groups,point_formats`)
+    fn ja3(&self) -> String {
+        let not_grease = |v: &&u16| !is_grease(**v);
+        format!(
+            "{},{},{},{},{}",
+            self.legacy_version,
+            join_decimal(self.cipher_suites.iter().filter(not_grease)),
+            join_decimal(self.extensions.iter().filter(not_grease)),
+            join_decimal(self.supported_groups.iter().filter(not_grease)),
+            join_decimal(self.ec_point_formats.iter()),
+        )
+    }
+
+    /// JA4 (`t{ver}{sni}{ciphers}{exts}{alpn}_{cipher_hash}_{ext_hash}`)
+    fn ja4(&self) -> String {
+        let version = self
+            .supported_versions
+            .iter()
+            .copied()
+            .filter(|v| !is_grease(*v))
+            .max()
+            .unwrap_or(self.legacy_version);
+        let version = match version {
+            0x0304 => "13",
+            0x0303 => "12",
+            0x0302 => "11",
+            0x0301 => "10",
+            0x0300 => "s3",
+            0x0002 => "s2",
+            0xfeff => "d1",
+            0xfefd => "d2",
+            0xfefc => "d3",
+            _ => "00",
+        };
+        let sni = if self.server_name.is_some() { 'd' } else { 'i' };
+
+        let mut ciphers: Vec<u16> = self
+            .cipher_suites
+            .iter()
+            .copied()
+            .filter(|v| !is_grease(*v))
+            .collect();
+        let extensions: Vec<u16> = self
+            .extensions
+            .iter()
+            .copied()
+            .filter(|v| !is_grease(*v))
+            .collect();
+
+        let alpn = match self.alpn_protocols.first() {
+            Some(proto) if !proto.is_empty() => {
+                let first = proto[0];
+                let last = proto[proto.len() - 1];
+                if first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric() {
+                    format!("{}{}", first as char, last as char)
+                } else {
+                    let hex = to_hex(proto);
+                    let mut chars = hex.chars();
+                    format!(
+                        "{}{}",
+                        chars.next().unwrap_or('0'),
+                        chars.next_back().unwrap_or('0')
+                    )
+                }
+            }
+            _ => "00".to_string(),
+        };
+
+        let part_a = format!(
+            "t{version}{sni}{:02}{:02}{alpn}",
+            ciphers.len().min(99),
+            extensions.len().min(99)
+        );
+
+        ciphers.sort_unstable();
+        let part_b = ja4_hash(&join_hex(&ciphers));
+
+        let mut sorted_exts: Vec<u16> = extensions
+            .into_iter()
+            .filter(|v| *v != EXT_SERVER_NAME && *v != EXT_ALPN)
+            .collect();
+        sorted_exts.sort_unstable();
+        let part_c = if sorted_exts.is_empty() {
+            ja4_hash("")
+        } else if self.signature_algorithms.is_empty() {
+            ja4_hash(&join_hex(&sorted_exts))
+        } else {
+            ja4_hash(&format!(
+                "{}_{}",
+                join_hex(&sorted_exts),
+                join_hex(&self.signature_algorithms)
+            ))
+        };
+
+        format!("{part_a}_{part_b}_{part_c}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GREASE: [u16; 4] = [0x0a0a, 0x1a1a, 0x2a2a, 0x3a3a];
+
+    fn u16_bytes(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+
+    fn with_u16_len(data: &[u8]) -> Vec<u8> {
+        let mut out = u16::try_from(data.len()).unwrap().to_be_bytes().to_vec();
+        out.extend_from_slice(data);
+        out
+    }
+
+    fn with_u8_len(data: &[u8]) -> Vec<u8> {
+        let mut out = vec![u8::try_from(data.len()).unwrap()];
+        out.extend_from_slice(data);
+        out
+    }
+
+    fn sni(name: &str) -> (u16, Vec<u8>) {
+        let mut entry = vec![0];
+        entry.extend(with_u16_len(name.as_bytes()));
+        (EXT_SERVER_NAME, with_u16_len(&entry))
+    }
+
+    fn alpn(protocols: &[&[u8]]) -> (u16, Vec<u8>) {
+        let list: Vec<u8> = protocols.iter().flat_map(|p| with_u8_len(p)).collect();
+        (EXT_ALPN, with_u16_len(&list))
+    }
+
+    /// TLS 레코드에 담은 `ClientHello` 바이트
+    fn client_hello(version: u16, ciphers: &[u16], extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut body = version.to_be_bytes().to_vec();
+        body.extend([0x11; 32]); // random
+        body.extend(with_u8_len(&[0x22; 32])); // session id
+        body.extend(with_u16_len(&u16_bytes(ciphers)));
+        body.extend(with_u8_len(&[0])); // compression
+        let exts: Vec<u8> = extensions
+            .iter()
+            .flat_map(|(ext_type, data)| {
+                let mut ext = ext_type.to_be_bytes().to_vec();
+                ext.extend(with_u16_len(data));
+                ext
+            })
+            .collect();
+        body.extend(with_u16_len(&exts));
+
+        let mut handshake = vec![HANDSHAKE_CLIENT_HELLO];
+        handshake.extend(&u32::try_from(body.len()).unwrap().to_be_bytes()[1..]);
+        handshake.extend(body);
+
+        let mut record = vec![RECORD_HANDSHAKE, 0x03, 0x01];
+        record.extend(with_u16_len(&handshake));
+        record
+    }
+
+    fn parse(bytes: &[u8]) -> ClientHello {
+        match parse_client_hello(bytes) {
+            ClientHelloParse::Complete(hello) => hello,
+            other => panic!("ClientHello 파싱 실패: {other:?}"),
+        }
+    }
+
+    /// JA4 문서 예시의 Chrome `ClientHello` (GREASE 포함, 원래 순서)
+    fn chrome_hello() -> Vec<u8> {
+        let ciphers = [
+            GREASE[0], 0x1301, 0x1302, 0x1303, 0xc02b, 0xc02f, 0xc02c, 0xc030, 0xcca9, 0xcca8,
+            0xc013, 0xc014, 0x009c, 0x009d, 0x002f, 0x0035,
+        ];
+        let extensions = [
+            (GREASE[1], Vec::new()),
+            sni("example.com"),
+            (0x0017, Vec::new()),
+            (0xff01, vec![0]),
+            (
+                EXT_SUPPORTED_GROUPS,
+                with_u16_len(&u16_bytes(&[GREASE[2], 0x001d, 0x0017, 0x0018])),
+            ),
+            (EXT_EC_POINT_FORMATS, with_u8_len(&[0])),
+            (0x0023, Vec::new()),
+            alpn(&[b"h2", b"http/1.1"]),
+            (0x0005, vec![1, 0, 0, 0, 0]),
+            (
+                EXT_SIGNATURE_ALGORITHMS,
+                with_u16_len(&u16_bytes(&[
+                    0x0403, 0x0804, 0x0401, 0x0503, 0x0805, 0x0501, 0x0806, 0x0601,
+                ])),
+            ),
+            (0x0012, Vec::new()),
+            (0x0033, Vec::new()),
+            (0x002d, with_u8_len(&[1])),
+            (
+                EXT_SUPPORTED_VERSIONS,
+                with_u8_len(&u16_bytes(&[GREASE[3], 0x0304, 0x0303])),
+            ),
+            (0x001b, Vec::new()),
+            (0x4469, Vec::new()),
+            (GREASE[0], vec![0]),
+            (0x0015, vec![0; 16]),
+        ];
+        client_hello(0x0303, &ciphers, &extensions)
+    }
+
+    #[test]
+    fn ja3_matches_published_example() {
+        // JA3 문서 예시: 769,47-53-5-10-49161-49162-49171-49172-50-56-19-4,0-10-11,23-24-25,0
+        let bytes = client_hello(
+            0x0301,
+            &[47, 53, 5, 10, 49161, 49162, 49171, 49172, 50, 56, 19, 4],
+            &[
+                sni("example.com"),
+                (
+                    EXT_SUPPORTED_GROUPS,
+                    with_u16_len(&u16_bytes(&[23, 24, 25])),
+                ),
+                (EXT_EC_POINT_FORMATS, with_u8_len(&[0])),
+            ],
+        );
+        let fingerprint = parse(&bytes).fingerprint();
+        assert_eq!(
+            fingerprint.ja3,
+            "769,47-53-5-10-49161-49162-49171-49172-50-56-19-4,0-10-11,23-24-25,0"
+        );
+        assert_eq!(fingerprint.ja3_hash, "ada70206e40642a3e4461f35503241d5");
+    }
+
+    #[test]
+    fn ja4_matches_published_example() {
+        let hello = parse(&chrome_hello());
+        assert_eq!(hello.server_name.as_deref(), Some("example.com"));
+        assert_eq!(
+            hello.fingerprint().ja4,
+            "t13d1516h2_8daaf6152771_e5627efa2ab1"
+        );
+    }
+
+    #[test]
+    fn grease_is_removed_and_order_kept_in_ja3() {
+        let fingerprint = parse(&chrome_hello()).fingerprint();
+        assert_eq!(
+            fingerprint.ja3,
+            "771,4865-4866-4867-49195-49199-49196-49200-52393-52392-49171-49172-156-157-47-53,\
+             0-23-65281-10-11-35-16-5-13-18-51-45-43-27-17513-21,29-23-24,0"
+        );
+        assert!(GREASE.iter().all(|v| is_grease(*v)));
+        assert!(!is_grease(0x0a0b));
+    }
+
+    #[test]
+    fn ja4_alpn_edge_cases() {
+        let ja4_prefix = |protocols: Vec<Vec<u8>>| {
+            let hello = ClientHello {
+                legacy_version: 0x0303,
+                cipher_suites: vec![0x1301],
+                alpn_protocols: protocols,
+                ..ClientHello::default()
+            };
+            hello.fingerprint().ja4[..10].to_string()
+        };
+
+        assert_eq!(ja4_prefix(Vec::new()), "t12i010000");
+        assert_eq!(ja4_prefix(vec![b"http/1.1".to_vec()]), "t12i0100h1");
+        // 영숫자가 아닌 첫/끝 글자는 16진수 표현의 첫/끝 글자
+        assert_eq!(ja4_prefix(vec![vec![0xab, 0xcd]]), "t12i0100ad");
+        assert_eq!(ja4_prefix(vec![Vec::new()]), "t12i010000");
+    }
+
+    #[test]
+    fn partial_and_non_tls_input() {
+        let bytes = chrome_hello();
+        assert!(matches!(
+            parse_client_hello(&bytes[..bytes.len() - 1]),
+            ClientHelloParse::Incomplete
+        ));
+        assert!(matches!(
+            parse_client_hello(b"GET / HTTP/1.1\r\n"),
+            ClientHelloParse::NotTls
+        ));
+    }
+}
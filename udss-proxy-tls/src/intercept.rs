@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use log::{debug, error, info};
+use lru::LruCache;
+use rcgen::{CertificateParams, ExtendedKeyUsagePurpose, KeyPair, KeyUsagePurpose};
+use rustls::ServerConfig;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use tokio::sync::OnceCell;
+
+use udss_proxy_config::{Config, LeafCertConfig};
+use udss_proxy_db::DatabasePool;
+use udss_proxy_error::{Result, tls_err};
+
+use crate::certs::{
+    ROOT_CA, RootCa, distinguished_name, generate_key_pair, set_validity, validate_key_algorithm,
+};
+use crate::pinning::PinningTracker;
+
+/// CONNECT 터널 TLS 가로채기
+pub struct Interceptor {
+    // 호스트별 발급 인증서 캐시
+    configs: Mutex<LruCache<String, Arc<ServerConfig>>>,
+    // 발급 중인 호스트 (동시 요청은 같은 발급 결과를 기다림)
+    pending: Mutex<HashMap<String, Arc<OnceCell<Arc<ServerConfig>>>>>,
+    // 발급 설정
+    leaf: LeafCertConfig,
+    // 발급 인증서 공용 키 (시작시 한 번 생성, 요청마다 키 생성하지 않음)
+    leaf_key: KeyPair,
+    /// 인증서 고정 목적지 추적기
+    pub pinning: Arc<PinningTracker>,
+}
+
+impl Interceptor {
+    /// 새 가로채기 인스턴스 생성 (발급 인증서 키 생성)
+    pub fn new(config: &Config, pool: DatabasePool) -> Result<Self> {
+        validate_key_algorithm(&config.leaf_cert.key_algorithm)?;
+        let leaf_key = generate_key_pair(&config.leaf_cert.key_algorithm)?;
+        info!(
+            "가로채기 인증서 키 생성: {}",
+            config.leaf_cert.key_algorithm
+        );
+        let capacity = NonZeroUsize::new(config.cache_size).unwrap_or(NonZeroUsize::MIN);
+        Ok(Self {
+            configs: Mutex::new(LruCache::new(capacity)),
+            pending: Mutex::new(HashMap::new()),
+            leaf: config.leaf_cert.clone(),
+            leaf_key,
+            pinning: Arc::new(PinningTracker::new(&config.pinning_bypass, pool)),
+        })
+    }
+
+    /// 호스트용 서버 TLS 설정 (캐시에 없으면 루트 CA로 발급)
+    pub async fn server_config(&self, host: &str) -> Result<Arc<ServerConfig>> {
+        let host = host.to_lowercase();
+        if let Ok(mut guard) = self.configs.lock()
+            && let Some(config) = guard.get(&host)
+        {
+            return Ok(config.clone());
+        }
+
+        // 같은 호스트 발급은 한 번만 수행
+        let pending = match self.pending.lock() {
+            Ok(mut guard) => guard.entry(host.clone()).or_default().clone(),
+            Err(e) => {
+                error!("pending Mutex 잠금 실패 (server_config): {e}");
+                Arc::new(OnceCell::new())
+            }
+        };
+        let result = pending
+            .get_or_try_init(|| self.issue_config(&host))
+            .await
+            .cloned();
+
+        if let Ok(mut guard) = self.pending.lock()
+            && guard
+                .get(&host)
+                .is_some_and(|cell| Arc::ptr_eq(cell, &pending))
+        {
+            guard.remove(&host);
+        }
+        result
+    }
+
+    /// 호스트 인증서 발급 후 캐시에 저장
+    async fn issue_config(&self, host: &str) -> Result<Arc<ServerConfig>> {
+        let (chain, key) = {
+            let ca_guard = ROOT_CA.lock().await;
+            let ca = ca_guard
+                .as_ref()
+                .ok_or_else(|| tls_err("루트 CA가 초기화되지 않았습니다"))?;
+            issue_leaf(ca, &self.leaf, host, &self.leaf_key)?
+        };
+        debug!("가로채기 인증서 발급: {host}");
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let mut config = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_single_cert(chain, key)?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        let config = Arc::new(config);
+
+        match self.configs.lock() {
+            Ok(mut guard) => {
+                guard.put(host.to_string(), config.clone());
+            }
+            Err(e) => error!("configs Mutex 잠금 실패 (server_config): {e}"),
+        }
+        Ok(config)
+    }
+}
+
+/// 루트 CA로 호스트 인증서 발급
+fn issue_leaf(
+    ca: &RootCa,
+    leaf: &LeafCertConfig,
+    host: &str,
+    key_pair: &KeyPair,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
+    let mut params = CertificateParams::new(vec![host.to_string()])?;
+    params.distinguished_name = distinguished_name(
+        Some(host),
+        leaf.organization.as_deref(),
+        leaf.organizational_unit.as_deref(),
+        leaf.country.as_deref(),
+    );
+    set_validity(&mut params, leaf.validity_days);
+    params.key_usages = vec![
+        KeyUsagePurpose::DigitalSignature,
+        KeyUsagePurpose::KeyEncipherment,
+    ];
+    params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+
+    let cert = params.signed_by(key_pair, &ca.cert, &ca.key_pair)?;
+
+    let chain = vec![cert.der().clone(), ca.cert.der().clone()];
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_pair.serialize_der()));
+    Ok((chain, key))
+}
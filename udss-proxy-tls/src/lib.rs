@@ -1,3 +1,15 @@
 pub mod certs;
+pub mod distribution;
+pub mod fingerprint;
+pub mod intercept;
+pub mod listener;
+pub mod pinning;
+pub mod upstream;
 
 pub use certs::{ensure_ssl_directories, init_root_ca, load_trusted_certificates};
+pub use distribution::CaBundle;
+pub use fingerprint::{ClientHello, ClientHelloParse, TlsFingerprint, parse_client_hello};
+pub use intercept::Interceptor;
+pub use listener::{build_listener_config, client_identity};
+pub use pinning::{PinningTracker, is_certificate_rejection};
+pub use upstream::{UpstreamConnector, UpstreamInfo, UpstreamTls};
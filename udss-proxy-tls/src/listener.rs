@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use log::{debug, info};
+use rustls::RootCertStore;
+use rustls::ServerConfig;
+use rustls::crypto::ring::default_provider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use x509_parser::extensions::GeneralName;
+use x509_parser::parse_x509_certificate;
+
+use udss_proxy_config::TlsListenerConfig;
+use udss_proxy_error::{Result, config_err, tls_err};
+
+/// HTTPS 프록시 리스너용 TLS 서버 설정 생성
+pub fn build_listener_config(config: &TlsListenerConfig) -> Result<Arc<ServerConfig>> {
+    debug!("HTTPS 리스너 TLS 설정 생성: {}", config.cert_file);
+
+    // 서버 인증서 체인 및 키 로드
+    let cert_chain = CertificateDer::pem_file_iter(&config.cert_file)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if cert_chain.is_empty() {
+        return Err(config_err(format!(
+            "서버 인증서 파일에 인증서가 없습니다: {}",
+            config.cert_file
+        )));
+    }
+    let key = PrivateKeyDer::from_pem_file(&config.key_file)?;
+
+    let builder = ServerConfig::builder_with_provider(Arc::new(default_provider()))
+        .with_safe_default_protocol_versions()?;
+
+    // 클라이언트 인증서 검증 설정
+    let builder = match &config.client_ca_file {
+        Some(ca_file) => {
+            let mut roots = RootCertStore::empty();
+            for cert in CertificateDer::pem_file_iter(ca_file)? {
+                roots.add(cert?)?;
+            }
+
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots));
+            let verifier = if config.require_client_cert {
+                verifier.build()
+            } else {
+                verifier.allow_unauthenticated().build()
+            }
+            .map_err(|e| tls_err(format!("클라이언트 인증서 검증기 생성 실패: {e}")))?;
+
+            info!(
+                "HTTPS 리스너 클라이언트 인증서 검증 활성화 (필수: {})",
+                config.require_client_cert
+            );
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => {
+            if config.require_client_cert {
+                return Err(config_err(
+                    "require_client_cert 사용시 client_ca_file 설정이 필요합니다.",
+                ));
+            }
+            builder.with_no_client_auth()
+        }
+    };
+
+    let mut server_config = builder.with_single_cert(cert_chain, key)?;
+
+    // ALPN: h2 우선, http/1.1 폴백
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(Arc::new(server_config))
+}
+
+/// 검증된 클라이언트 인증서의 사용자 이름 (주체 CN, 없으면 첫 이메일 SAN)
+///
+/// 클라이언트 인증서 검증을 켠 HTTPS 리스너에서만 인증서가 있으므로
+/// 그 외 커넥션은 항상 None이다.
+pub fn client_identity(certs: Option<&[CertificateDer<'_>]>) -> Option<String> {
+    let cert = certs?.first()?;
+    let (_, parsed) = parse_x509_certificate(cert.as_ref()).ok()?;
+    let common_name = parsed
+        .subject()
+        .iter_common_name()
+        .find_map(|cn| cn.as_str().ok())
+        .map(str::trim)
+        .filter(|cn| !cn.is_empty());
+    if let Some(common_name) = common_name {
+        return Some(common_name.to_string());
+    }
+
+    let san = parsed.subject_alternative_name().ok()??;
+    san.value.general_names.iter().find_map(|name| match name {
+        GeneralName::RFC822Name(email) => Some((*email).to_string()),
+        _ => None,
+    })
+}
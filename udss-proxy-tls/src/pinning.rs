@@ -0,0 +1,242 @@
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use log::{debug, error, info, warn};
+use lru::LruCache;
+use rustls::AlertDescription;
+
+use udss_proxy_config::PinningBypassConfig;
+use udss_proxy_db::DatabasePool;
+use udss_proxy_db::tls_intercept_bypass;
+use udss_proxy_error::Result;
+
+/// 실패 이력을 유지하는 최대 호스트 수 (넘으면 가장 오래전에 실패한 호스트부터 제거)
+const MAX_TRACKED_HOSTS: NonZeroUsize = NonZeroUsize::new(10_000).unwrap();
+
+/// 인증서 고정 목적지 추적기
+///
+/// 가로채기 핸드셰이크 실패를 SNI 호스트별로 집계하고, 기준을 넘은 호스트는
+/// 일정 시간 가로채기 없이 터널링하도록 우회 목록에 등록한다.
+pub struct PinningTracker {
+    config: PinningBypassConfig,
+    pool: DatabasePool,
+    // 호스트별 최근 실패 시각
+    failures: Mutex<LruCache<String, VecDeque<Instant>>>,
+    // 우회 호스트 (None이면 영구)
+    bypassed: Mutex<HashMap<String, Option<Instant>>>,
+}
+
+impl PinningTracker {
+    /// 새 추적기 생성
+    pub fn new(config: &PinningBypassConfig, pool: DatabasePool) -> Self {
+        Self {
+            config: config.clone(),
+            pool,
+            failures: Mutex::new(LruCache::new(MAX_TRACKED_HOSTS)),
+            bypassed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// DB에서 유효한 우회 목록 로드 (관리자 영구 등록 포함)
+    pub async fn load(&self) -> Result<()> {
+        let conn = self.pool.get_connection().await?;
+        let rows = conn.query(tls_intercept_bypass::SELECT_ACTIVE, &[]).await?;
+
+        let now = Instant::now();
+        let utc_now = Utc::now();
+        let mut loaded = HashMap::with_capacity(rows.len());
+        for row in rows {
+            let host: String = row.get(0);
+            let expires_at: Option<DateTime<Utc>> = row.get(1);
+            let deadline = expires_at.map(|at| {
+                let remaining = (at - utc_now).to_std().unwrap_or_default();
+                now + remaining
+            });
+            loaded.insert(host.to_lowercase(), deadline);
+        }
+
+        match self.bypassed.lock() {
+            Ok(mut guard) => {
+                info!("TLS 가로채기 우회 호스트 {}개 로드", loaded.len());
+                *guard = loaded;
+            }
+            Err(e) => error!("bypassed Mutex 잠금 실패 (load): {e}"),
+        }
+        Ok(())
+    }
+
+    /// 주기적으로 DB 우회 목록 재로드
+    pub fn spawn_refresh(self: std::sync::Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.load().await {
+                    warn!("TLS 가로채기 우회 목록 재로드 실패: {e}");
+                }
+            }
+        });
+    }
+
+    /// 가로채기 우회 여부
+    pub fn is_bypassed(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+        match self.bypassed.lock() {
+            Ok(mut guard) => match guard.get(&host) {
+                Some(None) => true,
+                Some(Some(deadline)) if *deadline > Instant::now() => true,
+                Some(Some(_)) => {
+                    debug!("TLS 가로채기 우회 만료: {host}");
+                    guard.remove(&host);
+                    false
+                }
+                None => false,
+            },
+            Err(e) => {
+                error!("bypassed Mutex 잠금 실패 (is_bypassed): {e}");
+                false
+            }
+        }
+    }
+
+    /// 가로채기 핸드셰이크 성공 (실패 이력 초기화)
+    pub fn record_success(&self, host: &str) {
+        if let Ok(mut guard) = self.failures.lock() {
+            guard.pop(&host.to_lowercase());
+        }
+    }
+
+    /// 가로채기 핸드셰이크 실패 기록 (기준 도달시 우회 등록)
+    pub fn record_failure(&self, host: &str) {
+        if !self.config.enabled || host.is_empty() {
+            return;
+        }
+        let host = host.to_lowercase();
+        let now = Instant::now();
+        let window = Duration::from_secs(self.config.window_seconds);
+
+        let failure_count = match self.failures.lock() {
+            Ok(mut guard) => {
+                let history = guard.get_or_insert_mut(host.clone(), VecDeque::new);
+                history.push_back(now);
+                while history
+                    .front()
+                    .is_some_and(|at| now.duration_since(*at) > window)
+                {
+                    history.pop_front();
+                }
+                let count = history.len();
+                if count < self.config.failure_threshold as usize {
+                    debug!("TLS 가로채기 핸드셰이크 실패 {host}: {count}회");
+                    return;
+                }
+                guard.pop(&host);
+                count
+            }
+            Err(e) => {
+                error!("failures Mutex 잠금 실패 (record_failure): {e}");
+                return;
+            }
+        };
+
+        let ttl = Duration::from_secs(self.config.ttl_seconds);
+        match self.bypassed.lock() {
+            Ok(mut guard) => {
+                guard.insert(host.clone(), Some(now + ttl));
+            }
+            Err(e) => {
+                error!("bypassed Mutex 잠금 실패 (record_failure): {e}");
+                return;
+            }
+        }
+        warn!(
+            "인증서 고정 의심 호스트 가로채기 우회: {host} ({failure_count}회 실패, {}초)",
+            self.config.ttl_seconds
+        );
+
+        // 관리자 검토용 DB 기록
+        let pool = self.pool.clone();
+        let expires_at = Utc::now() + ttl;
+        tokio::spawn(async move {
+            let failure_count = i32::try_from(failure_count).unwrap_or(i32::MAX);
+            let description = "핸드셰이크 반복 실패로 자동 등록".to_string();
+            let result = match pool.get_connection().await {
+                Ok(conn) => conn
+                    .execute(
+                        tls_intercept_bypass::INSERT,
+                        &[&host, &failure_count, &expires_at, &description],
+                    )
+                    .await
+                    .map(|_| ()),
+                Err(e) => {
+                    error!("TLS 가로채기 우회 기록용 연결 가져오기 실패: {e}");
+                    return;
+                }
+            };
+            if let Err(e) = result {
+                error!("TLS 가로채기 우회 기록 저장 실패: {e}");
+            }
+        });
+    }
+}
+
+/// 클라이언트가 인증서를 거부한 핸드셰이크 실패인지 확인
+///
+/// 인증서 고정 클라이언트는 `bad_certificate`, `unknown_ca`, `certificate_unknown`
+/// 경고로 핸드셰이크를 끊으므로 이 경우만 우회 판단에 반영한다.
+pub fn is_certificate_rejection(err: &io::Error) -> bool {
+    matches!(
+        err.get_ref()
+            .and_then(|inner| inner.downcast_ref::<rustls::Error>()),
+        Some(rustls::Error::AlertReceived(
+            AlertDescription::BadCertificate
+                | AlertDescription::UnknownCA
+                | AlertDescription::CertificateUnknown
+        ))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(description: AlertDescription) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            rustls::Error::AlertReceived(description),
+        )
+    }
+
+    #[test]
+    fn certificate_alerts_are_rejections() {
+        assert!(is_certificate_rejection(&alert(
+            AlertDescription::BadCertificate
+        )));
+        assert!(is_certificate_rejection(&alert(
+            AlertDescription::UnknownCA
+        )));
+        assert!(is_certificate_rejection(&alert(
+            AlertDescription::CertificateUnknown
+        )));
+    }
+
+    #[test]
+    fn other_failures_are_not_rejections() {
+        // 다른 경고, 프로토콜 오류, 연결 끊김은 인증서 고정으로 보지 않음
+        assert!(!is_certificate_rejection(&alert(
+            AlertDescription::ProtocolVersion
+        )));
+        assert!(!is_certificate_rejection(&io::Error::new(
+            io::ErrorKind::InvalidData,
+            rustls::Error::PeerMisbehaved(rustls::PeerMisbehaved::MissingKeyShare),
+        )));
+        assert!(!is_certificate_rejection(&io::Error::from(
+            io::ErrorKind::UnexpectedEof
+        )));
+    }
+}
@@ -0,0 +1,692 @@
+use std::future::Future;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use chrono::DateTime;
+use hyper::Uri;
+use hyper::http::uri::Scheme;
+use hyper_util::client::legacy::connect::{Connected, Connection, HttpConnector};
+use log::{debug, error, info, warn};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{ResolvesClientCert, WebPkiServerVerifier};
+use rustls::crypto::{CryptoProvider, ring};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::sign::CertifiedKey;
+use rustls::{
+    ClientConfig, DigitallySignedStruct, ProtocolVersion, RootCertStore, SignatureScheme,
+    SupportedProtocolVersion,
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::client::TlsStream;
+use tower_service::Service;
+use x509_parser::extensions::GeneralName;
+use x509_parser::parse_x509_certificate;
+
+use udss_proxy_config::Config;
+use udss_proxy_db::DatabasePool;
+use udss_proxy_db::{blocklist_notify, upstream_client_certs};
+use udss_proxy_error::{Result, config_err, tls_err};
+use udss_proxy_logging::{UpstreamTlsLog, UpstreamTlsLogger};
+
+/// 업스트림 TLS 정책 (로그 표시용 설명, 클라이언트 설정)
+struct TlsPolicy {
+    label: String,
+    config: Arc<ClientConfig>,
+}
+
+/// 업스트림 TLS 정책별 클라이언트 설정
+pub struct UpstreamTls {
+    /// 전역 정책
+    default: TlsPolicy,
+    /// 도메인별 예외 정책
+    exceptions: Vec<(String, TlsPolicy)>,
+    /// 설정 파일 클라이언트 인증서 (도메인, 인증서)
+    file_client_certs: Vec<(String, Arc<CertifiedKey>)>,
+    /// 적용중인 클라이언트 인증서 (설정 파일 + DB)
+    client_certs: RwLock<Vec<(String, Arc<CertifiedKey>)>>,
+}
+
+impl UpstreamTls {
+    /// 설정에서 업스트림 TLS 정책 생성
+    pub fn new(config: &Config) -> Result<Self> {
+        let roots = Arc::new(load_root_store(config)?);
+        let policy = &config.upstream_tls;
+
+        let default = Arc::new(build_client_config(
+            config,
+            &roots,
+            &policy.min_version,
+            &policy.cipher_suites,
+            &policy.groups,
+        )?);
+        info!(
+            "업스트림 TLS 정책: 최소 버전 {}, 암호 스위트 {}개, 그룹 {}개",
+            policy.min_version,
+            default.crypto_provider().cipher_suites.len(),
+            default.crypto_provider().kx_groups.len()
+        );
+        let default = TlsPolicy {
+            label: policy_label("default", &policy.min_version, &policy.cipher_suites),
+            config: default,
+        };
+
+        let mut exceptions = Vec::with_capacity(policy.exceptions.len());
+        for exception in &policy.exceptions {
+            let min_version = exception
+                .min_version
+                .as_ref()
+                .unwrap_or(&policy.min_version);
+            let cipher_suites = exception
+                .cipher_suites
+                .as_ref()
+                .unwrap_or(&policy.cipher_suites);
+            let client_config = build_client_config(
+                config,
+                &roots,
+                min_version,
+                cipher_suites,
+                exception.groups.as_ref().unwrap_or(&policy.groups),
+            )?;
+            info!("업스트림 TLS 예외 정책 등록: {}", exception.domain);
+            let domain = exception.domain.trim_start_matches('.').to_lowercase();
+            exceptions.push((
+                domain.clone(),
+                TlsPolicy {
+                    label: policy_label(&format!("exception {domain}"), min_version, cipher_suites),
+                    config: Arc::new(client_config),
+                },
+            ));
+        }
+
+        let mut file_client_certs = Vec::with_capacity(policy.client_certs.len());
+        for client_cert in &policy.client_certs {
+            let ssl_dir = Path::new(&config.ssl_dir);
+            let chain = CertificateDer::pem_file_iter(ssl_dir.join(&client_cert.cert_file))?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            let key = PrivateKeyDer::from_pem_file(ssl_dir.join(&client_cert.key_file))?;
+            info!("업스트림 클라이언트 인증서 등록: {}", client_cert.domain);
+            file_client_certs.push((
+                client_cert.domain.trim_start_matches('.').to_lowercase(),
+                certified_key(chain, key)?,
+            ));
+        }
+
+        Ok(Self {
+            default,
+            exceptions,
+            client_certs: RwLock::new(file_client_certs.clone()),
+            file_client_certs,
+        })
+    }
+
+    /// DB 클라이언트 인증서 로드 (설정 파일 인증서 유지)
+    pub async fn load_client_certs_from_db(&self, pool: &DatabasePool) -> Result<()> {
+        let conn = pool.get_connection().await?;
+        let rows = conn
+            .query(upstream_client_certs::SELECT_ACTIVE, &[])
+            .await?;
+
+        let mut client_certs = self.file_client_certs.clone();
+        for row in rows {
+            let domain: String = row.get(0);
+            let cert_pem: String = row.get(1);
+            let key_pem: String = row.get(2);
+
+            let loaded = CertificateDer::pem_slice_iter(cert_pem.as_bytes())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(Into::into)
+                .and_then(|chain| {
+                    let key = PrivateKeyDer::from_pem_slice(key_pem.as_bytes())?;
+                    certified_key(chain, key)
+                });
+            match loaded {
+                Ok(key) => {
+                    client_certs.push((domain.trim_start_matches('.').to_lowercase(), key));
+                }
+                Err(e) => error!("업스트림 클라이언트 인증서 로드 실패 ({domain}): {e}"),
+            }
+        }
+
+        info!("업스트림 클라이언트 인증서 {}개 적용", client_certs.len());
+        match self.client_certs.write() {
+            Ok(mut guard) => *guard = client_certs,
+            Err(e) => error!("client_certs RwLock 쓰기 잠금 실패: {e}"),
+        }
+        Ok(())
+    }
+
+    /// DB 클라이언트 인증서 변경 알림 수신시 재로드 (알림 연결이 끊기면 주기적 재로드)
+    pub fn spawn_reloader(self: Arc<Self>, pool: DatabasePool, poll_interval: Duration) {
+        tokio::spawn(async move {
+            loop {
+                match pool.listen(blocklist_notify::CHANNEL).await {
+                    Ok(mut listener) => {
+                        // 연결이 끊긴 동안의 변경 반영
+                        if let Err(e) = self.load_client_certs_from_db(&pool).await {
+                            error!("업스트림 클라이언트 인증서 재로드 실패: {e}");
+                        }
+                        while let Some(table) = listener.recv().await {
+                            if table != upstream_client_certs::TABLE {
+                                continue;
+                            }
+                            listener.drain();
+                            info!("업스트림 클라이언트 인증서 변경 감지, 재로드");
+                            if let Err(e) = self.load_client_certs_from_db(&pool).await {
+                                error!("업스트림 클라이언트 인증서 재로드 실패: {e}");
+                            }
+                        }
+                        warn!("업스트림 클라이언트 인증서 알림 연결 끊김, 주기적 재로드로 전환");
+                    }
+                    Err(e) => warn!("업스트림 클라이언트 인증서 알림 연결 실패: {e}"),
+                }
+
+                // 알림 재연결 전까지 폴링
+                tokio::time::sleep(poll_interval).await;
+                if let Err(e) = self.load_client_certs_from_db(&pool).await {
+                    error!("업스트림 클라이언트 인증서 주기적 재로드 실패: {e}");
+                }
+            }
+        });
+    }
+
+    /// 호스트에 적용할 정책 (가장 구체적으로 일치하는 예외 정책 우선)
+    fn policy(&self, host: &str) -> &TlsPolicy {
+        self.exceptions
+            .iter()
+            .filter(|(domain, _)| domain_matches(host, domain))
+            .max_by_key(|(domain, _)| domain.len())
+            .map_or(&self.default, |(_, policy)| policy)
+    }
+
+    /// 호스트에 적용할 정책 설명 (업스트림 TLS 로그용)
+    pub fn policy_label(&self, host: &str) -> &str {
+        &self.policy(&host.to_lowercase()).label
+    }
+
+    /// 호스트에 적용할 클라이언트 설정
+    pub fn client_config(&self, host: &str) -> Arc<ClientConfig> {
+        let host = host.to_lowercase();
+        let config = self.policy(&host).config.clone();
+
+        match self.client_cert(&host) {
+            Some(key) => {
+                debug!("업스트림 클라이언트 인증서 사용: {host}");
+                // 세션 재개 저장소는 원본 설정과 공유
+                let mut config = (*config).clone();
+                config.client_auth_cert_resolver = Arc::new(FixedClientCert(key));
+                Arc::new(config)
+            }
+            None => config,
+        }
+    }
+
+    /// 호스트에 가장 구체적으로 일치하는 클라이언트 인증서
+    fn client_cert(&self, host: &str) -> Option<Arc<CertifiedKey>> {
+        match self.client_certs.read() {
+            Ok(guard) => guard
+                .iter()
+                .filter(|(domain, _)| domain_matches(host, domain))
+                .max_by_key(|(domain, _)| domain.len())
+                .map(|(_, key)| key.clone()),
+            Err(e) => {
+                error!("client_certs RwLock 읽기 잠금 실패: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// 정책 설명 (`default: min TLS 1.2, cipher suites default`)
+fn policy_label(name: &str, min_version: &str, cipher_suites: &[String]) -> String {
+    let cipher_suites = if cipher_suites.is_empty() {
+        "default".to_string()
+    } else {
+        cipher_suites.join(",")
+    };
+    format!("{name}: min TLS {min_version}, cipher suites {cipher_suites}")
+}
+
+/// 인증서 체인과 개인키로 서명 가능한 인증서 생성
+fn certified_key(
+    chain: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+) -> Result<Arc<CertifiedKey>> {
+    if chain.is_empty() {
+        return Err(config_err("클라이언트 인증서 체인이 비어있습니다."));
+    }
+    let signing_key = ring::default_provider()
+        .key_provider
+        .load_private_key(key)?;
+    Ok(Arc::new(CertifiedKey::new(chain, signing_key)))
+}
+
+/// 항상 같은 인증서를 제시하는 클라이언트 인증서 선택기
+#[derive(Debug)]
+struct FixedClientCert(Arc<CertifiedKey>);
+
+impl ResolvesClientCert for FixedClientCert {
+    fn resolve(
+        &self,
+        _root_hint_subjects: &[&[u8]],
+        _sigschemes: &[SignatureScheme],
+    ) -> Option<Arc<CertifiedKey>> {
+        Some(self.0.clone())
+    }
+
+    fn has_certs(&self) -> bool {
+        true
+    }
+}
+
+/// 도메인 또는 하위 도메인 일치 여부
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// 신뢰 루트 인증서 로드 (시스템 + `ssl_dir/trusted_certs`)
+fn load_root_store(config: &Config) -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+
+    let native = rustls_native_certs::load_native_certs();
+    for e in &native.errors {
+        warn!("시스템 루트 인증서 로드 오류: {e}");
+    }
+    let (added, ignored) = roots.add_parsable_certificates(native.certs);
+    debug!("시스템 루트 인증서 {added}개 로드 ({ignored}개 무시)");
+
+    let trusted_dir = Path::new(&config.ssl_dir).join("trusted_certs");
+    if let Ok(entries) = std::fs::read_dir(&trusted_dir) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if !path
+                .extension()
+                .is_some_and(|ext| ext == "pem" || ext == "crt")
+            {
+                continue;
+            }
+            for cert in CertificateDer::pem_file_iter(&path)? {
+                roots.add(cert?)?;
+            }
+            info!("신뢰 인증서 추가: {}", path.display());
+        }
+    }
+
+    if roots.is_empty() && config.tls_verify_certificate {
+        return Err(config_err("업스트림 TLS 검증용 루트 인증서가 없습니다."));
+    }
+
+    Ok(roots)
+}
+
+/// 정책에 맞는 rustls 클라이언트 설정 생성
+fn build_client_config(
+    config: &Config,
+    roots: &Arc<RootCertStore>,
+    min_version: &str,
+    cipher_suites: &[String],
+    groups: &[String],
+) -> Result<ClientConfig> {
+    let provider = Arc::new(build_provider(cipher_suites, groups)?);
+
+    let versions: &[&'static SupportedProtocolVersion] = match min_version {
+        "1.2" => &[&rustls::version::TLS13, &rustls::version::TLS12],
+        "1.3" => &[&rustls::version::TLS13],
+        other => {
+            return Err(config_err(format!(
+                "지원하지 않는 최소 TLS 버전: {other} (1.2, 1.3 지원)"
+            )));
+        }
+    };
+
+    let verifier = Arc::new(PolicyVerifier {
+        inner: WebPkiServerVerifier::builder_with_provider(roots.clone(), provider.clone())
+            .build()
+            .map_err(|e| tls_err(format!("서버 인증서 검증기 생성 실패: {e}")))?,
+        verify: config.tls_verify_certificate,
+        skip_internal_ip: config.disable_verify_internal_ip,
+    });
+
+    let mut client_config = ClientConfig::builder_with_provider(provider)
+        .with_protocol_versions(versions)?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier)
+        .with_no_client_auth();
+    client_config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(client_config)
+}
+
+/// 허용 암호 스위트/그룹으로 제한한 암호 제공자
+fn build_provider(cipher_suites: &[String], groups: &[String]) -> Result<CryptoProvider> {
+    let mut provider = ring::default_provider();
+
+    if !cipher_suites.is_empty() {
+        provider.cipher_suites = cipher_suites
+            .iter()
+            .map(|name| {
+                ring::ALL_CIPHER_SUITES
+                    .iter()
+                    .find(|suite| suite.suite().as_str() == Some(name.as_str()))
+                    .copied()
+                    .ok_or_else(|| config_err(format!("알 수 없는 암호 스위트: {name}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+    }
+
+    if !groups.is_empty() {
+        provider.kx_groups = groups
+            .iter()
+            .map(|name| {
+                ring::ALL_KX_GROUPS
+                    .iter()
+                    .find(|group| group.name().as_str() == Some(name.as_str()))
+                    .copied()
+                    .ok_or_else(|| config_err(format!("알 수 없는 키 교환 그룹: {name}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+    }
+
+    Ok(provider)
+}
+
+/// 설정에 따라 인증서 검증을 생략할 수 있는 검증기
+#[derive(Debug)]
+struct PolicyVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    /// 인증서 검증 여부 (`tls_verify_certificate`)
+    verify: bool,
+    /// 내부 IP 검증 생략 여부 (`disable_verify_internal_ip`)
+    skip_internal_ip: bool,
+}
+
+impl ServerCertVerifier for PolicyVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        if !self.verify {
+            return Ok(ServerCertVerified::assertion());
+        }
+        if self.skip_internal_ip
+            && let ServerName::IpAddress(ip) = server_name
+            && is_internal_ip(IpAddr::from(*ip))
+        {
+            debug!("내부 IP 인증서 검증 생략: {server_name:?}");
+            return Ok(ServerCertVerified::assertion());
+        }
+
+        self.inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// 내부(사설/루프백/링크로컬) IP 여부
+fn is_internal_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local(),
+        IpAddr::V6(v6) => {
+            v6.is_loopback()
+                || (v6.segments()[0] & 0xfe00) == 0xfc00
+                || (v6.segments()[0] & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+/// 업스트림 연결 정보 (응답 extensions로 전달)
+#[derive(Debug, Clone)]
+pub struct UpstreamInfo {
+    /// 원 서버 주소
+    pub remote_addr: Option<SocketAddr>,
+}
+
+/// 업스트림 스트림 (평문/TLS)
+pub enum UpstreamStream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl UpstreamStream {
+    fn tcp(&self) -> &TcpStream {
+        match self {
+            UpstreamStream::Plain(stream) => stream,
+            UpstreamStream::Tls(stream) => stream.get_ref().0,
+        }
+    }
+}
+
+impl AsyncRead for UpstreamStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            UpstreamStream::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+            UpstreamStream::Tls(stream) => Pin::new(stream.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for UpstreamStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            UpstreamStream::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+            UpstreamStream::Tls(stream) => Pin::new(stream.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            UpstreamStream::Plain(stream) => Pin::new(stream).poll_flush(cx),
+            UpstreamStream::Tls(stream) => Pin::new(stream.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            UpstreamStream::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
+            UpstreamStream::Tls(stream) => Pin::new(stream.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
+
+impl Connection for UpstreamStream {
+    fn connected(&self) -> Connected {
+        Connected::new().extra(UpstreamInfo {
+            remote_addr: self.tcp().peer_addr().ok(),
+        })
+    }
+}
+
+/// 업스트림 커넥터 (http는 평문, https는 정책 적용 TLS)
+#[derive(Clone)]
+pub struct UpstreamConnector {
+    http: HttpConnector,
+    tls: Arc<UpstreamTls>,
+    logger: UpstreamTlsLogger,
+}
+
+impl UpstreamConnector {
+    /// 커넥터 생성
+    pub fn new(mut http: HttpConnector, tls: Arc<UpstreamTls>, logger: UpstreamTlsLogger) -> Self {
+        http.enforce_http(false);
+        Self { http, tls, logger }
+    }
+}
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+impl Service<Uri> for UpstreamConnector {
+    type Response = hyper_util::rt::TokioIo<UpstreamStream>;
+    type Error = BoxError;
+    type Future =
+        Pin<Box<dyn Future<Output = std::result::Result<Self::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), BoxError>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let is_https = uri.scheme() == Some(&Scheme::HTTPS);
+        let host = uri
+            .host()
+            .unwrap_or_default()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let connecting = self.http.call(uri);
+        let tls = self.tls.clone();
+        let logger = self.logger.clone();
+
+        Box::pin(async move {
+            let tcp = connecting.await?.into_inner();
+            if !is_https {
+                return Ok(hyper_util::rt::TokioIo::new(UpstreamStream::Plain(tcp)));
+            }
+
+            let server_name = ServerName::try_from(host.clone())?;
+            let policy = tls.policy_label(&host).to_string();
+            let target_ip = tcp
+                .peer_addr()
+                .map(|addr| addr.ip().to_string())
+                .unwrap_or_default();
+            let connector = TlsConnector::from(tls.client_config(&host));
+            let stream = match connector.connect(server_name, tcp).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    // 최소 버전, 암호 스위트 정책으로 거부된 핸드셰이크도 기록
+                    warn!("업스트림 TLS 핸드셰이크 실패 {host} ({policy}): {e}");
+                    logger.log(UpstreamTlsLog {
+                        host,
+                        target_ip,
+                        protocol_version: "none".to_string(),
+                        cipher_suite: "none".to_string(),
+                        policy: Some(policy),
+                        error: Some(e.to_string()),
+                        ..UpstreamTlsLog::default()
+                    });
+                    return Err(e.into());
+                }
+            };
+            logger.log(handshake_log(&host, policy, &stream));
+
+            Ok(hyper_util::rt::TokioIo::new(UpstreamStream::Tls(Box::new(
+                stream,
+            ))))
+        })
+    }
+}
+
+/// 핸드셰이크 결과를 로그 항목으로 변환
+fn handshake_log(host: &str, policy: String, stream: &TlsStream<TcpStream>) -> UpstreamTlsLog {
+    let (tcp, conn) = stream.get_ref();
+
+    let protocol_version = match conn.protocol_version() {
+        Some(ProtocolVersion::TLSv1_3) => "TLSv1.3".to_string(),
+        Some(ProtocolVersion::TLSv1_2) => "TLSv1.2".to_string(),
+        Some(other) => format!("{other:?}"),
+        None => "unknown".to_string(),
+    };
+    let cipher_suite = conn
+        .negotiated_cipher_suite()
+        .and_then(|suite| suite.suite().as_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let mut entry = UpstreamTlsLog {
+        host: host.to_string(),
+        target_ip: tcp
+            .peer_addr()
+            .map(|addr| addr.ip().to_string())
+            .unwrap_or_default(),
+        protocol_version,
+        cipher_suite,
+        policy: Some(policy),
+        ..UpstreamTlsLog::default()
+    };
+
+    // 서버 인증서 정보
+    if let Some(cert) = conn.peer_certificates().and_then(|certs| certs.first())
+        && let Ok((_, parsed)) = parse_x509_certificate(cert.as_ref())
+    {
+        entry.subject = Some(parsed.subject().to_string());
+        entry.issuer = Some(parsed.issuer().to_string());
+        entry.not_before = DateTime::from_timestamp(parsed.validity().not_before.timestamp(), 0);
+        entry.not_after = DateTime::from_timestamp(parsed.validity().not_after.timestamp(), 0);
+        if let Ok(Some(san)) = parsed.subject_alternative_name() {
+            let names = san
+                .value
+                .general_names
+                .iter()
+                .map(|name| match name {
+                    GeneralName::DNSName(dns) => format!("DNS:{dns}"),
+                    GeneralName::IPAddress(bytes) => format_ip_san(bytes),
+                    other => other.to_string(),
+                })
+                .collect::<Vec<_>>();
+            entry.san = Some(names.join(", "));
+        }
+    }
+
+    debug!(
+        "업스트림 TLS 핸드셰이크 {host}: {} {}",
+        entry.protocol_version, entry.cipher_suite
+    );
+    entry
+}
+
+/// IP SAN 표기
+fn format_ip_san(bytes: &[u8]) -> String {
+    match bytes.len() {
+        4 => format!(
+            "IP:{}",
+            IpAddr::from([bytes[0], bytes[1], bytes[2], bytes[3]])
+        ),
+        16 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(bytes);
+            format!("IP:{}", IpAddr::from(octets))
+        }
+        _ => "IP:invalid".to_string(),
+    }
+}